@@ -0,0 +1,127 @@
+use {
+  bitcoin::{
+    opcodes,
+    script::{self, PushBytesBuf},
+    Network, Witness,
+  },
+  criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput},
+  mockcore::TransactionTemplate,
+  ord::{options::Options, settings::Settings, Index},
+  ordinals::{Etching, Runestone},
+  std::fs,
+  tempfile::TempDir,
+};
+
+const BLOCKS: usize = 20;
+const TRANSACTIONS_PER_BLOCK: usize = 50;
+
+fn envelope(payload: &[&[u8]]) -> Witness {
+  let mut builder = script::Builder::new()
+    .push_opcode(opcodes::OP_FALSE)
+    .push_opcode(opcodes::all::OP_IF);
+
+  for data in payload {
+    let mut buf = PushBytesBuf::new();
+    buf.extend_from_slice(data).unwrap();
+    builder = builder.push_slice(buf);
+  }
+
+  let script = builder.push_opcode(opcodes::all::OP_ENDIF).into_script();
+
+  Witness::from_slice(&[script.into_bytes(), Vec::new()])
+}
+
+// A chain of coinbase-only blocks followed by blocks in which every
+// transaction spends one of those coinbases, reveals an inscription and
+// etches a rune.
+fn synthetic_chain() -> mockcore::Handle {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  core.mine_blocks((BLOCKS * TRANSACTIONS_PER_BLOCK).try_into().unwrap());
+
+  let runestone = Runestone {
+    etching: Some(Etching {
+      premine: Some(1000),
+      ..Default::default()
+    }),
+    ..Default::default()
+  }
+  .encipher();
+
+  for block in 0..BLOCKS {
+    for transaction in 0..TRANSACTIONS_PER_BLOCK {
+      let witness = envelope(&[
+        b"ord",
+        &[1],
+        b"text/plain;charset=utf-8",
+        &[],
+        format!("block {block} transaction {transaction}").as_bytes(),
+      ]);
+
+      core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          block * TRANSACTIONS_PER_BLOCK + transaction + 1,
+          0,
+          0,
+          witness,
+        )],
+        op_return: Some(runestone.clone()),
+        outputs: 1,
+        ..Default::default()
+      });
+    }
+
+    core.mine_blocks(1);
+  }
+
+  core
+}
+
+fn open(core: &mockcore::Handle, tempdir: &TempDir) -> Index {
+  let cookie_file = tempdir.path().join("cookie");
+
+  fs::write(&cookie_file, "username:password").unwrap();
+
+  let options = <Options as clap::Parser>::parse_from([
+    "ord",
+    "--regtest",
+    "--index-runes",
+    "--index-sats",
+    "--bitcoin-rpc-url",
+    &core.url(),
+    "--cookie-file",
+    cookie_file.to_str().unwrap(),
+    "--datadir",
+    tempdir.path().to_str().unwrap(),
+  ]);
+
+  Index::open(&Settings::from_options(options).or_defaults().unwrap()).unwrap()
+}
+
+fn initial_sync(c: &mut Criterion) {
+  let core = synthetic_chain();
+
+  let mut group = c.benchmark_group("initial_sync");
+
+  group.sample_size(10);
+  group.throughput(Throughput::Elements(
+    (BLOCKS * TRANSACTIONS_PER_BLOCK).try_into().unwrap(),
+  ));
+
+  group.bench_function("index_runes_index_sats", |b| {
+    b.iter_batched(
+      || {
+        let tempdir = TempDir::new().unwrap();
+        let index = open(&core, &tempdir);
+        (tempdir, index)
+      },
+      |(_tempdir, index)| index.update().unwrap(),
+      BatchSize::PerIteration,
+    )
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, initial_sync);
+criterion_main!(benches);
@@ -1,93 +1,195 @@
-use {colored::Colorize, reqwest::blocking::get, std::process};
+use {
+  colored::Colorize,
+  reqwest::{
+    blocking::{get, Client},
+    header,
+  },
+  serde_json::Value,
+  std::{collections::BTreeMap, process},
+};
+
+// media types whose previews have their own content security policy. they
+// are audited with the latest inscription of each type on each server.
+const PREVIEW_MEDIA_TYPES: &[&str] = &["application/json", "audio/midi", "model/stl", "text/csv"];
+
+// pages of the latest inscriptions to search for inscriptions of
+// `PREVIEW_MEDIA_TYPES`
+const MAX_PAGES: u32 = 10;
 
 const SERVERS: &[(&str, &str, &str)] = &[
   (
     "regtest.ordinals.net",
-    "/content/41bf99a297ca79d181160a91fc0efc8a71170ee24b87783c9c11b0fcbe23615fi0",
-    "https://regtest.ordinals.com/content/",
+    "41bf99a297ca79d181160a91fc0efc8a71170ee24b87783c9c11b0fcbe23615fi0",
+    "https://regtest.ordinals.com",
   ),
   (
     "regtest.ordinals.com",
-    "/content/41bf99a297ca79d181160a91fc0efc8a71170ee24b87783c9c11b0fcbe23615fi0",
-    "https://regtest.ordinals.com/content/",
+    "41bf99a297ca79d181160a91fc0efc8a71170ee24b87783c9c11b0fcbe23615fi0",
+    "https://regtest.ordinals.com",
   ),
   (
     "signet.ordinals.net",
-    "/content/7e1bc3b56b872aaf4d1aaf1565fac72182313c9142b207f9398afe263e234135i0",
-    "https://signet.ordinals.com/content/",
+    "7e1bc3b56b872aaf4d1aaf1565fac72182313c9142b207f9398afe263e234135i0",
+    "https://signet.ordinals.com",
   ),
   (
     "signet.ordinals.com",
-    "/content/7e1bc3b56b872aaf4d1aaf1565fac72182313c9142b207f9398afe263e234135i0",
-    "https://signet.ordinals.com/content/",
+    "7e1bc3b56b872aaf4d1aaf1565fac72182313c9142b207f9398afe263e234135i0",
+    "https://signet.ordinals.com",
   ),
   (
     "testnet.ordinals.net",
-    "/content/0a1b4e4acf89686e4d012561014041bffd57a62254486f24cb5b0a216c04f102i0",
-    "https://testnet.ordinals.com/content/",
+    "0a1b4e4acf89686e4d012561014041bffd57a62254486f24cb5b0a216c04f102i0",
+    "https://testnet.ordinals.com",
   ),
   (
     "testnet.ordinals.com",
-    "/content/0a1b4e4acf89686e4d012561014041bffd57a62254486f24cb5b0a216c04f102i0",
-    "https://testnet.ordinals.com/content/",
+    "0a1b4e4acf89686e4d012561014041bffd57a62254486f24cb5b0a216c04f102i0",
+    "https://testnet.ordinals.com",
   ),
   (
     "alpha.ordinals.net",
-    "/content/6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
-    "https://ordinals.com/content/",
+    "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
+    "https://ordinals.com",
   ),
   (
     "bravo.ordinals.net",
-    "/content/6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
-    "https://ordinals.com/content/",
+    "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
+    "https://ordinals.com",
   ),
   (
     "charlie.ordinals.net",
-    "/content/6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
-    "https://ordinals.com/content/",
+    "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
+    "https://ordinals.com",
   ),
   (
     "ordinals.com",
-    "/content/6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
-    "https://ordinals.com/content/",
+    "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0",
+    "https://ordinals.com",
   ),
 ];
 
 fn main() {
   let mut failures = 0;
 
-  for (host, path, needle) in SERVERS {
-    eprint!("GET {host}");
+  for (host, inscription, origin) in SERVERS {
+    // content may load other content and recursive endpoints
+    if !audit(host, &format!("/content/{inscription}"), |policy| {
+      policy.contains(&format!("{origin}/content/"))
+    }) {
+      failures += 1;
+    }
 
-    let response = get(format!("https://{host}{path}")).unwrap();
+    // previews, whose policy depends on the media type, may only load
+    // resources from the origin, and never from `'self'`
+    let preview = |policy: &str| policy.contains(origin) && !policy.contains("'self'");
 
-    let mut fail = false;
+    if !audit(host, &format!("/preview/{inscription}"), preview) {
+      failures += 1;
+    }
+
+    let inscriptions = find_inscriptions(host);
 
-    if !response.status().is_success() {
-      eprint!(" {}", response.status().to_string().red());
-      fail = true;
+    for media_type in PREVIEW_MEDIA_TYPES {
+      match inscriptions.get(media_type) {
+        Some(inscription) => {
+          if !audit(host, &format!("/preview/{inscription}"), preview) {
+            failures += 1;
+          }
+        }
+        None => eprintln!(
+          "{host}: no {media_type} inscription in the latest {} inscriptions {}",
+          MAX_PAGES * 100,
+          "SKIP".yellow(),
+        ),
+      }
     }
+  }
+
+  if failures > 0 {
+    process::exit(1);
+  }
+}
+
+fn audit(host: &str, path: &str, check: impl Fn(&str) -> bool) -> bool {
+  eprint!("GET {host}{path}");
+
+  let response = get(format!("https://{host}{path}")).unwrap();
+
+  let mut fail = false;
+
+  if !response.status().is_success() {
+    eprint!(" {}", response.status().to_string().red());
+    fail = true;
+  }
 
-    let headers = response.headers();
+  let headers = response.headers();
 
-    let content_security_policy = headers
-      .get("content-security-policy")
-      .map(|value| value.to_str().unwrap().to_string())
-      .unwrap_or_default();
+  let content_security_policy = headers
+    .get("content-security-policy")
+    .map(|value| value.to_str().unwrap().to_string())
+    .unwrap_or_default();
 
-    if !content_security_policy.contains(needle) {
-      fail = true;
+  if !check(&content_security_policy) {
+    fail = true;
+  }
+
+  if fail {
+    eprintln!(" {}", "FAIL".red());
+  } else {
+    eprintln!(" {}", "PASS".green());
+  }
+
+  !fail
+}
+
+// Find the latest inscription of each of `PREVIEW_MEDIA_TYPES` on `host`
+fn find_inscriptions(host: &str) -> BTreeMap<&'static str, String> {
+  let client = Client::new();
+
+  let mut inscriptions = BTreeMap::new();
+
+  for page in 0..MAX_PAGES {
+    let response = client
+      .get(format!("https://{host}/inscriptions/{page}"))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap()
+      .text()
+      .unwrap();
+
+    let listing = serde_json::from_str::<Value>(&response).unwrap();
+
+    let response = client
+      .post(format!("https://{host}/inscriptions"))
+      .header(header::ACCEPT, "application/json")
+      .header(header::CONTENT_TYPE, "application/json")
+      .body(listing["ids"].to_string())
+      .send()
+      .unwrap()
+      .text()
+      .unwrap();
+
+    for inscription in serde_json::from_str::<Vec<Value>>(&response).unwrap() {
+      let Some(content_type) = inscription["content_type"].as_str() else {
+        continue;
+      };
+
+      let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+      if let Some(media_type) = PREVIEW_MEDIA_TYPES.iter().find(|&&m| m == media_type) {
+        if let Some(id) = inscription["id"].as_str() {
+          inscriptions
+            .entry(*media_type)
+            .or_insert_with(|| id.to_string());
+        }
+      }
     }
 
-    if fail {
-      eprintln!(" {}", "FAIL".red());
-      failures += 1;
-    } else {
-      eprintln!(" {}", "PASS".green());
+    if inscriptions.len() == PREVIEW_MEDIA_TYPES.len() || listing["more"] != Value::Bool(true) {
+      break;
     }
   }
 
-  if failures > 0 {
-    process::exit(1);
-  }
+  inscriptions
 }
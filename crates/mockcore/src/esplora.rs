@@ -0,0 +1,188 @@
+use {
+  super::*,
+  serde_json::json,
+  std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::atomic::{AtomicBool, Ordering},
+  },
+};
+
+enum Response {
+  Bytes(Vec<u8>),
+  Json(Value),
+  NotFound,
+  Text(String),
+}
+
+pub(crate) struct Esplora {
+  port: u16,
+  shutdown: Arc<AtomicBool>,
+}
+
+impl Esplora {
+  pub(crate) fn spawn(state: Arc<Mutex<State>>) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let shutdown = Arc::new(AtomicBool::new(false));
+
+    {
+      let shutdown = shutdown.clone();
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          if shutdown.load(Ordering::Relaxed) {
+            break;
+          }
+
+          let Ok(stream) = stream else {
+            continue;
+          };
+
+          let state = state.clone();
+          thread::spawn(move || Self::handle(&state, stream));
+        }
+      });
+    }
+
+    Self { port, shutdown }
+  }
+
+  pub(crate) fn url(&self) -> String {
+    format!("http://127.0.0.1:{}", self.port)
+  }
+
+  fn handle(state: &Mutex<State>, mut stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+      return;
+    }
+
+    loop {
+      let mut line = String::new();
+      match reader.read_line(&mut line) {
+        Ok(0) | Err(_) => break,
+        Ok(_) if line == "\r\n" => break,
+        Ok(_) => {}
+      }
+    }
+
+    let path = request_line
+      .split_whitespace()
+      .nth(1)
+      .unwrap_or_default()
+      .trim_start_matches('/');
+
+    let (status, content_type, body) = match Self::route(&state.lock().unwrap(), path) {
+      Response::Bytes(bytes) => ("200 OK", "application/octet-stream", bytes),
+      Response::Json(value) => ("200 OK", "application/json", value.to_string().into_bytes()),
+      Response::NotFound => ("404 Not Found", "text/plain", b"not found".to_vec()),
+      Response::Text(text) => ("200 OK", "text/plain", text.into_bytes()),
+    };
+
+    let _ = write!(
+      stream,
+      "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+      body.len()
+    );
+    let _ = stream.write_all(&body);
+  }
+
+  fn route(state: &State, path: &str) -> Response {
+    let components = path.split('/').collect::<Vec<&str>>();
+
+    match components.as_slice() {
+      ["blocks", "tip", "height"] => Response::Text((state.hashes.len() - 1).to_string()),
+      ["block-height", height] => match height
+        .parse::<usize>()
+        .ok()
+        .and_then(|height| state.hashes.get(height))
+      {
+        Some(hash) => Response::Text(hash.to_string()),
+        None => Response::NotFound,
+      },
+      ["block", hash, rest @ ..] => {
+        let Some((height, hash)) = hash.parse::<BlockHash>().ok().and_then(|hash| {
+          state
+            .hashes
+            .iter()
+            .position(|candidate| *candidate == hash)
+            .map(|height| (height, hash))
+        }) else {
+          return Response::NotFound;
+        };
+
+        let block = &state.blocks[&hash];
+
+        match rest {
+          [] => Response::Json(json!({
+            "bits": block.header.bits.to_consensus(),
+            "difficulty": 0.0,
+            "height": height,
+            "id": hash,
+            "mediantime": block.header.time,
+            "merkle_root": block.header.merkle_root,
+            "nonce": block.header.nonce,
+            "previousblockhash": height
+              .checked_sub(1)
+              .map(|_| block.header.prev_blockhash),
+            "size": serialize(block).len(),
+            "timestamp": block.header.time,
+            "tx_count": block.txdata.len(),
+            "version": block.header.version.to_consensus(),
+            "weight": block.weight().to_wu(),
+          })),
+          ["header"] => Response::Text(hex::encode(serialize(&block.header))),
+          ["raw"] => Response::Bytes(serialize(block)),
+          _ => Response::NotFound,
+        }
+      }
+      ["tx", txid, rest @ ..] => {
+        let Some(transaction) = txid.parse::<Txid>().ok().and_then(|txid| {
+          state
+            .transactions
+            .get(&txid)
+            .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid))
+        }) else {
+          return Response::NotFound;
+        };
+
+        let txid = transaction.txid();
+
+        match rest {
+          ["raw"] => Response::Bytes(serialize(transaction)),
+          ["status"] => {
+            let height = state
+              .txid_to_block_height
+              .get(&txid)
+              .filter(|height| state.hashes.len() > usize::try_from(**height).unwrap());
+
+            Response::Json(json!({
+              "block_hash": height.map(|height| state.hashes[usize::try_from(*height).unwrap()]),
+              "block_height": height,
+              "confirmed": height.is_some(),
+            }))
+          }
+          ["outspend", vout] => match vout.parse::<u32>() {
+            Ok(vout) if usize::try_from(vout).unwrap() < transaction.output.len() => {
+              Response::Json(json!({
+                "spent": !state.utxos.contains_key(&OutPoint { txid, vout }),
+              }))
+            }
+            _ => Response::NotFound,
+          },
+          _ => Response::NotFound,
+        }
+      }
+      _ => Response::NotFound,
+    }
+  }
+}
+
+impl Drop for Esplora {
+  fn drop(&mut self) {
+    self.shutdown.store(true, Ordering::Relaxed);
+    let _ = TcpStream::connect(("127.0.0.1", self.port));
+  }
+}
@@ -27,6 +27,7 @@ use {
     ListWalletDirResult, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
     Timestamp, WalletProcessPsbtResult, WalletTxInfo,
   },
+  esplora::Esplora,
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
   serde::{Deserialize, Serialize},
@@ -44,11 +45,13 @@ use {
 };
 
 mod api;
+mod esplora;
 mod server;
 mod state;
 
 pub fn builder() -> Builder {
   Builder {
+    esplora: false,
     fail_lock_unspent: false,
     network: Network::Bitcoin,
     version: 240000,
@@ -56,12 +59,17 @@ pub fn builder() -> Builder {
 }
 
 pub struct Builder {
+  esplora: bool,
   fail_lock_unspent: bool,
   network: Network,
   version: usize,
 }
 
 impl Builder {
+  pub fn esplora(self, esplora: bool) -> Self {
+    Self { esplora, ..self }
+  }
+
   pub fn fail_lock_unspent(self, fail_lock_unspent: bool) -> Self {
     Self {
       fail_lock_unspent,
@@ -114,8 +122,11 @@ impl Builder {
 
     fs::write(tempdir.path().join(".cookie"), "username:password").unwrap();
 
+    let esplora = self.esplora.then(|| Esplora::spawn(state.clone()));
+
     Handle {
       close_handle: Some(close_handle),
+      esplora,
       tempdir,
       port,
       state,
@@ -191,6 +202,7 @@ impl<'a> Default for TransactionTemplate<'a> {
 
 pub struct Handle {
   close_handle: Option<CloseHandle>,
+  esplora: Option<Esplora>,
   port: u16,
   state: Arc<Mutex<State>>,
   tempdir: TempDir,
@@ -201,6 +213,14 @@ impl Handle {
     format!("http://127.0.0.1:{}", self.port)
   }
 
+  pub fn esplora_url(&self) -> String {
+    self
+      .esplora
+      .as_ref()
+      .expect("mock Esplora server not enabled")
+      .url()
+  }
+
   pub fn address(&self, output: OutPoint) -> Address {
     let state = self.state();
 
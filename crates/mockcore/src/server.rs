@@ -676,7 +676,7 @@ impl Api for Server {
         Some(transaction) => Ok(
           serde_json::to_value(GetRawTransactionResult {
             in_active_chain: Some(true),
-            hex: Vec::new(),
+            hex: serialize(transaction),
             txid: Txid::all_zeros(),
             hash: Wtxid::all_zeros(),
             size: 0,
@@ -1,6 +1,7 @@
 use super::*;
 
 #[derive(Serialize, Eq, PartialEq, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Cenotaph {
   pub etching: Option<Rune>,
   pub flaw: Option<Flaw>,
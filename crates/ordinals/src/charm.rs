@@ -1,6 +1,8 @@
 use super::*;
 
 #[derive(Copy, Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename_all = "lowercase"))]
 pub enum Charm {
   Coin = 0,
   Cursed = 1,
@@ -1,6 +1,7 @@
 use super::*;
 
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Edict {
   pub id: RuneId,
   pub amount: u128,
@@ -39,6 +39,26 @@ fn default<T: Default>() -> T {
   Default::default()
 }
 
+// Implements `JsonSchema` for a type serialized as a string matching `pattern`
+#[cfg(feature = "schemars")]
+macro_rules! string_schema {
+  ($type:ty, $name:literal, $description:literal, $pattern:literal) => {
+    impl schemars::JsonSchema for $type {
+      fn schema_name() -> std::borrow::Cow<'static, str> {
+        $name.into()
+      }
+
+      fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+          "type": "string",
+          "description": $description,
+          "pattern": $pattern,
+        })
+      }
+    }
+  };
+}
+
 mod artifact;
 mod cenotaph;
 mod charm;
@@ -1,6 +1,7 @@
 use super::*;
 
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Pile {
   pub amount: u128,
   pub divisibility: u8,
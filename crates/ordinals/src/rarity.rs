@@ -1,6 +1,10 @@
 use super::*;
 
-#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, DeserializeFromStr, SerializeDisplay)]
+#[derive(
+  Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, DeserializeFromStr, SerializeDisplay,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(rename_all = "lowercase"))]
 pub enum Rarity {
   Common,
   Uncommon,
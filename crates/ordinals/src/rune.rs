@@ -5,6 +5,9 @@ use super::*;
 )]
 pub struct Rune(pub u128);
 
+#[cfg(feature = "schemars")]
+string_schema!(Rune, "RuneName", "Rune name without spacers.", "^[A-Z]+$");
+
 impl Rune {
   const RESERVED: u128 = 6402364363415443603228541259936211926;
 
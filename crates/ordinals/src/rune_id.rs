@@ -18,6 +18,14 @@ pub struct RuneId {
   pub tx: u32,
 }
 
+#[cfg(feature = "schemars")]
+string_schema!(
+  RuneId,
+  "RuneId",
+  "Rune ID, the etching block height and transaction index.",
+  "^[0-9]+:[0-9]+$"
+);
+
 impl RuneId {
   pub fn new(block: u64, tx: u32) -> Option<RuneId> {
     let id = RuneId { block, tx };
@@ -2,6 +2,7 @@ use {super::*, std::num::ParseFloatError};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Ord, PartialOrd, Deserialize, Serialize)]
 #[serde(transparent)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Sat(pub u64);
 
 impl Sat {
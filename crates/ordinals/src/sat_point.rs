@@ -27,6 +27,14 @@ pub struct SatPoint {
   pub offset: u64,
 }
 
+#[cfg(feature = "schemars")]
+string_schema!(
+  SatPoint,
+  "SatPoint",
+  "Location of a sat, as `<TXID>:<VOUT>:<OFFSET>`.",
+  "^[0-9a-f]{64}:[0-9]+:[0-9]+$"
+);
+
 impl Display for SatPoint {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     write!(f, "{}:{}", self.outpoint, self.offset)
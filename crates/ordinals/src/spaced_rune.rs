@@ -8,6 +8,14 @@ pub struct SpacedRune {
   pub spacers: u32,
 }
 
+#[cfg(feature = "schemars")]
+string_schema!(
+  SpacedRune,
+  "SpacedRune",
+  "Rune name with spacers.",
+  "^[A-Z](•?[A-Z])*$"
+);
+
 impl SpacedRune {
   pub fn new(rune: Rune, spacers: u32) -> Self {
     Self { rune, spacers }
@@ -1,6 +1,7 @@
 use super::*;
 
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Terms {
   pub amount: Option<u128>,
   pub cap: Option<u128>,
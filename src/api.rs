@@ -1,24 +1,59 @@
 use {
   super::*,
+  schemars::{Schema, SchemaGenerator},
   serde_hex::{SerHex, Strict},
+  std::borrow::Cow,
 };
 
+pub use crate::index::pagination::Cursor;
+
+pub mod schema;
+
 pub use crate::{
   subcommand::decode::RawOutput as Decode,
   templates::{
-    BlocksHtml as Blocks, RuneHtml as Rune, RunesHtml as Runes, StatusHtml as Status,
-    TransactionHtml as Transaction,
+    collection::CollectionChild,
+    rune_mints::{RuneBlockActivity, RuneMint},
+    rune_search::{RuneSearchResult, RuneSort},
+    runes_minting::MintingRune,
+    transaction::{SatFlow, SatFlowInscription, SatFlowRune, SatFlowSegment},
+    BlocksHtml as Blocks, CollectionHtml as Collection, RuneHtml as Rune,
+    RuneMintsHtml as RuneMints, RuneSearchHtml as RuneSearch, RunesMintingHtml as RunesMinting,
+    StatusHtml as Status, TransactionHtml as Transaction,
   },
 };
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AddressInfo {
+  pub inscriptions: Vec<InscriptionId>,
+  pub more: bool,
+  #[schemars(with = "Vec<schema::OutPoint>")]
+  pub outputs: Vec<OutPoint>,
+  pub page: usize,
+  pub rarities: Option<BTreeMap<Rarity, u64>>,
+  pub runes_balances: Option<BTreeMap<SpacedRune, Pile>>,
+  pub sat_balance: u64,
+  pub transactions: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AddressInscriptions {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Block {
   pub best_height: u32,
+  #[schemars(with = "schema::BlockHash")]
   pub hash: BlockHash,
   pub height: u32,
   pub inscriptions: Vec<InscriptionId>,
   pub runes: Vec<SpacedRune>,
+  #[schemars(with = "schema::BlockHash")]
   pub target: BlockHash,
+  #[schemars(with = "Vec<schema::Transaction>")]
   pub transactions: Vec<bitcoin::blockdata::transaction::Transaction>,
 }
 
@@ -42,15 +77,17 @@ impl Block {
   }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct BlockInfo {
   pub average_fee: u64,
   pub average_fee_rate: u64,
   pub bits: u32,
   #[serde(with = "SerHex::<Strict>")]
+  #[schemars(with = "schema::Hex")]
   pub chainwork: [u8; 32],
   pub confirmations: i32,
   pub difficulty: f64,
+  #[schemars(with = "schema::BlockHash")]
   pub hash: BlockHash,
   pub feerate_percentiles: [u64; 5],
   pub height: u32,
@@ -59,13 +96,17 @@ pub struct BlockInfo {
   pub max_tx_size: u32,
   pub median_fee: u64,
   pub median_time: Option<u64>,
+  #[schemars(with = "schema::MerkleRoot")]
   pub merkle_root: TxMerkleNode,
   pub min_fee: u64,
   pub min_fee_rate: u64,
+  #[schemars(with = "Option<schema::BlockHash>")]
   pub next_block: Option<BlockHash>,
   pub nonce: u32,
+  #[schemars(with = "Option<schema::BlockHash>")]
   pub previous_block: Option<BlockHash>,
   pub subsidy: u64,
+  #[schemars(with = "schema::BlockHash")]
   pub target: BlockHash,
   pub timestamp: u64,
   pub total_fee: u64,
@@ -75,21 +116,37 @@ pub struct BlockInfo {
   pub version: u32,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+// The result of looking up one item of a batch request
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(rename = "{T}Result")]
+pub enum BatchResult<T> {
+  Ok(T),
+  Error(String),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Children {
   pub ids: Vec<InscriptionId>,
   pub more: bool,
   pub page: usize,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ChildInscriptions {
   pub children: Vec<ChildInscriptionRecursive>,
   pub more: bool,
   pub page: usize,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DecodePreview {
+  pub inscriptions: Vec<ParsedEnvelope>,
+  pub runestone: Option<Artifact>,
+  pub simulation: Simulation,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Inscription {
   pub address: Option<String>,
   pub charms: Vec<Charm>,
@@ -111,7 +168,7 @@ pub struct Inscription {
   pub value: Option<u64>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct InscriptionRecursive {
   pub charms: Vec<Charm>,
   pub content_type: Option<String>,
@@ -121,6 +178,7 @@ pub struct InscriptionRecursive {
   pub height: u32,
   pub id: InscriptionId,
   pub number: i32,
+  #[schemars(with = "schema::OutPoint")]
   pub output: OutPoint,
   pub sat: Option<ordinals::Sat>,
   pub satpoint: SatPoint,
@@ -128,28 +186,32 @@ pub struct InscriptionRecursive {
   pub value: Option<u64>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ChildInscriptionRecursive {
   pub charms: Vec<Charm>,
   pub fee: u64,
   pub height: u32,
   pub id: InscriptionId,
   pub number: i32,
+  #[schemars(with = "schema::OutPoint")]
   pub output: OutPoint,
   pub sat: Option<ordinals::Sat>,
   pub satpoint: SatPoint,
   pub timestamp: i64,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
   pub more: bool,
   pub page_index: u32,
+  pub next_cursor: Option<Cursor>,
+  pub prev_cursor: Option<Cursor>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Output {
+  #[schemars(with = "Option<schema::Address>")]
   pub address: Option<Address<NetworkUnchecked>>,
   pub indexed: bool,
   pub inscriptions: Vec<InscriptionId>,
@@ -189,7 +251,61 @@ impl Output {
   }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RuneRecursive {
+  pub block: u64,
+  pub burned: u128,
+  pub divisibility: u8,
+  #[schemars(with = "schema::Txid")]
+  pub etching: Txid,
+  pub id: RuneId,
+  pub max_supply: u128,
+  pub mintable: bool,
+  pub mints: u128,
+  pub number: u64,
+  pub premine: u128,
+  pub spaced_rune: SpacedRune,
+  pub supply: u128,
+  pub symbol: Option<char>,
+  pub terms: Option<Terms>,
+  pub timestamp: u64,
+  pub turbo: bool,
+}
+
+impl From<Rune> for RuneRecursive {
+  fn from(rune: Rune) -> Self {
+    Self {
+      block: rune.entry.block,
+      burned: rune.entry.burned,
+      divisibility: rune.entry.divisibility,
+      etching: rune.entry.etching,
+      id: rune.id,
+      max_supply: rune.entry.max_supply(),
+      mintable: rune.mintable,
+      mints: rune.entry.mints,
+      number: rune.entry.number,
+      premine: rune.entry.premine,
+      spaced_rune: rune.entry.spaced_rune,
+      supply: rune.entry.supply(),
+      symbol: rune.entry.symbol,
+      terms: rune.entry.terms,
+      timestamp: rune.entry.timestamp,
+      turbo: rune.entry.turbo,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Runes {
+  pub entries: Vec<(RuneId, RuneEntry)>,
+  pub more: bool,
+  pub prev: Option<usize>,
+  pub next: Option<usize>,
+  pub next_cursor: Option<Cursor>,
+  pub prev_cursor: Option<Cursor>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Sat {
   pub block: u32,
   pub charms: Vec<Charm>,
@@ -208,14 +324,50 @@ pub struct Sat {
   pub timestamp: i64,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SatInscription {
   pub id: Option<InscriptionId>,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SatInscriptions {
   pub ids: Vec<InscriptionId>,
   pub more: bool,
   pub page: u64,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SimulatedOutput {
+  pub inscriptions: Vec<InscriptionId>,
+  pub runes: BTreeMap<SpacedRune, Pile>,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+  pub value: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Simulation {
+  pub burned: BTreeMap<SpacedRune, Pile>,
+  pub fee: u64,
+  pub fee_inscriptions: Vec<InscriptionId>,
+  pub fee_sat_ranges: Option<Vec<(u64, u64)>>,
+  pub outputs: Vec<SimulatedOutput>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UtxoRecursive {
+  pub inscriptions: Vec<InscriptionId>,
+  pub runes: BTreeMap<SpacedRune, Pile>,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+  pub value: u64,
+}
+
+impl From<Output> for UtxoRecursive {
+  fn from(output: Output) -> Self {
+    Self {
+      inscriptions: output.inscriptions,
+      runes: output.runes,
+      sat_ranges: output.sat_ranges,
+      value: output.value,
+    }
+  }
+}
@@ -0,0 +1,114 @@
+// JSON schemas of types defined outside of ord, for use with
+// `#[schemars(with = "...")]`, and of values which handlers construct
+// without a dedicated type
+
+use super::*;
+
+macro_rules! string_schema {
+  ($name:ident, $description:literal $(, $pattern:literal)?) => {
+    pub struct $name;
+
+    impl JsonSchema for $name {
+      fn schema_name() -> Cow<'static, str> {
+        stringify!($name).into()
+      }
+
+      fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        schemars::json_schema!({
+          "type": "string",
+          "description": $description,
+          $("pattern": $pattern,)?
+        })
+      }
+    }
+  };
+}
+
+string_schema!(Address, "Bitcoin address.");
+
+string_schema!(BlockHash, "Block hash.", "^[0-9a-f]{64}$");
+
+pub struct DateTime;
+
+impl JsonSchema for DateTime {
+  fn schema_name() -> Cow<'static, str> {
+    "DateTime".into()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    schemars::json_schema!({
+      "type": "string",
+      "format": "date-time",
+    })
+  }
+}
+
+string_schema!(
+  EncodedTransaction,
+  "Hex-encoded transaction or PSBT, or base64-encoded PSBT."
+);
+
+string_schema!(Hex, "Hex-encoded bytes.", "^([0-9a-f]{2})*$");
+
+string_schema!(MerkleRoot, "Merkle root.", "^[0-9a-f]{64}$");
+
+string_schema!(
+  OutPoint,
+  "Transaction output, as `<TXID>:<VOUT>`.",
+  "^[0-9a-f]{64}:[0-9]+$"
+);
+
+string_schema!(Txid, "Transaction ID.", "^[0-9a-f]{64}$");
+
+/// Bitcoin transaction.
+#[derive(JsonSchema)]
+#[schemars(rename = "BitcoinTransaction")]
+pub struct Transaction {
+  pub version: i32,
+  pub lock_time: u32,
+  pub input: Vec<TxIn>,
+  pub output: Vec<TxOut>,
+}
+
+/// Transaction input.
+#[derive(JsonSchema)]
+pub struct TxIn {
+  pub previous_output: OutPoint,
+  pub script_sig: Hex,
+  pub sequence: u32,
+  pub witness: Vec<Hex>,
+}
+
+/// Transaction output.
+#[derive(JsonSchema)]
+pub struct TxOut {
+  pub value: u64,
+  pub script_pubkey: Hex,
+}
+
+/// Rune balances of all outputs, keyed by spaced rune and outpoint.
+#[derive(JsonSchema)]
+#[schemars(transparent)]
+pub struct RuneBalances(pub BTreeMap<SpacedRune, BTreeMap<OutPoint, u128>>);
+
+#[derive(JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLRequest {
+  pub query: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub operation_name: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub variables: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+#[derive(JsonSchema)]
+pub struct GraphQLResponse {
+  pub data: serde_json::Value,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub errors: Vec<GraphQLError>,
+}
+
+#[derive(JsonSchema)]
+pub struct GraphQLError {
+  pub message: String,
+}
@@ -1,6 +1,6 @@
 use {super::*, clap::ValueEnum};
 
-#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum Chain {
   #[default]
@@ -1,12 +1,15 @@
 use {
   self::{
+    block_source::BlockSource,
     entry::{
       Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
       OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxOutValue, TxidValue,
     },
     event::Event,
     lot::Lot,
+    pagination::{Page, Pagination},
     reorg::Reorg,
+    storage::SharedDatabase,
     updater::Updater,
   },
   super::*,
@@ -16,20 +19,17 @@ use {
     templates::StatusHtml,
   },
   bitcoin::block::Header,
-  bitcoincore_rpc::{
-    json::{GetBlockHeaderResult, GetBlockStatsResult},
-    Client,
-  },
+  bitcoincore_rpc::json::{GetBlockHeaderResult, GetBlockStatsResult},
   chrono::SubsecRound,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
   redb::{
     Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
-    ReadOnlyTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata, RepairSession,
-    StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
+    ReadOnlyTable, ReadTransaction, ReadableMultimapTable, ReadableTable, ReadableTableMetadata,
+    RepairSession, StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
   },
   std::{
-    collections::HashMap,
+    collections::{BinaryHeap, HashMap},
     io::{BufWriter, Write},
     sync::Once,
   },
@@ -37,18 +37,27 @@ use {
 
 pub use self::entry::RuneEntry;
 
+mod allocation;
+mod block_source;
+mod commitment;
 pub(crate) mod entry;
+mod esplora;
 pub mod event;
+pub(crate) mod export;
 mod fetcher;
 mod lot;
+pub mod pagination;
 mod reorg;
 mod rtx;
+mod simulation;
+mod snapshot;
+pub(crate) mod storage;
 mod updater;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 26;
+const SCHEMA_VERSION: u64 = 31;
 
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
@@ -60,12 +69,17 @@ define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { OPEN_MINTS, (u64, u64, u32), () }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_TXOUT, &OutPointValue, TxOutValue }
+define_table! { RUNE_ID_AND_HEIGHT_TO_ACTIVITY, (u64, u32, u32), (u64, u128) }
+define_table! { RUNE_ID_TO_OUTPUTS, RuneIdValue, u64 }
+define_table! { RUNE_ID_TO_RECENT_MINTS, RuneIdValue, &[u8] }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SCRIPT_PUBKEY_TO_TRANSACTION_COUNT, &[u8], u64 }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
@@ -183,8 +197,8 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
-  pub(crate) client: Client,
-  database: Database,
+  pub(crate) client: BlockSource,
+  database: SharedDatabase,
   durability: redb::Durability,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   first_inscription_height: u32,
@@ -211,7 +225,7 @@ impl Index {
     settings: &Settings,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   ) -> Result<Self> {
-    let client = settings.bitcoin_rpc_client(None)?;
+    let client = BlockSource::new(settings)?;
 
     let path = settings.index().to_owned();
 
@@ -259,29 +273,7 @@ impl Index {
       .open(&path)
     {
       Ok(database) => {
-        {
-          let schema_version = database
-            .begin_read()?
-            .open_table(STATISTIC_TO_COUNT)?
-            .get(&Statistic::Schema.key())?
-            .map(|x| x.value())
-            .unwrap_or(0);
-
-          match schema_version.cmp(&SCHEMA_VERSION) {
-            cmp::Ordering::Less =>
-              bail!(
-                "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Greater =>
-              bail!(
-                "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
-                path.display()
-              ),
-            cmp::Ordering::Equal => {
-            }
-          }
-        }
+        Self::check_schema_version(&database, &path)?;
 
         database
       }
@@ -306,11 +298,16 @@ impl Index {
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(OPEN_MINTS)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
         tx.open_table(OUTPOINT_TO_TXOUT)?;
+        tx.open_table(RUNE_ID_AND_HEIGHT_TO_ACTIVITY)?;
+        tx.open_table(RUNE_ID_TO_OUTPUTS)?;
+        tx.open_table(RUNE_ID_TO_RECENT_MINTS)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SCRIPT_PUBKEY_TO_TRANSACTION_COUNT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
@@ -431,7 +428,7 @@ impl Index {
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
       client,
-      database,
+      database: SharedDatabase::new(database),
       durability,
       event_sender,
       first_inscription_height: settings.first_inscription_height(),
@@ -449,6 +446,29 @@ impl Index {
     })
   }
 
+  fn check_schema_version(database: &Database, path: &Path) -> Result {
+    let schema_version = database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    match schema_version.cmp(&SCHEMA_VERSION) {
+      cmp::Ordering::Less =>
+        bail!(
+          "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        ),
+      cmp::Ordering::Greater =>
+        bail!(
+          "index at `{}` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+          path.display()
+        ),
+      cmp::Ordering::Equal => Ok(()),
+    }
+  }
+
   #[cfg(test)]
   fn set_durability(&mut self, durability: redb::Durability) {
     self.durability = durability;
@@ -666,74 +686,6 @@ impl Index {
     }
   }
 
-  pub fn export(&self, filename: &String, include_addresses: bool) -> Result {
-    let mut writer = BufWriter::new(fs::File::create(filename)?);
-    let rtx = self.database.begin_read()?;
-
-    let blocks_indexed = rtx
-      .open_table(HEIGHT_TO_BLOCK_HEADER)?
-      .range(0..)?
-      .next_back()
-      .transpose()?
-      .map(|(height, _header)| height.value() + 1)
-      .unwrap_or(0);
-
-    writeln!(writer, "# export at block height {}", blocks_indexed)?;
-
-    log::info!("exporting database tables to {filename}");
-
-    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
-
-    for result in rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-      .iter()?
-    {
-      let entry = result?;
-      let sequence_number = entry.0.value();
-      let entry = InscriptionEntry::load(entry.1.value());
-      let satpoint = SatPoint::load(
-        *sequence_number_to_satpoint
-          .get(sequence_number)?
-          .unwrap()
-          .value(),
-      );
-
-      write!(
-        writer,
-        "{}\t{}\t{}",
-        entry.inscription_number, entry.id, satpoint
-      )?;
-
-      if include_addresses {
-        let address = if satpoint.outpoint == unbound_outpoint() {
-          "unbound".to_string()
-        } else {
-          let output = self
-            .get_transaction(satpoint.outpoint.txid)?
-            .unwrap()
-            .output
-            .into_iter()
-            .nth(satpoint.outpoint.vout.try_into().unwrap())
-            .unwrap();
-          self
-            .settings
-            .chain()
-            .address_from_script(&output.script_pubkey)
-            .map(|address| address.to_string())
-            .unwrap_or_else(|e| e.to_string())
-        };
-        write!(writer, "\t{}", address)?;
-      }
-      writeln!(writer)?;
-
-      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
-        break;
-      }
-    }
-    writer.flush()?;
-    Ok(())
-  }
-
   fn begin_read(&self) -> Result<rtx::Rtx> {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
@@ -936,29 +888,279 @@ impl Index {
     Ok(entries)
   }
 
-  pub fn runes_paginated(
+  pub fn runes_page(&self, pagination: Pagination) -> Result<Page<(RuneId, RuneEntry)>> {
+    let rune_id_to_rune_entry = self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    // rune ids are ordered by etching, like rune numbers
+    pagination::paginate(pagination, |bounds, ascending, skip, take| {
+      pagination::directed(
+        rune_id_to_rune_entry.range(pagination::key_bounds(bounds, |key| {
+          (
+            u64::try_from(key >> 32).unwrap_or(u64::MAX),
+            u32::try_from(key & u128::from(u32::MAX)).unwrap_or(u32::MAX),
+          )
+        }))?,
+        ascending,
+      )
+      .skip(skip)
+      .take(take)
+      .map(|result| {
+        let (id, entry) = result?;
+        let id = RuneId::load(id.value());
+        Ok((
+          (u128::from(id.block) << 32) | u128::from(id.tx),
+          (id, RuneEntry::load(entry.value())),
+        ))
+      })
+      .collect()
+    })
+  }
+
+  /// Returns runes whose names start with `prefix`, which must consist of the
+  /// letters `A` through `Z`, or are one typo away from `prefix`, along with
+  /// the number of unspent outputs holding a balance of each rune. Every
+  /// matching rune is ranked by `rank`, lowest first, and only the first
+  /// `limit` are returned. The returned `bool` is true if there are more.
+  pub fn search_runes<K: Ord>(
     &self,
-    page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<(RuneId, RuneEntry)>, bool)> {
-    let mut entries = Vec::new();
+    prefix: &str,
+    limit: usize,
+    rank: impl Fn(&RuneEntry, u64) -> K,
+  ) -> Result<(Vec<(RuneId, RuneEntry, u64)>, bool)> {
+    if prefix.is_empty() {
+      return Ok((Vec::new(), false));
+    }
 
-    for result in self
+    let Ok(Rune(first)) = prefix.parse::<Rune>() else {
+      return Ok((Vec::new(), false));
+    };
+
+    let rtx = self.database.begin_read()?;
+
+    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let rune_id_to_outputs = rtx.open_table(RUNE_ID_TO_OUTPUTS)?;
+
+    // runes are ordered by length and then alphabetically, so the runes of
+    // each length that start with `prefix` are a contiguous range of keys,
+    // offset from the first rune of that length by the position of `prefix`
+    // among the runes of its own length
+    let position = first - "A".repeat(prefix.len()).parse::<Rune>()?.n();
+
+    let mut ids = Vec::new();
+
+    for length in prefix.len().. {
+      let Ok(Rune(shortest)) = "A".repeat(length).parse::<Rune>() else {
+        break;
+      };
+
+      let span = 26u128.pow((length - prefix.len()).try_into().unwrap());
+
+      let Some(start) = position
+        .checked_mul(span)
+        .and_then(|offset| shortest.checked_add(offset))
+      else {
+        break;
+      };
+
+      let range = match start.checked_add(span) {
+        Some(end) => rune_to_rune_id.range(start..end)?,
+        None => rune_to_rune_id.range(start..)?,
+      };
+
+      for result in range {
+        ids.push(RuneId::load(result?.1.value()));
+      }
+    }
+
+    // names one deletion, transposition, substitution, or insertion away from
+    // `prefix`, which don't start with `prefix` and so weren't found above
+    let letters = prefix.chars().collect::<Vec<char>>();
+
+    let mut typos = BTreeSet::new();
+
+    for i in 0..=letters.len() {
+      if i < letters.len() {
+        let mut deletion = letters.clone();
+        deletion.remove(i);
+        typos.insert(deletion);
+      }
+
+      if i + 1 < letters.len() {
+        let mut transposition = letters.clone();
+        transposition.swap(i, i + 1);
+        typos.insert(transposition);
+      }
+
+      for letter in 'A'..='Z' {
+        if i < letters.len() {
+          let mut substitution = letters.clone();
+          substitution[i] = letter;
+          typos.insert(substitution);
+        }
+
+        let mut insertion = letters.clone();
+        insertion.insert(i, letter);
+        typos.insert(insertion);
+      }
+    }
+
+    for typo in typos {
+      let name = typo.into_iter().collect::<String>();
+
+      if name.is_empty() || name.starts_with(prefix) {
+        continue;
+      }
+
+      let Ok(rune) = name.parse::<Rune>() else {
+        continue;
+      };
+
+      if let Some(id) = rune_to_rune_id.get(rune.n())? {
+        ids.push(RuneId::load(id.value()));
+      }
+    }
+
+    // keep the `limit` best ranked runes in a max-heap, so that the worst of
+    // them can be evicted when a better one is found
+    let mut best = BinaryHeap::new();
+
+    for (i, &id) in ids.iter().enumerate() {
+      let entry = RuneEntry::load(rune_id_to_rune_entry.get(id.store())?.unwrap().value());
+
+      let outputs = rune_id_to_outputs
+        .get(id.store())?
+        .map(|outputs| outputs.value())
+        .unwrap_or_default();
+
+      best.push((rank(&entry, outputs), i, outputs));
+
+      if best.len() > limit {
+        best.pop();
+      }
+    }
+
+    let more = ids.len() > best.len();
+
+    let runes = best
+      .into_sorted_vec()
+      .into_iter()
+      .map(|(_rank, i, outputs)| {
+        let entry = rune_id_to_rune_entry.get(ids[i].store())?.unwrap();
+        Ok((ids[i], RuneEntry::load(entry.value()), outputs))
+      })
+      .collect::<Result<Vec<(RuneId, RuneEntry, u64)>>>()?;
+
+    Ok((runes, more))
+  }
+
+  /// Returns the height, number of mints, and amount burned of the most recent
+  /// `limit` blocks in which the rune with `id` was minted or burned, most
+  /// recent first.
+  pub fn get_rune_activity(&self, id: RuneId, limit: usize) -> Result<Vec<(u32, u64, u128)>> {
+    self
       .database
       .begin_read()?
-      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-      .iter()?
+      .open_table(RUNE_ID_AND_HEIGHT_TO_ACTIVITY)?
+      .range((id.block, id.tx, 0)..=(id.block, id.tx, u32::MAX))?
       .rev()
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
+      .take(limit)
+      .map(|result| {
+        let (key, value) = result?;
+        let (_block, _tx, height) = key.value();
+        let (mints, burned) = value.value();
+        Ok((height, mints, burned))
+      })
+      .collect()
+  }
+
+  /// Returns the number of times the rune with `id` was minted in blocks at
+  /// or after `height`.
+  pub fn get_rune_mints_since(&self, id: RuneId, height: u32) -> Result<u64> {
+    let mut mints = 0;
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_AND_HEIGHT_TO_ACTIVITY)?
+      .range((id.block, id.tx, height)..=(id.block, id.tx, u32::MAX))?
     {
-      let (id, entry) = result?;
-      entries.push((RuneId::load(id.value()), RuneEntry::load(entry.value())));
+      mints += result?.1.value().0;
     }
 
-    let more = entries.len() > page_size;
+    Ok(mints)
+  }
+
+  /// Returns the most recent mint transactions of the rune with `id`, and the
+  /// heights of the blocks they were mined in, most recent first.
+  pub fn get_recent_rune_mints(&self, id: RuneId) -> Result<Vec<(Txid, u32)>> {
+    let Some(recent_mints) = self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_TO_RECENT_MINTS)?
+      .get(&id.store())?
+    else {
+      return Ok(Vec::new());
+    };
+
+    Ok(
+      recent_mints
+        .value()
+        .chunks_exact(36)
+        .map(|chunk| {
+          (
+            Txid::load(chunk[..32].try_into().unwrap()),
+            u32::from_le_bytes(chunk[32..].try_into().unwrap()),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// Returns the runes which can be minted in the next block, along with the
+  /// number of times each was minted in the last `blocks` blocks.
+  pub fn get_runes_minting(&self, blocks: u32) -> Result<Vec<(RuneId, RuneEntry, u64)>> {
+    let rtx = self.begin_read()?;
+
+    let height = rtx.block_count()?;
+
+    let since = height.saturating_sub(blocks);
+
+    let id_and_height_to_activity = rtx.0.open_table(RUNE_ID_AND_HEIGHT_TO_ACTIVITY)?;
+    let id_to_entry = rtx.0.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let mut runes = Vec::new();
+
+    // open mints are keyed by the height they end at, so skip those which
+    // have already ended
+    for result in rtx
+      .0
+      .open_table(OPEN_MINTS)?
+      .range((u64::from(height) + 1, 0, 0)..)?
+    {
+      let (_end, block, tx) = result?.0.value();
+
+      let id = RuneId { block, tx };
 
-    Ok((entries, more))
+      let entry = RuneEntry::load(id_to_entry.get(id.store())?.unwrap().value());
+
+      if entry.mintable(height.into()).is_err() {
+        continue;
+      }
+
+      let mut mints = 0;
+
+      for result in id_and_height_to_activity.range((block, tx, since)..=(block, tx, u32::MAX))? {
+        mints += result?.1.value().0;
+      }
+
+      runes.push((id, entry, mints));
+    }
+
+    Ok(runes)
   }
 
   pub fn encode_rune_balance(id: RuneId, balance: u128, buffer: &mut Vec<u8>) {
@@ -1102,65 +1304,65 @@ impl Index {
   }
 
   pub fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
-    self.client.get_block_header(&hash).into_option()
+    self.client.get_block_header(hash)
+  }
+
+  pub fn block_height_by_hash(&self, hash: BlockHash) -> Result<Option<u32>> {
+    self.client.get_block_height(hash)
   }
 
   pub fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
-    self.client.get_block_header_info(&hash).into_option()
+    self.client.get_block_header_info(hash)
   }
 
   pub fn block_stats(&self, height: u64) -> Result<Option<GetBlockStatsResult>> {
-    self.client.get_block_stats(height).into_option()
+    self.client.get_block_stats(height)
   }
 
   pub fn get_block_by_height(&self, height: u32) -> Result<Option<Block>> {
     Ok(
       self
         .client
-        .get_block_hash(height.into())
-        .into_option()?
-        .map(|hash| self.client.get_block(&hash))
-        .transpose()?,
+        .get_block_hash(height)?
+        .map(|hash| self.client.get_block(hash))
+        .transpose()?
+        .flatten(),
     )
   }
 
   pub fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
-    self.client.get_block(&hash).into_option()
+    self.client.get_block(hash)
   }
 
-  pub fn get_collections_paginated(
-    &self,
-    page_size: usize,
-    page_index: usize,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
+  pub fn get_collections_page(&self, pagination: Pagination) -> Result<Page<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let mut collections = rtx
-      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
-      .iter()?
-      .skip(page_index.saturating_mul(page_size))
-      .take(page_size.saturating_add(1))
+    let sequence_number_to_children = rtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+
+    pagination::paginate(pagination, |bounds, ascending, skip, take| {
+      pagination::directed(
+        sequence_number_to_children.range(pagination::key_bounds(bounds, |key| {
+          u32::try_from(key).unwrap_or(u32::MAX)
+        }))?,
+        ascending,
+      )
+      .skip(skip)
+      .take(take)
       .map(|result| {
-        result
-          .and_then(|(parent, _children)| {
-            sequence_number_to_inscription_entry
-              .get(parent.value())
-              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-          })
-          .map_err(|err| err.into())
+        let (parent, _children) = result?;
+        let entry = sequence_number_to_inscription_entry
+          .get(parent.value())?
+          .unwrap();
+        Ok((
+          parent.value().into(),
+          InscriptionEntry::load(entry.value()).id,
+        ))
       })
-      .collect::<Result<Vec<InscriptionId>>>()?;
-
-    let more = collections.len() > page_size;
-
-    if more {
-      collections.pop();
-    }
-
-    Ok((collections, more))
+      .collect()
+    })
   }
 
   #[cfg(test)]
@@ -1226,6 +1428,17 @@ impl Index {
       .collect()
   }
 
+  pub fn get_child_count(&self, sequence_number: u32) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .get(sequence_number)?
+        .len(),
+    )
+  }
+
   pub fn get_children_by_sequence_number_paginated(
     &self,
     sequence_number: u32,
@@ -1261,6 +1474,36 @@ impl Index {
     Ok((children, more))
   }
 
+  pub fn get_children_page(
+    &self,
+    sequence_number: u32,
+    pagination: Pagination,
+  ) -> Result<Page<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let sequence_number_to_children = rtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+
+    pagination::paginate(pagination, |bounds, ascending, skip, take| {
+      pagination::directed(sequence_number_to_children.get(sequence_number)?, ascending)
+        .filter(|result| {
+          result
+            .as_ref()
+            .map(|child| pagination::contains(bounds, child.value().into()))
+            .unwrap_or(true)
+        })
+        .skip(skip)
+        .take(take)
+        .map(|result| {
+          let child = result?.value();
+          let entry = sequence_number_to_entry.get(child)?.unwrap();
+          Ok((child.into(), InscriptionEntry::load(entry.value()).id))
+        })
+        .collect()
+    })
+  }
+
   pub fn get_parents_by_sequence_number_paginated(
     &self,
     parent_sequence_numbers: Vec<u32>,
@@ -1555,7 +1798,7 @@ impl Index {
       }
     }
 
-    self.client.get_raw_transaction(&txid, None).into_option()
+    self.client.get_transaction(txid)
   }
 
   pub fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
@@ -1668,10 +1911,7 @@ impl Index {
             .get(&outpoint.store())?
             .is_none()
         } else {
-          self
-            .client
-            .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
-            .is_none()
+          self.client.get_tx_out(outpoint)?.is_none()
         },
     )
   }
@@ -1685,19 +1925,15 @@ impl Index {
       return Ok(true);
     }
 
-    let Some(info) = self
-      .client
-      .get_raw_transaction_info(&outpoint.txid, None)
-      .into_option()?
-    else {
+    let Some((transaction, blockhash)) = self.client.get_transaction_info(outpoint.txid)? else {
       return Ok(false);
     };
 
-    if info.blockhash.is_none() {
+    if blockhash.is_none() {
       return Ok(false);
     }
 
-    if outpoint.vout.into_usize() >= info.vout.len() {
+    if outpoint.vout.into_usize() >= transaction.output.len() {
       return Ok(false);
     }
 
@@ -1738,40 +1974,30 @@ impl Index {
     ))
   }
 
-  pub fn get_inscriptions_paginated(
-    &self,
-    page_size: u32,
-    page_index: u32,
-  ) -> Result<(Vec<InscriptionId>, bool)> {
-    let rtx = self.database.begin_read()?;
-
-    let sequence_number_to_inscription_entry =
-      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-
-    let last = sequence_number_to_inscription_entry
-      .iter()?
-      .next_back()
-      .map(|result| result.map(|(number, _entry)| number.value()))
-      .transpose()?
-      .unwrap_or_default();
-
-    let start = last.saturating_sub(page_size.saturating_mul(page_index));
-
-    let end = start.saturating_sub(page_size);
-
-    let mut inscriptions = sequence_number_to_inscription_entry
-      .range(end..=start)?
-      .rev()
-      .map(|result| result.map(|(_number, entry)| InscriptionEntry::load(entry.value()).id))
-      .collect::<Result<Vec<InscriptionId>, StorageError>>()?;
-
-    let more = u32::try_from(inscriptions.len()).unwrap_or(u32::MAX) > page_size;
-
-    if more {
-      inscriptions.pop();
-    }
-
-    Ok((inscriptions, more))
+  pub fn get_inscriptions_page(&self, pagination: Pagination) -> Result<Page<InscriptionId>> {
+    let sequence_number_to_inscription_entry = self
+      .database
+      .begin_read()?
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    pagination::paginate(pagination, |bounds, ascending, skip, take| {
+      pagination::directed(
+        sequence_number_to_inscription_entry.range(pagination::key_bounds(bounds, |key| {
+          u32::try_from(key).unwrap_or(u32::MAX)
+        }))?,
+        ascending,
+      )
+      .skip(skip)
+      .take(take)
+      .map(|result| {
+        let (sequence_number, entry) = result?;
+        Ok((
+          sequence_number.value().into(),
+          InscriptionEntry::load(entry.value()).id,
+        ))
+      })
+      .collect()
+    })
   }
 
   pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
@@ -2250,6 +2476,20 @@ impl Index {
       .collect()
   }
 
+  /// Returns the number of transactions which spent from or sent to
+  /// `address`.
+  pub(crate) fn get_address_transaction_count(&self, address: &Address) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SCRIPT_PUBKEY_TO_TRANSACTION_COUNT)?
+        .get(address.script_pubkey().as_bytes())?
+        .map(|count| count.value())
+        .unwrap_or_default(),
+    )
+  }
+
   pub(crate) fn get_aggregated_rune_balances_for_outputs(
     &self,
     outputs: &Vec<OutPoint>,
@@ -2297,6 +2537,32 @@ impl Index {
     Ok(acc)
   }
 
+  pub(crate) fn get_sat_rarities_for_outputs(
+    &self,
+    outputs: &Vec<OutPoint>,
+  ) -> Result<Option<BTreeMap<Rarity, u64>>> {
+    if !self.has_sat_index() {
+      return Ok(None);
+    }
+
+    let mut rarities = BTreeMap::new();
+
+    for output in outputs {
+      for (start, end) in self.list(*output)?.unwrap_or_default() {
+        // sat ranges are split but never merged, and each starts within the
+        // subsidy of a single block, so only the first sat of a range can be
+        // the first sat of a block, and thus be rarer than common
+        *rarities.entry(Sat(start).rarity()).or_default() += 1;
+
+        if end - start > 1 {
+          *rarities.entry(Rarity::Common).or_default() += end - start - 1;
+        }
+      }
+    }
+
+    Ok(Some(rarities))
+  }
+
   pub(crate) fn get_output_info(&self, outpoint: OutPoint) -> Result<Option<(api::Output, TxOut)>> {
     let sat_ranges = self.list(outpoint)?;
 
@@ -3623,7 +3889,19 @@ mod tests {
 
       context.mine_blocks(1);
 
-      let (inscriptions, more) = context.index.get_inscriptions_paginated(100, 0).unwrap();
+      let Page {
+        items: inscriptions,
+        more,
+        ..
+      } = context
+        .index
+        .get_inscriptions_page(Pagination {
+          cursor: None,
+          limit: 100,
+          order: pagination::Order::Desc,
+          page: 0,
+        })
+        .unwrap();
       assert_eq!(inscriptions, &[inscription_id]);
       assert!(!more);
     }
@@ -3650,12 +3928,85 @@ mod tests {
 
       assert_eq!(ids.len(), 100);
 
-      let (inscriptions, more) = context.index.get_inscriptions_paginated(100, 0).unwrap();
+      let Page {
+        items: inscriptions,
+        more,
+        ..
+      } = context
+        .index
+        .get_inscriptions_page(Pagination {
+          cursor: None,
+          limit: 100,
+          order: pagination::Order::Desc,
+          page: 0,
+        })
+        .unwrap();
       assert_eq!(inscriptions, ids);
       assert!(more);
     }
   }
 
+  #[test]
+  fn get_inscriptions_page_with_cursor() {
+    for context in Context::configurations() {
+      context.mine_blocks(1);
+
+      let mut ids = Vec::new();
+
+      for i in 0..5 {
+        let txid = context.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
+          ..default()
+        });
+        context.mine_blocks(1);
+        ids.push(InscriptionId { txid, index: 0 });
+      }
+
+      let pagination = Pagination {
+        cursor: None,
+        limit: 2,
+        order: pagination::Order::Asc,
+        page: 0,
+      };
+
+      let first = context.index.get_inscriptions_page(pagination).unwrap();
+      assert_eq!(first.items, &ids[..2]);
+      assert!(first.more);
+      assert_eq!(first.prev, None);
+
+      let second = context
+        .index
+        .get_inscriptions_page(Pagination {
+          cursor: first.next,
+          ..pagination
+        })
+        .unwrap();
+      assert_eq!(second.items, &ids[2..4]);
+
+      let last = context
+        .index
+        .get_inscriptions_page(Pagination {
+          cursor: second.next,
+          ..pagination
+        })
+        .unwrap();
+      assert_eq!(last.items, &ids[4..]);
+      assert!(!last.more);
+      assert_eq!(last.next, None);
+
+      assert_eq!(
+        context
+          .index
+          .get_inscriptions_page(Pagination {
+            cursor: last.prev,
+            ..pagination
+          })
+          .unwrap(),
+        second
+      );
+    }
+  }
+
   #[test]
   fn unrecognized_even_field_inscriptions_are_cursed_and_unbound() {
     for context in Context::configurations() {
@@ -6791,4 +7142,92 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn open_mints_ending_in_next_block_are_removed() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let (_txid, id) = context.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(100),
+            offset: (None, Some(2)),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let open_mints = || {
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(OPEN_MINTS)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| result.unwrap().0.value())
+        .collect::<Vec<(u64, u64, u32)>>()
+    };
+
+    assert_eq!(open_mints(), [(id.block + 2, id.block, id.tx)]);
+
+    context.mine_blocks(1);
+
+    assert_eq!(open_mints(), []);
+    assert!(context.index.get_runes_minting(1).unwrap().is_empty());
+  }
+
+  #[test]
+  fn search_runes_ranks_every_candidate() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-runes").build();
+
+    let ids = (0..3)
+      .map(|i| {
+        context
+          .etch(
+            Runestone {
+              etching: Some(Etching {
+                rune: Some(Rune(RUNE + i)),
+                ..default()
+              }),
+              ..default()
+            },
+            1,
+          )
+          .1
+      })
+      .collect::<Vec<RuneId>>();
+
+    let search = |limit| {
+      let (runes, more) = context
+        .index
+        .search_runes("A", limit, |entry, _outputs| Reverse(entry.number))
+        .unwrap();
+
+      (
+        runes
+          .into_iter()
+          .map(|(id, ..)| id)
+          .collect::<Vec<RuneId>>(),
+        more,
+      )
+    };
+
+    assert_eq!(search(1), (vec![ids[2]], true));
+    assert_eq!(search(2), (vec![ids[2], ids[1]], true));
+    assert_eq!(search(3), (vec![ids[2], ids[1], ids[0]], false));
+  }
 }
@@ -0,0 +1,143 @@
+use super::*;
+
+pub(super) struct Allocation {
+  pub(super) allocated: Vec<HashMap<RuneId, Lot>>,
+  pub(super) burned: HashMap<RuneId, Lot>,
+}
+
+/// Allocate the unallocated rune balances of a transaction to its outputs
+/// according to its runestone. Balances allocated to OP_RETURN outputs, or
+/// left over in a transaction with a cenotaph or without any non-OP_RETURN
+/// outputs, are burned.
+pub(super) fn allocate(
+  tx: &Transaction,
+  artifact: Option<&Artifact>,
+  etched: Option<RuneId>,
+  mut unallocated: HashMap<RuneId, Lot>,
+) -> Allocation {
+  let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
+
+  if let Some(Artifact::Runestone(runestone)) = artifact {
+    for Edict { id, amount, output } in runestone.edicts.iter().copied() {
+      let amount = Lot(amount);
+
+      // edicts with output values greater than the number of outputs
+      // should never be produced by the edict parser
+      let output = usize::try_from(output).unwrap();
+      assert!(output <= tx.output.len());
+
+      let id = if id == RuneId::default() {
+        let Some(id) = etched else {
+          continue;
+        };
+
+        id
+      } else {
+        id
+      };
+
+      let Some(balance) = unallocated.get_mut(&id) else {
+        continue;
+      };
+
+      let mut allocate = |balance: &mut Lot, amount: Lot, output: usize| {
+        if amount > 0 {
+          *balance -= amount;
+          *allocated[output].entry(id).or_default() += amount;
+        }
+      };
+
+      if output == tx.output.len() {
+        // find non-OP_RETURN outputs
+        let destinations = tx
+          .output
+          .iter()
+          .enumerate()
+          .filter_map(|(output, tx_out)| (!tx_out.script_pubkey.is_op_return()).then_some(output))
+          .collect::<Vec<usize>>();
+
+        if !destinations.is_empty() {
+          if amount == 0 {
+            // if amount is zero, divide balance between eligible outputs
+            let amount = *balance / destinations.len() as u128;
+            let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
+
+            for (i, output) in destinations.iter().enumerate() {
+              allocate(
+                balance,
+                if i < remainder { amount + 1 } else { amount },
+                *output,
+              );
+            }
+          } else {
+            // if amount is non-zero, distribute amount to eligible outputs
+            for output in destinations {
+              allocate(balance, amount.min(*balance), output);
+            }
+          }
+        }
+      } else {
+        // Get the allocatable amount
+        let amount = if amount == 0 {
+          *balance
+        } else {
+          amount.min(*balance)
+        };
+
+        allocate(balance, amount, output);
+      }
+    }
+  }
+
+  let mut burned: HashMap<RuneId, Lot> = HashMap::new();
+
+  if let Some(Artifact::Cenotaph(_)) = artifact {
+    for (id, balance) in unallocated {
+      *burned.entry(id).or_default() += balance;
+    }
+  } else {
+    let pointer = artifact
+      .map(|artifact| match artifact {
+        Artifact::Runestone(runestone) => runestone.pointer,
+        Artifact::Cenotaph(_) => unreachable!(),
+      })
+      .unwrap_or_default();
+
+    // assign all un-allocated runes to the default output, or the first non
+    // OP_RETURN output if there is no default
+    if let Some(vout) = pointer
+      .map(|pointer| pointer.into_usize())
+      .inspect(|&pointer| assert!(pointer < allocated.len()))
+      .or_else(|| {
+        tx.output
+          .iter()
+          .enumerate()
+          .find(|(_vout, tx_out)| !tx_out.script_pubkey.is_op_return())
+          .map(|(vout, _tx_out)| vout)
+      })
+    {
+      for (id, balance) in unallocated {
+        if balance > 0 {
+          *allocated[vout].entry(id).or_default() += balance;
+        }
+      }
+    } else {
+      for (id, balance) in unallocated {
+        if balance > 0 {
+          *burned.entry(id).or_default() += balance;
+        }
+      }
+    }
+  }
+
+  // burn balances allocated to OP_RETURN outputs
+  for (balances, tx_out) in allocated.iter_mut().zip(&tx.output) {
+    if tx_out.script_pubkey.is_op_return() {
+      for (id, balance) in balances.drain() {
+        *burned.entry(id).or_default() += balance;
+      }
+    }
+  }
+
+  Allocation { allocated, burned }
+}
@@ -0,0 +1,115 @@
+use {super::*, esplora::Esplora};
+
+pub(crate) enum BlockSource {
+  Core(Client),
+  Esplora(Esplora),
+}
+
+impl BlockSource {
+  pub(crate) fn new(settings: &Settings) -> Result<Self> {
+    Ok(match settings.esplora_url() {
+      Some(url) => Self::Esplora(Esplora::new(url, settings.chain())?),
+      None => Self::Core(settings.bitcoin_rpc_client(None)?),
+    })
+  }
+
+  pub(crate) fn get_block_count(&self) -> Result<u64> {
+    match self {
+      Self::Core(client) => Ok(client.get_block_count()?),
+      Self::Esplora(esplora) => esplora.get_block_count(),
+    }
+  }
+
+  pub(crate) fn get_header_count(&self) -> Result<u64> {
+    match self {
+      Self::Core(client) => Ok(client.get_blockchain_info()?.headers),
+      Self::Esplora(esplora) => esplora.get_block_count(),
+    }
+  }
+
+  pub(crate) fn get_block_hash(&self, height: u32) -> Result<Option<BlockHash>> {
+    match self {
+      Self::Core(client) => client.get_block_hash(height.into()).into_option(),
+      Self::Esplora(esplora) => esplora.get_block_hash(height),
+    }
+  }
+
+  pub(crate) fn get_block(&self, hash: BlockHash) -> Result<Option<Block>> {
+    match self {
+      Self::Core(client) => client.get_block(&hash).into_option(),
+      Self::Esplora(esplora) => esplora.get_block(hash),
+    }
+  }
+
+  pub(crate) fn get_block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
+    match self {
+      Self::Core(client) => client.get_block_header(&hash).into_option(),
+      Self::Esplora(esplora) => esplora.get_block_header(hash),
+    }
+  }
+
+  pub(crate) fn get_block_height(&self, hash: BlockHash) -> Result<Option<u32>> {
+    match self {
+      Self::Core(client) => Ok(
+        client
+          .get_block_header_info(&hash)
+          .into_option()?
+          .map(|info| info.height.try_into().unwrap()),
+      ),
+      Self::Esplora(esplora) => esplora.get_block_height(hash),
+    }
+  }
+
+  // Esplora reports neither chainwork nor block statistics
+  pub(crate) fn get_block_header_info(
+    &self,
+    hash: BlockHash,
+  ) -> Result<Option<GetBlockHeaderResult>> {
+    match self {
+      Self::Core(client) => client.get_block_header_info(&hash).into_option(),
+      Self::Esplora(_) => Ok(None),
+    }
+  }
+
+  pub(crate) fn get_block_stats(&self, height: u64) -> Result<Option<GetBlockStatsResult>> {
+    match self {
+      Self::Core(client) => client.get_block_stats(height).into_option(),
+      Self::Esplora(_) => Ok(None),
+    }
+  }
+
+  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    match self {
+      Self::Core(client) => client.get_raw_transaction(&txid, None).into_option(),
+      Self::Esplora(esplora) => esplora.get_transaction(txid),
+    }
+  }
+
+  pub(crate) fn get_transaction_info(
+    &self,
+    txid: Txid,
+  ) -> Result<Option<(Transaction, Option<BlockHash>)>> {
+    match self {
+      Self::Core(client) => client
+        .get_raw_transaction_info(&txid, None)
+        .into_option()?
+        .map(|info| Ok((info.transaction()?, info.blockhash)))
+        .transpose(),
+      Self::Esplora(esplora) => esplora.get_transaction_info(txid),
+    }
+  }
+
+  pub(crate) fn get_tx_out(&self, outpoint: OutPoint) -> Result<Option<TxOut>> {
+    match self {
+      Self::Core(client) => Ok(
+        client
+          .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))?
+          .map(|tx_out| TxOut {
+            value: tx_out.value.to_sat(),
+            script_pubkey: ScriptBuf::from_bytes(tx_out.script_pub_key.hex),
+          }),
+      ),
+      Self::Esplora(esplora) => esplora.get_tx_out(outpoint),
+    }
+  }
+}
@@ -0,0 +1,71 @@
+use {super::*, block_source::BlockSource};
+
+/// Whether `tx`, mined at `height`, spends a taproot output with a tapscript
+/// that commits to `rune`, and that output has at least
+/// `Runestone::COMMIT_CONFIRMATIONS` confirmations.
+pub(super) fn tx_commits_to_rune(
+  client: &BlockSource,
+  height: u32,
+  tx: &Transaction,
+  rune: Rune,
+) -> Result<bool> {
+  let commitment = rune.commitment();
+
+  for input in &tx.input {
+    // extracting a tapscript does not indicate that the input being spent
+    // was actually a taproot output. this is checked below, when we load the
+    // output's entry from the database
+    let Some(tapscript) = input.witness.tapscript() else {
+      continue;
+    };
+
+    for instruction in tapscript.instructions() {
+      // ignore errors, since the extracted script may not be valid
+      let Ok(instruction) = instruction else {
+        break;
+      };
+
+      let Some(pushbytes) = instruction.push_bytes() else {
+        continue;
+      };
+
+      if pushbytes.as_bytes() != commitment {
+        continue;
+      }
+
+      let (input_transaction, blockhash) = client
+        .get_transaction_info(input.previous_output.txid)?
+        .ok_or_else(|| {
+          anyhow!(
+            "can't get input transaction: {}",
+            input.previous_output.txid
+          )
+        })?;
+
+      let taproot = input_transaction.output[input.previous_output.vout.into_usize()]
+        .script_pubkey
+        .is_v1_p2tr();
+
+      if !taproot {
+        continue;
+      }
+
+      // unconfirmed commit transactions have no confirmations
+      let Some(blockhash) = blockhash else {
+        continue;
+      };
+
+      let commit_tx_height = client
+        .get_block_height(blockhash)?
+        .ok_or_else(|| anyhow!("can't get block header: {blockhash}"))?;
+
+      let confirmations = height.checked_sub(commit_tx_height).unwrap() + 1;
+
+      if confirmations >= Runestone::COMMIT_CONFIRMATIONS.into() {
+        return Ok(true);
+      }
+    }
+  }
+
+  Ok(false)
+}
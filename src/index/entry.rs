@@ -40,11 +40,12 @@ impl Entry for Rune {
   }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RuneEntry {
   pub block: u64,
   pub burned: u128,
   pub divisibility: u8,
+  #[schemars(with = "api::schema::Txid")]
   pub etching: Txid,
   pub mints: u128,
   pub number: u64,
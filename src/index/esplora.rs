@@ -0,0 +1,166 @@
+use {
+  super::*,
+  reqwest::{blocking::Response, StatusCode},
+  serde::de::DeserializeOwned,
+};
+
+#[derive(Deserialize)]
+struct BlockInfo {
+  height: u32,
+}
+
+#[derive(Deserialize)]
+struct OutSpend {
+  spent: bool,
+}
+
+#[derive(Deserialize)]
+struct TransactionStatus {
+  block_hash: Option<BlockHash>,
+}
+
+// same as the timeout of Bitcoin Core RPC requests
+pub(crate) const TIMEOUT: Duration = Duration::from_secs(15);
+
+pub(crate) struct Esplora {
+  client: reqwest::blocking::Client,
+  url: String,
+}
+
+impl Esplora {
+  pub(crate) fn new(url: &str, chain: Chain) -> Result<Self> {
+    log::info!("Connecting to Esplora at {url}");
+
+    let esplora = Self {
+      client: reqwest::blocking::Client::builder()
+        .connect_timeout(TIMEOUT)
+        .timeout(TIMEOUT)
+        .build()?,
+      url: url.trim_end_matches('/').into(),
+    };
+
+    let genesis_block_hash = esplora
+      .get_block_hash(0)
+      .with_context(|| format!("failed to connect to Esplora at `{url}`"))?;
+
+    ensure!(
+      genesis_block_hash == Some(chain.genesis_block().block_hash()),
+      "Esplora server at `{url}` is not on {chain}",
+    );
+
+    Ok(esplora)
+  }
+
+  fn get(&self, path: &str) -> Result<Option<Response>> {
+    let response = self.client.get(format!("{}/{path}", self.url)).send()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?))
+  }
+
+  fn get_bytes(&self, path: &str) -> Result<Option<Vec<u8>>> {
+    self
+      .get(path)?
+      .map(|response| Ok(response.bytes()?.to_vec()))
+      .transpose()
+  }
+
+  fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+    self
+      .get(path)?
+      .map(|response| Ok(response.json()?))
+      .transpose()
+  }
+
+  fn get_text(&self, path: &str) -> Result<Option<String>> {
+    self
+      .get(path)?
+      .map(|response| Ok(response.text()?.trim().to_string()))
+      .transpose()
+  }
+
+  pub(crate) fn get_block_count(&self) -> Result<u64> {
+    self
+      .get_text("blocks/tip/height")?
+      .ok_or_else(|| anyhow!("Esplora server has no chain tip"))?
+      .parse()
+      .context("failed to parse chain tip height")
+  }
+
+  pub(crate) fn get_block_hash(&self, height: u32) -> Result<Option<BlockHash>> {
+    self
+      .get_text(&format!("block-height/{height}"))?
+      .map(|hash| hash.parse().context("failed to parse block hash"))
+      .transpose()
+  }
+
+  pub(crate) fn get_block(&self, hash: BlockHash) -> Result<Option<Block>> {
+    self
+      .get_bytes(&format!("block/{hash}/raw"))?
+      .map(|block| Ok(consensus::encode::deserialize(&block)?))
+      .transpose()
+  }
+
+  pub(crate) fn get_block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
+    self
+      .get_text(&format!("block/{hash}/header"))?
+      .map(|header| Ok(consensus::encode::deserialize(&hex::decode(header)?)?))
+      .transpose()
+  }
+
+  pub(crate) fn get_block_height(&self, hash: BlockHash) -> Result<Option<u32>> {
+    Ok(
+      self
+        .get_json::<BlockInfo>(&format!("block/{hash}"))?
+        .map(|info| info.height),
+    )
+  }
+
+  pub(crate) fn get_transaction(&self, txid: Txid) -> Result<Option<Transaction>> {
+    self
+      .get_bytes(&format!("tx/{txid}/raw"))?
+      .map(|transaction| Ok(consensus::encode::deserialize(&transaction)?))
+      .transpose()
+  }
+
+  pub(crate) fn get_transaction_info(
+    &self,
+    txid: Txid,
+  ) -> Result<Option<(Transaction, Option<BlockHash>)>> {
+    let Some(transaction) = self.get_transaction(txid)? else {
+      return Ok(None);
+    };
+
+    let Some(status) = self.get_json::<TransactionStatus>(&format!("tx/{txid}/status"))? else {
+      return Ok(None);
+    };
+
+    Ok(Some((transaction, status.block_hash)))
+  }
+
+  pub(crate) fn get_tx_out(&self, outpoint: OutPoint) -> Result<Option<TxOut>> {
+    let Some(outspend) =
+      self.get_json::<OutSpend>(&format!("tx/{}/outspend/{}", outpoint.txid, outpoint.vout))?
+    else {
+      return Ok(None);
+    };
+
+    if outspend.spent {
+      return Ok(None);
+    }
+
+    Ok(
+      self
+        .get_transaction(outpoint.txid)?
+        .and_then(|transaction| {
+          transaction
+            .output
+            .into_iter()
+            .nth(outpoint.vout.into_usize())
+        }),
+    )
+  }
+}
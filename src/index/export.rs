@@ -0,0 +1,444 @@
+use {super::*, clap::ValueEnum};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub(crate) enum Format {
+  Csv,
+  Jsonl,
+  #[default]
+  Tsv,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub(crate) enum Table {
+  #[default]
+  Inscriptions,
+  RuneBalances,
+  Runes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Column {
+  Address,
+  Charms,
+  ContentLength,
+  ContentType,
+  Fee,
+  Height,
+  Id,
+  Metaprotocol,
+  Number,
+  Parents,
+  Rune,
+  Sat,
+  Satpoint,
+  SequenceNumber,
+}
+
+impl Column {
+  fn name(self) -> &'static str {
+    match self {
+      Self::Address => "address",
+      Self::Charms => "charms",
+      Self::ContentLength => "content_length",
+      Self::ContentType => "content_type",
+      Self::Fee => "fee",
+      Self::Height => "height",
+      Self::Id => "id",
+      Self::Metaprotocol => "metaprotocol",
+      Self::Number => "number",
+      Self::Parents => "parents",
+      Self::Rune => "rune",
+      Self::Sat => "sat",
+      Self::Satpoint => "satpoint",
+      Self::SequenceNumber => "sequence_number",
+    }
+  }
+
+  fn needs_inscription(self) -> bool {
+    matches!(
+      self,
+      Self::ContentLength | Self::ContentType | Self::Metaprotocol
+    )
+  }
+}
+
+pub(crate) struct ExportOptions {
+  pub(crate) columns: Vec<Column>,
+  pub(crate) format: Format,
+  pub(crate) from_height: Option<u32>,
+  // whether CSV and TSV exports start with a row of column names
+  pub(crate) header: bool,
+  pub(crate) table: Table,
+  pub(crate) to_height: Option<u32>,
+}
+
+impl ExportOptions {
+  fn contains_height(&self, height: u64) -> bool {
+    self
+      .from_height
+      .map(|from| height >= u64::from(from))
+      .unwrap_or(true)
+      && self
+        .to_height
+        .map(|to| height <= u64::from(to))
+        .unwrap_or(true)
+  }
+}
+
+struct Field {
+  json: String,
+  text: String,
+}
+
+impl Field {
+  fn new(value: impl Serialize + Display) -> Self {
+    Self {
+      json: serde_json::to_string(&value).unwrap(),
+      text: value.to_string(),
+    }
+  }
+
+  fn optional(value: Option<impl Serialize + Display>) -> Self {
+    match value {
+      Some(value) => Self::new(value),
+      None => Self {
+        json: "null".into(),
+        text: String::new(),
+      },
+    }
+  }
+
+  fn list(values: Vec<impl Serialize + Display>) -> Self {
+    Self {
+      json: serde_json::to_string(&values).unwrap(),
+      text: values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(" "),
+    }
+  }
+}
+
+struct Writer<W: Write> {
+  format: Format,
+  header: bool,
+  wrote_header: bool,
+  writer: W,
+}
+
+impl<W: Write> Writer<W> {
+  fn write(&mut self, row: &[(&str, Field)]) -> Result {
+    if self.header && !self.wrote_header {
+      let names = row.iter().map(|(name, _)| *name).collect::<Vec<&str>>();
+
+      match self.format {
+        Format::Csv => writeln!(self.writer, "{}", names.join(","))?,
+        Format::Jsonl => {}
+        Format::Tsv => writeln!(self.writer, "{}", names.join("\t"))?,
+      }
+
+      self.wrote_header = true;
+    }
+
+    match self.format {
+      Format::Csv => {
+        let cells = row
+          .iter()
+          .map(|(_, field)| {
+            if field.text.contains([',', '"', '\n', '\r']) {
+              format!("\"{}\"", field.text.replace('"', "\"\""))
+            } else {
+              field.text.clone()
+            }
+          })
+          .collect::<Vec<String>>();
+
+        writeln!(self.writer, "{}", cells.join(","))?;
+      }
+      Format::Jsonl => {
+        let members = row
+          .iter()
+          .map(|(name, field)| format!("\"{name}\":{}", field.json))
+          .collect::<Vec<String>>();
+
+        writeln!(self.writer, "{{{}}}", members.join(","))?;
+      }
+      Format::Tsv => {
+        let cells = row
+          .iter()
+          .map(|(_, field)| field.text.replace(['\t', '\n', '\r'], " "))
+          .collect::<Vec<String>>();
+
+        writeln!(self.writer, "{}", cells.join("\t"))?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl Index {
+  pub(crate) fn export(&self, filename: &str, options: &ExportOptions) -> Result {
+    if options.table != Table::Inscriptions && !options.columns.is_empty() {
+      bail!("columns can only be selected when exporting inscriptions");
+    }
+
+    if options.table == Table::RuneBalances
+      && (options.from_height.is_some() || options.to_height.is_some())
+    {
+      bail!("rune balances cannot be filtered by height");
+    }
+
+    if options.table != Table::Inscriptions && !self.index_runes {
+      bail!("exporting runes requires index created with `--index-runes` flag");
+    }
+
+    let mut writer = Writer {
+      format: options.format,
+      header: options.header,
+      wrote_header: false,
+      writer: BufWriter::new(fs::File::create(filename)?),
+    };
+
+    let rtx = self.database.begin_read()?;
+
+    let blocks_indexed = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _header)| height.value() + 1)
+      .unwrap_or(0);
+
+    if options.format == Format::Tsv {
+      writeln!(writer.writer, "# export at block height {}", blocks_indexed)?;
+    }
+
+    log::info!("exporting database tables to {filename}");
+
+    match options.table {
+      Table::Inscriptions => self.export_inscriptions(&rtx, &mut writer, options)?,
+      Table::RuneBalances => self.export_rune_balances(&rtx, &mut writer)?,
+      Table::Runes => self.export_runes(&rtx, &mut writer, options, blocks_indexed)?,
+    }
+
+    writer.writer.flush()?;
+
+    Ok(())
+  }
+
+  fn export_inscriptions(
+    &self,
+    rtx: &ReadTransaction,
+    writer: &mut Writer<impl Write>,
+    options: &ExportOptions,
+  ) -> Result {
+    let columns = if options.columns.is_empty() {
+      vec![Column::Number, Column::Id, Column::Satpoint]
+    } else {
+      options.columns.clone()
+    };
+
+    let needs_inscription = columns.iter().any(|column| column.needs_inscription());
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    let sequence_number_to_rune_id = rtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    for result in sequence_number_to_inscription_entry.iter()? {
+      let entry = result?;
+      let sequence_number = entry.0.value();
+      let entry = InscriptionEntry::load(entry.1.value());
+
+      if !options.contains_height(entry.height.into()) {
+        continue;
+      }
+
+      let satpoint = SatPoint::load(
+        *sequence_number_to_satpoint
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      let inscription = if needs_inscription {
+        self.get_inscription_by_id(entry.id)?
+      } else {
+        None
+      };
+
+      let mut row = Vec::new();
+
+      for column in &columns {
+        let field = match column {
+          Column::Address => Field::new(if satpoint.outpoint == unbound_outpoint() {
+            "unbound".to_string()
+          } else {
+            let output = self
+              .get_transaction(satpoint.outpoint.txid)?
+              .unwrap()
+              .output
+              .into_iter()
+              .nth(satpoint.outpoint.vout.try_into().unwrap())
+              .unwrap();
+            self
+              .settings
+              .chain()
+              .address_from_script(&output.script_pubkey)
+              .map(|address| address.to_string())
+              .unwrap_or_else(|e| e.to_string())
+          }),
+          Column::Charms => Field::list(Charm::charms(entry.charms)),
+          Column::ContentLength => Field::optional(
+            inscription
+              .as_ref()
+              .and_then(|inscription| inscription.content_length()),
+          ),
+          Column::ContentType => Field::optional(
+            inscription
+              .as_ref()
+              .and_then(|inscription| inscription.content_type()),
+          ),
+          Column::Fee => Field::new(entry.fee),
+          Column::Height => Field::new(entry.height),
+          Column::Id => Field::new(entry.id),
+          Column::Metaprotocol => Field::optional(
+            inscription
+              .as_ref()
+              .and_then(|inscription| inscription.metaprotocol()),
+          ),
+          Column::Number => Field::new(entry.inscription_number),
+          Column::Parents => Field::list(
+            entry
+              .parents
+              .iter()
+              .map(|parent| {
+                Ok(
+                  InscriptionEntry::load(
+                    sequence_number_to_inscription_entry
+                      .get(parent)?
+                      .unwrap()
+                      .value(),
+                  )
+                  .id,
+                )
+              })
+              .collect::<Result<Vec<InscriptionId>>>()?,
+          ),
+          Column::Rune => Field::optional(
+            sequence_number_to_rune_id
+              .get(sequence_number)?
+              .map(|id| -> Result<SpacedRune> {
+                Ok(
+                  RuneEntry::load(rune_id_to_rune_entry.get(id.value())?.unwrap().value())
+                    .spaced_rune,
+                )
+              })
+              .transpose()?,
+          ),
+          Column::Sat => Field::optional(entry.sat),
+          Column::Satpoint => Field::new(satpoint),
+          Column::SequenceNumber => Field::new(sequence_number),
+        };
+
+        row.push((column.name(), field));
+      }
+
+      writer.write(&row)?;
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn export_runes(
+    &self,
+    rtx: &ReadTransaction,
+    writer: &mut Writer<impl Write>,
+    options: &ExportOptions,
+    blocks_indexed: u32,
+  ) -> Result {
+    for result in rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+      let id = RuneId::load(id.value());
+      let entry = RuneEntry::load(entry.value());
+
+      if !options.contains_height(entry.block) {
+        continue;
+      }
+
+      let terms = entry.terms.unwrap_or_default();
+
+      writer.write(&[
+        ("id", Field::new(id)),
+        ("rune", Field::new(entry.spaced_rune)),
+        ("number", Field::new(entry.number)),
+        ("block", Field::new(entry.block)),
+        ("etching", Field::new(entry.etching)),
+        ("divisibility", Field::new(entry.divisibility)),
+        ("symbol", Field::optional(entry.symbol)),
+        ("premine", Field::new(entry.premine)),
+        ("mints", Field::new(entry.mints)),
+        ("burned", Field::new(entry.burned)),
+        ("supply", Field::new(entry.supply())),
+        // whether the rune can be minted in the next block
+        (
+          "mintable",
+          Field::new(entry.mintable(blocks_indexed.into()).is_ok()),
+        ),
+        ("cap", Field::optional(terms.cap)),
+        ("amount", Field::optional(terms.amount)),
+        ("height_start", Field::optional(terms.height.0)),
+        ("height_end", Field::optional(terms.height.1)),
+        ("offset_start", Field::optional(terms.offset.0)),
+        ("offset_end", Field::optional(terms.offset.1)),
+        ("timestamp", Field::new(entry.timestamp)),
+        ("turbo", Field::new(entry.turbo)),
+      ])?;
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn export_rune_balances(&self, rtx: &ReadTransaction, writer: &mut Writer<impl Write>) -> Result {
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (outpoint, balances_buffer) = result?;
+      let outpoint = OutPoint::load(*outpoint.value());
+      let balances_buffer = balances_buffer.value();
+
+      let mut i = 0;
+      while i < balances_buffer.len() {
+        let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
+        i += length;
+
+        let entry = RuneEntry::load(rune_id_to_rune_entry.get(id.store())?.unwrap().value());
+
+        writer.write(&[
+          ("outpoint", Field::new(outpoint)),
+          ("id", Field::new(id)),
+          ("rune", Field::new(entry.spaced_rune)),
+          ("amount", Field::new(amount)),
+          ("divisibility", Field::new(entry.divisibility)),
+        ])?;
+      }
+
+      if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -1,14 +1,25 @@
 use {
   super::*,
   base64::Engine,
+  futures::{stream, StreamExt, TryStreamExt},
   hyper::{client::HttpConnector, Body, Client, Method, Request, Uri},
   serde_json::{json, Value},
 };
 
-pub(crate) struct Fetcher {
-  auth: String,
-  client: Client<HttpConnector>,
-  url: Uri,
+// Esplora serves one transaction per request, so each batch is fetched with
+// this many requests in flight
+const ESPLORA_CONCURRENT_REQUESTS: usize = 16;
+
+pub(crate) enum Fetcher {
+  Core {
+    auth: String,
+    client: Client<HttpConnector>,
+    url: Uri,
+  },
+  Esplora {
+    client: reqwest::Client,
+    url: String,
+  },
 }
 
 #[derive(Deserialize, Debug)]
@@ -26,6 +37,16 @@ struct JsonError {
 
 impl Fetcher {
   pub(crate) fn new(settings: &Settings) -> Result<Self> {
+    if let Some(url) = settings.esplora_url() {
+      return Ok(Fetcher::Esplora {
+        client: reqwest::Client::builder()
+          .connect_timeout(esplora::TIMEOUT)
+          .timeout(esplora::TIMEOUT)
+          .build()?,
+        url: url.trim_end_matches('/').into(),
+      });
+    }
+
     let client = Client::new();
 
     let url = if settings.bitcoin_rpc_url(None).starts_with("http://") {
@@ -42,7 +63,7 @@ impl Fetcher {
       "Basic {}",
       &base64::engine::general_purpose::STANDARD.encode(auth)
     );
-    Ok(Fetcher { client, url, auth })
+    Ok(Fetcher::Core { client, url, auth })
   }
 
   pub(crate) async fn get_transactions(&self, txids: Vec<Txid>) -> Result<Vec<Transaction>> {
@@ -50,6 +71,14 @@ impl Fetcher {
       return Ok(Vec::new());
     }
 
+    if let Fetcher::Esplora { client, url } = self {
+      return stream::iter(txids)
+        .map(|txid| Self::get_esplora_transaction(client, url, txid))
+        .buffered(ESPLORA_CONCURRENT_REQUESTS)
+        .try_collect()
+        .await;
+    }
+
     let mut reqs = Vec::with_capacity(txids.len());
     for (i, txid) in txids.iter().enumerate() {
       let req = json!({
@@ -119,15 +148,60 @@ impl Fetcher {
     Ok(txs)
   }
 
+  async fn get_esplora_transaction(
+    client: &reqwest::Client,
+    url: &str,
+    txid: Txid,
+  ) -> Result<Transaction> {
+    let mut retries = 0;
+
+    loop {
+      match Self::try_get_esplora_transaction(client, url, txid).await {
+        Ok(tx) => return Ok(tx),
+        Err(error) => {
+          if retries >= 5 {
+            return Err(anyhow!(
+              "failed to fetch raw transaction {txid} after 5 retries: {error}"
+            ));
+          }
+
+          log::info!("failed to fetch raw transaction {txid}, retrying: {error}");
+
+          tokio::time::sleep(Duration::from_millis(100 * u64::pow(2, retries))).await;
+          retries += 1;
+        }
+      }
+    }
+  }
+
+  async fn try_get_esplora_transaction(
+    client: &reqwest::Client,
+    url: &str,
+    txid: Txid,
+  ) -> Result<Transaction> {
+    let response = client
+      .get(format!("{url}/tx/{txid}/raw"))
+      .send()
+      .await?
+      .error_for_status()?;
+
+    consensus::deserialize(&response.bytes().await?)
+      .map_err(|e| anyhow!("Esplora response for {txid} not valid bitcoin tx: {e}"))
+  }
+
   async fn try_get_transactions(&self, body: String) -> Result<Vec<JsonResponse<String>>> {
+    let Fetcher::Core { auth, client, url } = self else {
+      unreachable!("JSON-RPC requests are only sent to Bitcoin Core");
+    };
+
     let req = Request::builder()
       .method(Method::POST)
-      .uri(&self.url)
-      .header(hyper::header::AUTHORIZATION, &self.auth)
+      .uri(url)
+      .header(hyper::header::AUTHORIZATION, auth)
       .header(hyper::header::CONTENT_TYPE, "application/json")
       .body(Body::from(body))?;
 
-    let response = self.client.request(req).await?;
+    let response = client.request(req).await?;
 
     let buf = hyper::body::to_bytes(response).await?;
 
@@ -0,0 +1,363 @@
+use {
+  super::*,
+  base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+  schemars::{Schema, SchemaGenerator},
+  std::{
+    borrow::Cow,
+    ops::Bound::{self, Excluded, Unbounded},
+  },
+};
+
+pub(super) type Bounds = (Bound<u128>, Bound<u128>);
+
+// Opaque position between two items of a list, identified by the key of the
+// item it follows or precedes. Unlike page offsets, cursors don't shift when
+// new items are added.
+#[derive(Debug, PartialEq, Copy, Clone, DeserializeFromStr, SerializeDisplay)]
+pub enum Cursor {
+  After(u128),
+  Before(u128),
+}
+
+impl JsonSchema for Cursor {
+  fn schema_name() -> Cow<'static, str> {
+    "Cursor".into()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    schemars::json_schema!({
+      "type": "string",
+      "description": "Opaque pagination cursor.",
+      "pattern": "^[A-Za-z0-9_-]+$",
+    })
+  }
+}
+
+impl Display for Cursor {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let position = match self {
+      Self::After(key) => format!("a{key}"),
+      Self::Before(key) => format!("b{key}"),
+    };
+
+    write!(f, "{}", URL_SAFE_NO_PAD.encode(position))
+  }
+}
+
+impl FromStr for Cursor {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let position = String::from_utf8(URL_SAFE_NO_PAD.decode(s)?)?;
+
+    if let Some(key) = position.strip_prefix('a') {
+      Ok(Self::After(key.parse()?))
+    } else if let Some(key) = position.strip_prefix('b') {
+      Ok(Self::Before(key.parse()?))
+    } else {
+      bail!("invalid cursor `{s}`")
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+  Asc,
+  Desc,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Pagination {
+  pub cursor: Option<Cursor>,
+  pub limit: usize,
+  pub order: Order,
+  // index of the page to return if there is no cursor
+  pub page: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub more: bool,
+  pub next: Option<Cursor>,
+  pub prev: Option<Cursor>,
+}
+
+// Returns the page of items selected by `pagination`. `range` must return
+// the keys and items within `bounds`, in ascending key order if `ascending`
+// is true and descending order otherwise, skipping the first `skip` and
+// returning at most `take`.
+pub(super) fn paginate<T>(
+  pagination: Pagination,
+  mut range: impl FnMut(Bounds, bool, usize, usize) -> Result<Vec<(u128, T)>>,
+) -> Result<Page<T>> {
+  let Pagination {
+    cursor,
+    limit,
+    order,
+    page,
+  } = pagination;
+
+  let ascending = order == Order::Asc;
+
+  let before = |key| {
+    if ascending {
+      (Unbounded, Excluded(key))
+    } else {
+      (Excluded(key), Unbounded)
+    }
+  };
+
+  let after = |key| {
+    if ascending {
+      (Excluded(key), Unbounded)
+    } else {
+      (Unbounded, Excluded(key))
+    }
+  };
+
+  let (mut items, backward) = match cursor {
+    None => (
+      range(
+        (Unbounded, Unbounded),
+        ascending,
+        page.saturating_mul(limit),
+        limit.saturating_add(1),
+      )?,
+      false,
+    ),
+    Some(Cursor::After(key)) => (
+      range(after(key), ascending, 0, limit.saturating_add(1))?,
+      false,
+    ),
+    Some(Cursor::Before(key)) => (
+      range(before(key), !ascending, 0, limit.saturating_add(1))?,
+      true,
+    ),
+  };
+
+  let truncated = items.len() > limit;
+
+  items.truncate(limit);
+
+  if backward {
+    items.reverse();
+  }
+
+  let (Some(&(first, _)), Some(&(last, _))) = (items.first(), items.last()) else {
+    return Ok(Page {
+      items: Vec::new(),
+      more: false,
+      next: None,
+      prev: None,
+    });
+  };
+
+  let more = if backward {
+    !range(after(last), ascending, 0, 1)?.is_empty()
+  } else {
+    truncated
+  };
+
+  let less = if backward {
+    truncated
+  } else {
+    !range(before(first), !ascending, 0, 1)?.is_empty()
+  };
+
+  Ok(Page {
+    items: items.into_iter().map(|(_key, item)| item).collect(),
+    more,
+    next: more.then_some(Cursor::After(last)),
+    prev: less.then_some(Cursor::Before(first)),
+  })
+}
+
+// Converts `bounds` to bounds on table keys. Keys out of range saturate.
+pub(super) fn key_bounds<K>(bounds: Bounds, key: impl Fn(u128) -> K) -> (Bound<K>, Bound<K>) {
+  let convert = |bound| match bound {
+    Bound::Included(k) => Bound::Included(key(k)),
+    Bound::Excluded(k) => Bound::Excluded(key(k)),
+    Bound::Unbounded => Bound::Unbounded,
+  };
+
+  (convert(bounds.0), convert(bounds.1))
+}
+
+pub(super) fn contains(bounds: Bounds, key: u128) -> bool {
+  std::ops::RangeBounds::contains(&bounds, &key)
+}
+
+pub(super) fn directed<'a, I: DoubleEndedIterator + 'a>(
+  iter: I,
+  ascending: bool,
+) -> Box<dyn Iterator<Item = I::Item> + 'a> {
+  if ascending {
+    Box::new(iter)
+  } else {
+    Box::new(iter.rev())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn page(keys: &[u128], pagination: Pagination) -> Page<u128> {
+    paginate(pagination, |bounds, ascending, skip, take| {
+      Ok(
+        directed(keys.iter().copied(), ascending)
+          .filter(|key| contains(bounds, *key))
+          .skip(skip)
+          .take(take)
+          .map(|key| (key, key))
+          .collect(),
+      )
+    })
+    .unwrap()
+  }
+
+  fn pagination(cursor: Option<Cursor>, order: Order) -> Pagination {
+    Pagination {
+      cursor,
+      limit: 2,
+      order,
+      page: 0,
+    }
+  }
+
+  #[test]
+  fn cursor_round_trips() {
+    for cursor in [
+      Cursor::After(0),
+      Cursor::Before(1),
+      Cursor::After(u128::MAX),
+    ] {
+      assert_eq!(cursor.to_string().parse::<Cursor>().unwrap(), cursor);
+    }
+
+    assert_eq!(Cursor::After(5).to_string(), "YTU");
+  }
+
+  #[test]
+  fn invalid_cursors_are_errors() {
+    assert!("".parse::<Cursor>().is_err());
+    assert!("!".parse::<Cursor>().is_err());
+    assert!(URL_SAFE_NO_PAD.encode("c5").parse::<Cursor>().is_err());
+    assert!(URL_SAFE_NO_PAD.encode("a").parse::<Cursor>().is_err());
+    assert!(URL_SAFE_NO_PAD.encode("a-1").parse::<Cursor>().is_err());
+  }
+
+  #[test]
+  fn ascending() {
+    let keys = [1, 2, 3, 4, 5];
+
+    let first = page(&keys, pagination(None, Order::Asc));
+    assert_eq!(
+      first,
+      Page {
+        items: vec![1, 2],
+        more: true,
+        next: Some(Cursor::After(2)),
+        prev: None,
+      }
+    );
+
+    let second = page(&keys, pagination(first.next, Order::Asc));
+    assert_eq!(
+      second,
+      Page {
+        items: vec![3, 4],
+        more: true,
+        next: Some(Cursor::After(4)),
+        prev: Some(Cursor::Before(3)),
+      }
+    );
+
+    let third = page(&keys, pagination(second.next, Order::Asc));
+    assert_eq!(
+      third,
+      Page {
+        items: vec![5],
+        more: false,
+        next: None,
+        prev: Some(Cursor::Before(5)),
+      }
+    );
+
+    assert_eq!(page(&keys, pagination(third.prev, Order::Asc)), second);
+    assert_eq!(page(&keys, pagination(second.prev, Order::Asc)), first);
+  }
+
+  #[test]
+  fn descending() {
+    let keys = [1, 2, 3, 4, 5];
+
+    let first = page(&keys, pagination(None, Order::Desc));
+    assert_eq!(
+      first,
+      Page {
+        items: vec![5, 4],
+        more: true,
+        next: Some(Cursor::After(4)),
+        prev: None,
+      }
+    );
+
+    let second = page(&keys, pagination(first.next, Order::Desc));
+    assert_eq!(
+      second,
+      Page {
+        items: vec![3, 2],
+        more: true,
+        next: Some(Cursor::After(2)),
+        prev: Some(Cursor::Before(3)),
+      }
+    );
+
+    assert_eq!(page(&keys, pagination(second.prev, Order::Desc)), first);
+  }
+
+  #[test]
+  fn pages_do_not_shift_when_items_are_added() {
+    let first = page(&[1, 2, 3, 4, 5], pagination(None, Order::Desc));
+
+    assert_eq!(
+      page(&[1, 2, 3, 4, 5, 6, 7], pagination(first.next, Order::Desc)).items,
+      [3, 2]
+    );
+  }
+
+  #[test]
+  fn page_index_is_used_without_cursor() {
+    assert_eq!(
+      page(
+        &[1, 2, 3, 4, 5],
+        Pagination {
+          page: 1,
+          ..pagination(None, Order::Asc)
+        }
+      ),
+      Page {
+        items: vec![3, 4],
+        more: true,
+        next: Some(Cursor::After(4)),
+        prev: Some(Cursor::Before(3)),
+      }
+    );
+  }
+
+  #[test]
+  fn empty_page_has_no_cursors() {
+    assert_eq!(
+      page(&[1, 2], pagination(Some(Cursor::After(2)), Order::Asc)),
+      Page {
+        items: Vec::new(),
+        more: false,
+        next: None,
+        prev: None,
+      }
+    );
+  }
+}
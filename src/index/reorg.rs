@@ -37,10 +37,7 @@ impl Reorg {
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
-          let bitcoind_block_hash = index
-            .client
-            .get_block_hash(u64::from(height.saturating_sub(depth)))
-            .into_option()?;
+          let bitcoind_block_hash = index.client.get_block_hash(height.saturating_sub(depth))?;
 
           if index_block_hash == bitcoind_block_hash {
             return Err(anyhow!(reorg::Error::Recoverable { height, depth }));
@@ -84,15 +81,9 @@ impl Reorg {
     }
 
     if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
-      && u32::try_from(
-        index
-          .settings
-          .bitcoin_rpc_client(None)?
-          .get_blockchain_info()?
-          .headers,
-      )
-      .unwrap()
-      .saturating_sub(height)
+      && u32::try_from(index.client.get_header_count()?)
+        .unwrap()
+        .saturating_sub(height)
         <= CHAIN_TIP_DISTANCE
     {
       let wtx = index.begin_write()?;
@@ -0,0 +1,277 @@
+use super::*;
+
+impl Index {
+  /// Simulate the effect of `tx` being mined in the next block. `input_values`
+  /// holds the value of the output spent by each of the transaction's inputs.
+  pub(crate) fn simulate_transaction(
+    &self,
+    tx: &Transaction,
+    input_values: &[u64],
+  ) -> Result<api::Simulation> {
+    assert_eq!(tx.input.len(), input_values.len());
+
+    let txid = tx.txid();
+
+    let overflow = || anyhow!("transaction value overflows");
+
+    let total_input_value = input_values
+      .iter()
+      .try_fold(0u64, |total, value| total.checked_add(*value))
+      .ok_or_else(overflow)?;
+
+    let total_output_value = tx
+      .output
+      .iter()
+      .try_fold(0u64, |total, tx_out| total.checked_add(tx_out.value))
+      .ok_or_else(overflow)?;
+
+    let fee = total_input_value
+      .checked_sub(total_output_value)
+      .ok_or_else(|| anyhow!("transaction output value exceeds input value"))?;
+
+    // offsets of inputs are at most the total input value, so they cannot
+    // overflow
+    let mut input_offsets = Vec::with_capacity(input_values.len());
+    let mut offset = 0;
+    for value in input_values {
+      input_offsets.push(offset);
+      offset += value;
+    }
+
+    let mut inscriptions = Vec::new();
+
+    for (input, start) in tx.input.iter().zip(&input_offsets) {
+      for (satpoint, id) in self.get_inscriptions_on_output_with_satpoints(input.previous_output)? {
+        inscriptions.push((start.checked_add(satpoint.offset).ok_or_else(overflow)?, id));
+      }
+    }
+
+    for (index, envelope) in ParsedEnvelope::from_transaction(tx).into_iter().enumerate() {
+      let Some(start) = input_offsets.get(envelope.input.into_usize()) else {
+        continue;
+      };
+
+      let offset = envelope
+        .payload
+        .pointer()
+        .filter(|&pointer| pointer < total_output_value)
+        .unwrap_or(*start);
+
+      inscriptions.push((
+        offset,
+        InscriptionId {
+          txid,
+          index: index.try_into().unwrap(),
+        },
+      ));
+    }
+
+    inscriptions.sort_by_key(|(offset, _id)| *offset);
+
+    let input_sat_ranges = if self.has_sat_index() {
+      tx.input
+        .iter()
+        .map(|input| self.list(input.previous_output))
+        .collect::<Result<Option<Vec<Vec<(u64, u64)>>>>>()?
+        .map(|ranges| ranges.into_iter().flatten().collect::<Vec<(u64, u64)>>())
+    } else {
+      None
+    };
+
+    let (allocated, burned, runes) = if self.has_rune_index() {
+      self.simulate_rune_transfers(tx)?
+    } else {
+      Default::default()
+    };
+
+    let piles = |balances: HashMap<RuneId, Lot>| {
+      balances
+        .into_iter()
+        .filter(|(_id, balance)| *balance > 0)
+        .map(|(id, balance)| {
+          let (spaced_rune, divisibility, symbol) = runes[&id];
+          (
+            spaced_rune,
+            Pile {
+              amount: balance.n(),
+              divisibility,
+              symbol,
+            },
+          )
+        })
+        .collect::<BTreeMap<SpacedRune, Pile>>()
+    };
+
+    let mut allocated = allocated.into_iter();
+
+    let mut outputs = Vec::with_capacity(tx.output.len());
+    let mut start = 0;
+    for tx_out in &tx.output {
+      // outputs end at most at the total output value, so they cannot overflow
+      let end = start + tx_out.value;
+
+      outputs.push(api::SimulatedOutput {
+        inscriptions: inscriptions
+          .iter()
+          .filter(|(offset, _id)| (start..end).contains(offset))
+          .map(|(_offset, id)| *id)
+          .collect(),
+        runes: piles(allocated.next().unwrap_or_default()),
+        sat_ranges: input_sat_ranges
+          .as_ref()
+          .map(|ranges| Self::slice_sat_ranges(ranges, start, end)),
+        value: tx_out.value,
+      });
+
+      start = end;
+    }
+
+    Ok(api::Simulation {
+      burned: piles(burned),
+      fee,
+      fee_inscriptions: inscriptions
+        .iter()
+        .filter(|(offset, _id)| *offset >= total_output_value)
+        .map(|(_offset, id)| *id)
+        .collect(),
+      fee_sat_ranges: input_sat_ranges
+        .as_ref()
+        .map(|ranges| Self::slice_sat_ranges(ranges, total_output_value, total_input_value)),
+      outputs,
+    })
+  }
+
+  #[allow(clippy::type_complexity)]
+  fn simulate_rune_transfers(
+    &self,
+    tx: &Transaction,
+  ) -> Result<(
+    Vec<HashMap<RuneId, Lot>>,
+    HashMap<RuneId, Lot>,
+    HashMap<RuneId, (SpacedRune, u8, Option<char>)>,
+  )> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let id_to_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let rune_to_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+
+    let height = self.block_count()?;
+
+    let mut runes = HashMap::new();
+
+    let mut entry = |id: RuneId| -> Result<Option<RuneEntry>> {
+      let Some(entry) = id_to_entry
+        .get(&id.store())?
+        .map(|entry| RuneEntry::load(entry.value()))
+      else {
+        return Ok(None);
+      };
+
+      runes.insert(id, (entry.spaced_rune, entry.divisibility, entry.symbol));
+
+      Ok(Some(entry))
+    };
+
+    let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
+
+    for input in &tx.input {
+      let Some(balances) = outpoint_to_balances.get(&input.previous_output.store())? else {
+        continue;
+      };
+
+      let buffer = balances.value();
+      let mut i = 0;
+      while i < buffer.len() {
+        let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
+        i += len;
+        entry(id)?;
+        *unallocated.entry(id).or_default() += balance;
+      }
+    }
+
+    let artifact = Runestone::decipher(tx);
+
+    if let Some(id) = artifact.as_ref().and_then(Artifact::mint) {
+      if let Some(amount) = entry(id)?.and_then(|entry| entry.mintable(height.into()).ok()) {
+        *unallocated.entry(id).or_default() += amount;
+      }
+    }
+
+    // the etched rune's ID isn't known until the transaction is mined, so
+    // use a placeholder in the next block that no mined rune can have
+    let mut etched = None;
+
+    let etching = match &artifact {
+      Some(Artifact::Runestone(runestone)) => runestone
+        .etching
+        .map(|etching| (etching.rune, Some(etching))),
+      Some(Artifact::Cenotaph(cenotaph)) => cenotaph.etching.map(|rune| (Some(rune), None)),
+      None => None,
+    };
+
+    if let Some((rune, etching)) = etching {
+      let id = RuneId {
+        block: height.into(),
+        tx: u32::MAX,
+      };
+
+      // etchings follow the same rules as when indexing, with reserved
+      // runes named after the placeholder ID
+      let rune = match rune {
+        Some(rune) => (rune
+          >= Rune::minimum_at_height(self.settings.chain().network(), Height(height))
+          && !rune.is_reserved()
+          && rune_to_id.get(rune.0)?.is_none()
+          && commitment::tx_commits_to_rune(&self.client, height, tx, rune)?)
+        .then_some(rune),
+        None => Some(Rune::reserved(id.block, id.tx)),
+      };
+
+      if let Some(rune) = rune {
+        let etching = etching.unwrap_or_default();
+
+        runes.insert(
+          id,
+          (
+            SpacedRune {
+              rune,
+              spacers: etching.spacers.unwrap_or_default(),
+            },
+            etching.divisibility.unwrap_or_default(),
+            etching.symbol,
+          ),
+        );
+
+        *unallocated.entry(id).or_default() += etching.premine.unwrap_or_default();
+
+        etched = Some(id);
+      }
+    }
+
+    let allocation::Allocation { allocated, burned } =
+      allocation::allocate(tx, artifact.as_ref(), etched, unallocated);
+
+    Ok((allocated, burned, runes))
+  }
+
+  fn slice_sat_ranges(ranges: &[(u64, u64)], start: u64, end: u64) -> Vec<(u64, u64)> {
+    let mut slice = Vec::new();
+    let mut offset = 0;
+
+    for (range_start, range_end) in ranges {
+      let size = range_end - range_start;
+
+      let from = start.max(offset);
+      let to = end.min(offset + size);
+
+      if from < to {
+        slice.push((range_start + from - offset, range_start + to - offset));
+      }
+
+      offset += size;
+    }
+
+    slice
+  }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+const LATEST: &str = "LATEST";
+
+impl Index {
+  fn snapshot_name(rtx: &rtx::Rtx) -> Result<Option<String>> {
+    let (Some(height), Some(hash)) = (rtx.block_height()?, rtx.block_hash(None)?) else {
+      return Ok(None);
+    };
+
+    Ok(Some(format!("{height}-{hash}.redb")))
+  }
+
+  pub(crate) fn publish_snapshot(&self, dir: &Path) -> Result {
+    fs::create_dir_all(dir)?;
+
+    let Some(name) = Self::snapshot_name(&self.begin_read()?)? else {
+      return Ok(());
+    };
+
+    if dir.join(&name).exists() {
+      return Ok(());
+    }
+
+    let partial = dir.join("snapshot.partial");
+
+    {
+      // pages freed by commits are not reused while an older read transaction
+      // is open, so the committed state that the copy's header points to
+      // stays intact while the index file is copied, without keeping the
+      // updater from committing
+      let _rtx = self.database.begin_read()?;
+
+      fs::copy(&self.path, &partial)?;
+    }
+
+    // a copy of an open index needs recovery, which is done once here instead
+    // of by every follower
+    let database = Database::builder()
+      .set_cache_size(self.settings.index_cache_size())
+      .open(&partial)?;
+
+    // the updater may have committed after the name was read, so the snapshot
+    // is named after the state that was copied
+    let Some(name) = Self::snapshot_name(&rtx::Rtx(database.begin_read()?))? else {
+      return Ok(());
+    };
+
+    drop(database);
+
+    if dir.join(&name).exists() {
+      fs::remove_file(&partial)?;
+      return Ok(());
+    }
+
+    fs::rename(&partial, dir.join(&name))?;
+
+    let previous = Self::latest_snapshot(dir)?;
+
+    fs::write(dir.join("LATEST.partial"), &name)?;
+    fs::rename(dir.join("LATEST.partial"), dir.join(LATEST))?;
+
+    log::info!("Published index snapshot {name} to {}", dir.display());
+
+    // keep the previous snapshot, since followers may still be copying it
+    for entry in fs::read_dir(dir)? {
+      let path = entry?.path();
+
+      let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        continue;
+      };
+
+      if file_name.ends_with(".redb") && file_name != name && Some(file_name) != previous.as_deref()
+      {
+        fs::remove_file(&path)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn follow_snapshot(&self, dir: &Path) -> Result<bool> {
+    let Some(latest) = Self::latest_snapshot(dir)? else {
+      return Ok(false);
+    };
+
+    if Self::snapshot_name(&self.begin_read()?)?.as_ref() == Some(&latest) {
+      return Ok(false);
+    }
+
+    let partial = self.path.with_extension("partial");
+
+    // the snapshot is copied because opening a database writes to it, and
+    // other followers may be opening the same snapshot. the current database
+    // keeps serving requests while it is copied, and on Linux, `fs::copy`
+    // clones the file instead of copying its contents on filesystems which
+    // support it.
+    fs::copy(dir.join(&latest), &partial)
+      .with_context(|| format!("failed to copy index snapshot `{latest}`"))?;
+
+    let database = Database::builder()
+      .set_cache_size(self.settings.index_cache_size())
+      .open(&partial)?;
+
+    Self::check_schema_version(&database, &dir.join(&latest))?;
+
+    {
+      let rtx = database.begin_read()?;
+      let statistics = rtx.open_table(STATISTIC_TO_COUNT)?;
+
+      ensure!(
+        Self::is_statistic_set(&statistics, Statistic::IndexAddresses)? == self.index_addresses
+          && Self::is_statistic_set(&statistics, Statistic::IndexRunes)? == self.index_runes
+          && Self::is_statistic_set(&statistics, Statistic::IndexSats)? == self.index_sats
+          && Self::is_statistic_set(&statistics, Statistic::IndexSpentSats)?
+            == self.index_spent_sats
+          && Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?
+            == self.index_transactions,
+        "index snapshot `{latest}` was built with different index options than this server",
+      );
+    }
+
+    // the database is already open, so renaming over the current index file
+    // leaves readers of the old one undisturbed
+    fs::rename(&partial, &self.path)?;
+
+    self.database.replace(database);
+
+    log::info!("Following index snapshot {latest}");
+
+    Ok(true)
+  }
+
+  fn latest_snapshot(dir: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(dir.join(LATEST)) {
+      Ok(latest) => Ok(Some(latest.trim().into())),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+      Err(err) => Err(err.into()),
+    }
+  }
+}
@@ -0,0 +1,30 @@
+use {
+  super::*,
+  redb::TransactionError,
+  std::sync::{Arc, RwLock},
+};
+
+pub(crate) struct SharedDatabase(RwLock<Arc<Database>>);
+
+impl SharedDatabase {
+  pub(crate) fn new(database: Database) -> Self {
+    Self(RwLock::new(Arc::new(database)))
+  }
+
+  fn current(&self) -> Arc<Database> {
+    self.0.read().unwrap().clone()
+  }
+
+  pub(crate) fn begin_read(&self) -> Result<ReadTransaction, TransactionError> {
+    self.current().begin_read()
+  }
+
+  pub(crate) fn begin_write(&self) -> Result<WriteTransaction, TransactionError> {
+    self.current().begin_write()
+  }
+
+  // transactions begun before the swap keep reading from the old database
+  pub(crate) fn replace(&self, database: Database) {
+    *self.0.write().unwrap() = Arc::new(database);
+  }
+}
@@ -1,6 +1,6 @@
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
-  super::{fetcher::Fetcher, *},
+  super::{block_source::BlockSource, fetcher::Fetcher, *},
   futures::future::try_join_all,
   tokio::sync::{
     broadcast::{self, error::TryRecvError},
@@ -12,22 +12,76 @@ mod inscription_updater;
 mod rune_updater;
 
 pub(crate) struct BlockData {
+  pub(crate) artifacts: Vec<Option<Artifact>>,
+  pub(crate) envelopes: Vec<Vec<ParsedEnvelope>>,
   pub(crate) header: Header,
   pub(crate) txdata: Vec<(Transaction, Txid)>,
 }
 
-impl From<Block> for BlockData {
-  fn from(block: Block) -> Self {
+impl BlockData {
+  fn envelopes(&self, tx_offset: usize) -> &[ParsedEnvelope] {
+    self
+      .envelopes
+      .get(tx_offset)
+      .map(Vec::as_slice)
+      .unwrap_or_default()
+  }
+}
+
+// Parses the parts of a block that do not depend on index state, so that it
+// can be done on worker threads ahead of indexing. Envelopes and artifacts are
+// only parsed for blocks in which they will be indexed.
+#[derive(Clone, Copy)]
+struct BlockParser {
+  first_inscription_height: u32,
+  first_rune_height: u32,
+  index_inscriptions: bool,
+  index_runes: bool,
+}
+
+impl BlockParser {
+  fn new(index: &Index) -> Self {
+    Self {
+      first_inscription_height: index.first_inscription_height,
+      first_rune_height: index.settings.first_rune_height(),
+      index_inscriptions: index.settings.index_inscriptions(),
+      index_runes: index.index_runes,
+    }
+  }
+
+  fn parse(self, height: u32, block: Block) -> BlockData {
+    let txdata = block
+      .txdata
+      .into_iter()
+      .map(|transaction| {
+        let txid = transaction.txid();
+        (transaction, txid)
+      })
+      .collect::<Vec<(Transaction, Txid)>>();
+
+    let envelopes = if self.index_inscriptions && height >= self.first_inscription_height {
+      txdata
+        .iter()
+        .map(|(tx, _)| ParsedEnvelope::from_transaction(tx))
+        .collect()
+    } else {
+      Vec::new()
+    };
+
+    let artifacts = if self.index_runes && height >= self.first_rune_height {
+      txdata
+        .iter()
+        .map(|(tx, _)| Runestone::decipher(tx))
+        .collect()
+    } else {
+      Vec::new()
+    };
+
     BlockData {
+      artifacts,
+      envelopes,
       header: block.header,
-      txdata: block
-        .txdata
-        .into_iter()
-        .map(|transaction| {
-          let txid = transaction.txid();
-          (transaction, txid)
-        })
-        .collect(),
+      txdata,
     }
   }
 }
@@ -163,29 +217,70 @@ impl<'index> Updater<'index> {
 
     let height_limit = index.height_limit;
 
-    let client = index.settings.bitcoin_rpc_client(None)?;
+    let client = BlockSource::new(&index.settings)?;
 
     let first_inscription_height = index.first_inscription_height;
 
-    thread::spawn(move || loop {
-      if let Some(height_limit) = height_limit {
-        if height >= height_limit {
+    let parser = BlockParser::new(index);
+
+    let workers = thread::available_parallelism()
+      .map(|workers| workers.get())
+      .unwrap_or(1);
+
+    let (block_senders, data_receivers): (Vec<_>, Vec<_>) = (0..workers)
+      .map(|_| {
+        let (block_sender, block_receiver) = std::sync::mpsc::sync_channel::<(u32, Block)>(1);
+        let (data_sender, data_receiver) = std::sync::mpsc::sync_channel(1);
+
+        thread::spawn(move || {
+          for (height, block) in block_receiver {
+            if data_sender.send(parser.parse(height, block)).is_err() {
+              break;
+            }
+          }
+        });
+
+        (block_sender, data_receiver)
+      })
+      .unzip();
+
+    // blocks are handed to workers and collected from them in the same
+    // round-robin order, so they arrive in order
+    thread::spawn(move || {
+      for data_receiver in data_receivers.iter().cycle() {
+        let Ok(block) = data_receiver.recv() else {
+          break;
+        };
+
+        if let Err(err) = tx.send(block) {
+          log::info!("Block receiver disconnected: {err}");
           break;
         }
       }
+    });
+
+    thread::spawn(move || {
+      let mut block_senders = block_senders.iter().cycle();
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
-        Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
-            log::info!("Block receiver disconnected: {err}");
+      loop {
+        if let Some(height_limit) = height_limit {
+          if height >= height_limit {
             break;
           }
-          height += 1;
         }
-        Ok(None) => break,
-        Err(err) => {
-          log::error!("failed to fetch block {height}: {err}");
-          break;
+
+        match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+          Ok(Some(block)) => {
+            if block_senders.next().unwrap().send((height, block)).is_err() {
+              break;
+            }
+            height += 1;
+          }
+          Ok(None) => break,
+          Err(err) => {
+            log::error!("failed to fetch block {height}: {err}");
+            break;
+          }
         }
       }
     });
@@ -194,30 +289,31 @@ impl<'index> Updater<'index> {
   }
 
   fn get_block_with_retries(
-    client: &Client,
+    client: &BlockSource,
     height: u32,
     index_sats: bool,
     first_inscription_height: u32,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
     loop {
-      match client
-        .get_block_hash(height.into())
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if index_sats || height >= first_inscription_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
-              }
-            })
-            .transpose()
-        }) {
+      match client.get_block_hash(height).and_then(|option| {
+        option
+          .map(|hash| {
+            if index_sats || height >= first_inscription_height {
+              client
+                .get_block(hash)?
+                .ok_or_else(|| anyhow!("block {hash} not found"))
+            } else {
+              Ok(Block {
+                header: client
+                  .get_block_header(hash)?
+                  .ok_or_else(|| anyhow!("block header {hash} not found"))?,
+                txdata: Vec::new(),
+              })
+            }
+          })
+          .transpose()
+      }) {
         Err(err) => {
           if cfg!(test) {
             return Err(err);
@@ -403,6 +499,8 @@ impl<'index> Updater<'index> {
 
     if let Some(address_txout_receiver) = address_txout_receiver {
       let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+      let mut script_pubkey_to_transaction_count =
+        wtx.open_table(SCRIPT_PUBKEY_TO_TRANSACTION_COUNT)?;
       for (tx, txid) in &block.txdata {
         self.index_transaction_output_script_pubkeys(
           tx,
@@ -410,6 +508,7 @@ impl<'index> Updater<'index> {
           address_txout_receiver,
           utxo_cache,
           &mut script_pubkey_to_outpoint,
+          &mut script_pubkey_to_transaction_count,
           &mut outpoint_to_txout,
           index_inscriptions,
         )?;
@@ -540,6 +639,7 @@ impl<'index> Updater<'index> {
         self.index_transaction_sats(
           tx,
           *txid,
+          block.envelopes(tx_offset),
           &mut sat_to_satpoint,
           &mut input_sat_ranges,
           &mut sat_ranges_written,
@@ -555,6 +655,7 @@ impl<'index> Updater<'index> {
         self.index_transaction_sats(
           tx,
           *txid,
+          block.envelopes(0),
           &mut sat_to_satpoint,
           &mut coinbase_inputs,
           &mut sat_ranges_written,
@@ -590,8 +691,14 @@ impl<'index> Updater<'index> {
         outpoint_to_sat_ranges.insert(&OutPoint::null().store(), lost_sat_ranges.as_slice())?;
       }
     } else if index_inscriptions {
-      for (tx, txid) in block.txdata.iter().skip(1).chain(block.txdata.first()) {
-        inscription_updater.index_inscriptions(tx, *txid, None)?;
+      for ((tx, txid), envelopes) in block
+        .txdata
+        .iter()
+        .zip(&block.envelopes)
+        .skip(1)
+        .chain(block.txdata.iter().zip(&block.envelopes).take(1))
+      {
+        inscription_updater.index_inscriptions(tx, *txid, envelopes, None)?;
       }
     }
 
@@ -625,7 +732,11 @@ impl<'index> Updater<'index> {
     )?;
 
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
+      let mut open_mints = wtx.open_table(OPEN_MINTS)?;
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_id_and_height_to_activity = wtx.open_table(RUNE_ID_AND_HEIGHT_TO_ACTIVITY)?;
+      let mut rune_id_to_outputs = wtx.open_table(RUNE_ID_TO_OUTPUTS)?;
+      let mut rune_id_to_recent_mints = wtx.open_table(RUNE_ID_TO_RECENT_MINTS)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
@@ -642,13 +753,19 @@ impl<'index> Updater<'index> {
         burned: HashMap::new(),
         client: &self.index.client,
         height: self.height,
+        id_and_height_to_activity: &mut rune_id_and_height_to_activity,
         id_to_entry: &mut rune_id_to_rune_entry,
+        id_to_outputs: &mut rune_id_to_outputs,
+        id_to_recent_mints: &mut rune_id_to_recent_mints,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(
           self.index.settings.chain().network(),
           Height(self.height),
         ),
+        minted: HashMap::new(),
+        open_mints: &mut open_mints,
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        outputs: HashMap::new(),
         rune_to_id: &mut rune_to_rune_id,
         runes,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
@@ -656,8 +773,8 @@ impl<'index> Updater<'index> {
         transaction_id_to_rune: &mut transaction_id_to_rune,
       };
 
-      for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-        rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid)?;
+      for (i, ((tx, txid), artifact)) in block.txdata.iter().zip(&block.artifacts).enumerate() {
+        rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid, artifact.as_ref())?;
       }
 
       rune_updater.update()?;
@@ -683,9 +800,13 @@ impl<'index> Updater<'index> {
     txout_receiver: &mut broadcast::Receiver<TxOut>,
     utxo_cache: &mut HashMap<OutPoint, TxOut>,
     script_pubkey_to_outpoint: &mut MultimapTable<&[u8], OutPointValue>,
+    script_pubkey_to_transaction_count: &mut Table<&[u8], u64>,
     outpoint_to_txout: &mut Table<&OutPointValue, TxOutValue>,
     index_inscriptions: bool,
   ) -> Result {
+    // script pubkeys which this transaction spends from or sends to
+    let mut script_pubkeys = HashSet::new();
+
     for txin in &tx.input {
       let output = txin.previous_output;
       if output.is_null() {
@@ -713,6 +834,8 @@ impl<'index> Updater<'index> {
       }
 
       script_pubkey_to_outpoint.remove(&txout.script_pubkey.as_bytes(), output.store())?;
+
+      script_pubkeys.insert(txout.script_pubkey);
     }
 
     for (vout, txout) in tx.output.iter().enumerate() {
@@ -723,6 +846,17 @@ impl<'index> Updater<'index> {
       )?;
 
       utxo_cache.insert(OutPoint { txid: *txid, vout }, txout.clone());
+
+      script_pubkeys.insert(txout.script_pubkey.clone());
+    }
+
+    for script_pubkey in script_pubkeys {
+      let count = script_pubkey_to_transaction_count
+        .get(script_pubkey.as_bytes())?
+        .map(|count| count.value())
+        .unwrap_or_default();
+
+      script_pubkey_to_transaction_count.insert(script_pubkey.as_bytes(), count + 1)?;
     }
 
     Ok(())
@@ -732,6 +866,7 @@ impl<'index> Updater<'index> {
     &mut self,
     tx: &Transaction,
     txid: Txid,
+    envelopes: &[ParsedEnvelope],
     sat_to_satpoint: &mut Table<u64, &SatPointValue>,
     input_sat_ranges: &mut VecDeque<(u64, u64)>,
     sat_ranges_written: &mut u64,
@@ -740,7 +875,7 @@ impl<'index> Updater<'index> {
     index_inscriptions: bool,
   ) -> Result {
     if index_inscriptions {
-      inscription_updater.index_inscriptions(tx, txid, Some(input_sat_ranges))?;
+      inscription_updater.index_inscriptions(tx, txid, envelopes, Some(input_sat_ranges))?;
     }
 
     for (vout, output) in tx.output.iter().enumerate() {
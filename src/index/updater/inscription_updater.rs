@@ -72,6 +72,7 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     &mut self,
     tx: &Transaction,
     txid: Txid,
+    envelopes: &[ParsedEnvelope],
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
   ) -> Result {
     let mut floating_inscriptions = Vec::new();
@@ -81,9 +82,8 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
     let mut total_input_value = 0;
     let total_output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
 
-    let envelopes = ParsedEnvelope::from_transaction(tx);
     let inscriptions = !envelopes.is_empty();
-    let mut envelopes = envelopes.into_iter().peekable();
+    let mut envelopes = envelopes.iter().peekable();
 
     for (input_index, txin) in tx.input.iter().enumerate() {
       // skip subsidy since no inscriptions possible
@@ -1,15 +1,24 @@
 use super::*;
 
+// number of mint transactions kept for each rune in `RUNE_ID_TO_RECENT_MINTS`
+const RECENT_MINTS: usize = 20;
+
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
-  pub(super) client: &'client Client,
+  pub(super) client: &'client BlockSource,
   pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
   pub(super) height: u32,
+  pub(super) id_and_height_to_activity: &'a mut Table<'tx, (u64, u32, u32), (u64, u128)>,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
+  pub(super) id_to_outputs: &'a mut Table<'tx, RuneIdValue, u64>,
+  pub(super) id_to_recent_mints: &'a mut Table<'tx, RuneIdValue, &'static [u8]>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
+  pub(super) minted: HashMap<RuneId, Vec<Txid>>,
+  pub(super) open_mints: &'a mut Table<'tx, (u64, u64, u32), ()>,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outputs: HashMap<RuneId, i64>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
@@ -18,18 +27,22 @@ pub(super) struct RuneUpdater<'a, 'tx, 'client> {
 }
 
 impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
-  pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
-    let artifact = Runestone::decipher(tx);
-
+  pub(super) fn index_runes(
+    &mut self,
+    tx_index: u32,
+    tx: &Transaction,
+    txid: Txid,
+    artifact: Option<&Artifact>,
+  ) -> Result<()> {
     let mut unallocated = self.unallocated(tx)?;
 
-    let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
-
     if let Some(artifact) = &artifact {
       if let Some(id) = artifact.mint() {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
+          self.minted.entry(id).or_default().push(txid);
+
           if let Some(sender) = self.event_sender {
             sender.blocking_send(Event::RuneMinted {
               block_height: self.height,
@@ -40,132 +53,25 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
           }
         }
       }
+    }
 
-      let etched = self.etched(tx_index, tx, artifact)?;
-
-      if let Artifact::Runestone(runestone) = artifact {
-        if let Some((id, ..)) = etched {
-          *unallocated.entry(id).or_default() +=
-            runestone.etching.unwrap().premine.unwrap_or_default();
-        }
-
-        for Edict { id, amount, output } in runestone.edicts.iter().copied() {
-          let amount = Lot(amount);
-
-          // edicts with output values greater than the number of outputs
-          // should never be produced by the edict parser
-          let output = usize::try_from(output).unwrap();
-          assert!(output <= tx.output.len());
-
-          let id = if id == RuneId::default() {
-            let Some((id, ..)) = etched else {
-              continue;
-            };
-
-            id
-          } else {
-            id
-          };
-
-          let Some(balance) = unallocated.get_mut(&id) else {
-            continue;
-          };
-
-          let mut allocate = |balance: &mut Lot, amount: Lot, output: usize| {
-            if amount > 0 {
-              *balance -= amount;
-              *allocated[output].entry(id).or_default() += amount;
-            }
-          };
-
-          if output == tx.output.len() {
-            // find non-OP_RETURN outputs
-            let destinations = tx
-              .output
-              .iter()
-              .enumerate()
-              .filter_map(|(output, tx_out)| {
-                (!tx_out.script_pubkey.is_op_return()).then_some(output)
-              })
-              .collect::<Vec<usize>>();
-
-            if !destinations.is_empty() {
-              if amount == 0 {
-                // if amount is zero, divide balance between eligible outputs
-                let amount = *balance / destinations.len() as u128;
-                let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
-
-                for (i, output) in destinations.iter().enumerate() {
-                  allocate(
-                    balance,
-                    if i < remainder { amount + 1 } else { amount },
-                    *output,
-                  );
-                }
-              } else {
-                // if amount is non-zero, distribute amount to eligible outputs
-                for output in destinations {
-                  allocate(balance, amount.min(*balance), output);
-                }
-              }
-            }
-          } else {
-            // Get the allocatable amount
-            let amount = if amount == 0 {
-              *balance
-            } else {
-              amount.min(*balance)
-            };
-
-            allocate(balance, amount, output);
-          }
-        }
-      }
+    let etched = match artifact {
+      Some(artifact) => self.etched(tx_index, tx, artifact)?,
+      None => None,
+    };
 
-      if let Some((id, rune)) = etched {
-        self.create_rune_entry(txid, artifact, id, rune)?;
+    if let Some(Artifact::Runestone(runestone)) = artifact {
+      if let Some((id, ..)) = etched {
+        *unallocated.entry(id).or_default() +=
+          runestone.etching.unwrap().premine.unwrap_or_default();
       }
     }
 
-    let mut burned: HashMap<RuneId, Lot> = HashMap::new();
-
-    if let Some(Artifact::Cenotaph(_)) = artifact {
-      for (id, balance) in unallocated {
-        *burned.entry(id).or_default() += balance;
-      }
-    } else {
-      let pointer = artifact
-        .map(|artifact| match artifact {
-          Artifact::Runestone(runestone) => runestone.pointer,
-          Artifact::Cenotaph(_) => unreachable!(),
-        })
-        .unwrap_or_default();
+    let allocation::Allocation { allocated, burned } =
+      allocation::allocate(tx, artifact, etched.map(|(id, _rune)| id), unallocated);
 
-      // assign all un-allocated runes to the default output, or the first non
-      // OP_RETURN output if there is no default
-      if let Some(vout) = pointer
-        .map(|pointer| pointer.into_usize())
-        .inspect(|&pointer| assert!(pointer < allocated.len()))
-        .or_else(|| {
-          tx.output
-            .iter()
-            .enumerate()
-            .find(|(_vout, tx_out)| !tx_out.script_pubkey.is_op_return())
-            .map(|(vout, _tx_out)| vout)
-        })
-      {
-        for (id, balance) in unallocated {
-          if balance > 0 {
-            *allocated[vout].entry(id).or_default() += balance;
-          }
-        }
-      } else {
-        for (id, balance) in unallocated {
-          if balance > 0 {
-            *burned.entry(id).or_default() += balance;
-          }
-        }
-      }
+    if let (Some(artifact), Some((id, rune))) = (artifact, etched) {
+      self.create_rune_entry(txid, artifact, id, rune)?;
     }
 
     // update outpoint balances
@@ -175,14 +81,6 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         continue;
       }
 
-      // increment burned balances
-      if tx.output[vout].script_pubkey.is_op_return() {
-        for (id, balance) in &balances {
-          *burned.entry(*id).or_default() += *balance;
-        }
-        continue;
-      }
-
       buffer.clear();
 
       let mut balances = balances.into_iter().collect::<Vec<(RuneId, Lot)>>();
@@ -198,6 +96,8 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
+        *self.outputs.entry(id).or_default() += 1;
+
         if let Some(sender) = self.event_sender {
           sender.blocking_send(Event::RuneTransferred {
             outpoint,
@@ -232,12 +132,73 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
   }
 
   pub(super) fn update(self) -> Result {
+    // remove mints which end at or before the next block, since mints are
+    // only open in blocks before their end
+    self.open_mints.retain_in(
+      ..=(u64::from(self.height) + 1, u64::MAX, u32::MAX),
+      |_, ()| false,
+    )?;
+
+    // record the number of mints and the amount burned in this block
+    let mut activity: HashMap<RuneId, (u64, u128)> = HashMap::new();
+
+    for (rune_id, txids) in &self.minted {
+      activity.entry(*rune_id).or_default().0 = txids.len().try_into().unwrap();
+    }
+
+    for (rune_id, burned) in &self.burned {
+      activity.entry(*rune_id).or_default().1 = burned.n();
+    }
+
+    for (rune_id, activity) in activity {
+      self
+        .id_and_height_to_activity
+        .insert((rune_id.block, rune_id.tx, self.height), activity)?;
+    }
+
+    // prepend this block's mints, most recent first, to the recent mints
+    for (rune_id, txids) in self.minted {
+      let mut recent_mints = Vec::new();
+
+      for txid in txids.into_iter().rev().take(RECENT_MINTS) {
+        recent_mints.extend_from_slice(&txid.store());
+        recent_mints.extend_from_slice(&self.height.to_le_bytes());
+      }
+
+      if let Some(previous) = self.id_to_recent_mints.get(&rune_id.store())? {
+        recent_mints.extend_from_slice(previous.value());
+      }
+
+      recent_mints.truncate(RECENT_MINTS * 36);
+
+      self
+        .id_to_recent_mints
+        .insert(&rune_id.store(), recent_mints.as_slice())?;
+    }
+
     for (rune_id, burned) in self.burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
       self.id_to_entry.insert(&rune_id.store(), entry.store())?;
     }
 
+    // update the number of unspent outputs holding each rune
+    for (rune_id, change) in self.outputs {
+      if change == 0 {
+        continue;
+      }
+
+      let outputs = self
+        .id_to_outputs
+        .get(&rune_id.store())?
+        .map(|outputs| outputs.value())
+        .unwrap_or_default()
+        .checked_add_signed(change)
+        .unwrap();
+
+      self.id_to_outputs.insert(&rune_id.store(), outputs)?;
+    }
+
     Ok(())
   }
 
@@ -308,6 +269,12 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
+    if entry.terms.and_then(|terms| terms.cap).unwrap_or_default() > 0 {
+      self
+        .open_mints
+        .insert(Self::open_mint_key(id, &entry), ())?;
+    }
+
     if let Some(sender) = self.event_sender {
       sender.blocking_send(Event::RuneEtched {
         block_height: self.height,
@@ -351,7 +318,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
       if rune < self.minimum
         || rune.is_reserved()
         || self.rune_to_id.get(rune.0)?.is_some()
-        || !self.tx_commits_to_rune(tx, rune)?
+        || !commitment::tx_commits_to_rune(self.client, self.height, tx, rune)?
       {
         return Ok(None);
       }
@@ -396,74 +363,22 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     self.id_to_entry.insert(&id.store(), rune_entry.store())?;
 
+    if rune_entry.mints
+      >= rune_entry
+        .terms
+        .and_then(|terms| terms.cap)
+        .unwrap_or_default()
+    {
+      self
+        .open_mints
+        .remove(Self::open_mint_key(id, &rune_entry))?;
+    }
+
     Ok(Some(Lot(amount)))
   }
 
-  fn tx_commits_to_rune(&self, tx: &Transaction, rune: Rune) -> Result<bool> {
-    let commitment = rune.commitment();
-
-    for input in &tx.input {
-      // extracting a tapscript does not indicate that the input being spent
-      // was actually a taproot output. this is checked below, when we load the
-      // output's entry from the database
-      let Some(tapscript) = input.witness.tapscript() else {
-        continue;
-      };
-
-      for instruction in tapscript.instructions() {
-        // ignore errors, since the extracted script may not be valid
-        let Ok(instruction) = instruction else {
-          break;
-        };
-
-        let Some(pushbytes) = instruction.push_bytes() else {
-          continue;
-        };
-
-        if pushbytes.as_bytes() != commitment {
-          continue;
-        }
-
-        let Some(tx_info) = self
-          .client
-          .get_raw_transaction_info(&input.previous_output.txid, None)
-          .into_option()?
-        else {
-          panic!(
-            "can't get input transaction: {}",
-            input.previous_output.txid
-          );
-        };
-
-        let taproot = tx_info.vout[input.previous_output.vout.into_usize()]
-          .script_pub_key
-          .script()?
-          .is_v1_p2tr();
-
-        if !taproot {
-          continue;
-        }
-
-        let commit_tx_height = self
-          .client
-          .get_block_header_info(&tx_info.blockhash.unwrap())
-          .into_option()?
-          .unwrap()
-          .height;
-
-        let confirmations = self
-          .height
-          .checked_sub(commit_tx_height.try_into().unwrap())
-          .unwrap()
-          + 1;
-
-        if confirmations >= Runestone::COMMIT_CONFIRMATIONS.into() {
-          return Ok(true);
-        }
-      }
-    }
-
-    Ok(false)
+  fn open_mint_key(id: RuneId, entry: &RuneEntry) -> (u64, u64, u32) {
+    (entry.end().unwrap_or(u64::MAX), id.block, id.tx)
   }
 
   fn unallocated(&mut self, tx: &Transaction) -> Result<HashMap<RuneId, Lot>> {
@@ -482,6 +397,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
           let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
           i += len;
           *unallocated.entry(id).or_default() += balance;
+          *self.outputs.entry(id).or_default() -= 1;
         }
       }
     }
@@ -4,8 +4,12 @@ use tag::Tag;
 
 pub(crate) use self::{envelope::ParsedEnvelope, media::Media};
 
-pub use self::{envelope::Envelope, inscription::Inscription, inscription_id::InscriptionId};
+pub use self::{
+  compression::Compression, envelope::Envelope, inscription::Inscription,
+  inscription_id::InscriptionId,
+};
 
+pub(crate) mod compression;
 mod envelope;
 mod inscription;
 pub(crate) mod inscription_id;
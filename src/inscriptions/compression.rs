@@ -0,0 +1,190 @@
+use {
+  super::*,
+  brotli::enc::{
+    backward_references::BrotliEncoderMode, writer::CompressorWriter, BrotliEncoderParams,
+  },
+  clap::ValueEnum,
+  flate2::{
+    read::{GzDecoder, ZlibDecoder},
+    write::{GzEncoder, ZlibEncoder},
+  },
+  io::Write,
+};
+
+#[derive(Debug, PartialEq, Copy, Clone, ValueEnum)]
+pub enum Compression {
+  #[value(name = "br")]
+  Brotli,
+  Deflate,
+  Gzip,
+  Zstd,
+}
+
+// Hint about the kind of content being compressed. Brotli has modes tuned for
+// each kind of content, while the other algorithms compress all content alike.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) enum CompressionMode {
+  Font,
+  Generic,
+  Text,
+}
+
+impl Compression {
+  pub fn content_encoding(self) -> &'static str {
+    match self {
+      Self::Brotli => "br",
+      Self::Deflate => "deflate",
+      Self::Gzip => "gzip",
+      Self::Zstd => "zstd",
+    }
+  }
+
+  pub fn from_content_encoding(content_encoding: &str) -> Option<Self> {
+    [Self::Brotli, Self::Deflate, Self::Gzip, Self::Zstd]
+      .into_iter()
+      .find(|compression| compression.content_encoding() == content_encoding)
+  }
+
+  pub(crate) fn compress(self, body: &[u8], mode: CompressionMode) -> Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+
+    match self {
+      Self::Brotli => CompressorWriter::with_params(
+        &mut compressed,
+        body.len(),
+        &BrotliEncoderParams {
+          lgblock: 24,
+          lgwin: 24,
+          mode: match mode {
+            CompressionMode::Font => BrotliEncoderMode::BROTLI_MODE_FONT,
+            CompressionMode::Generic => BrotliEncoderMode::BROTLI_MODE_GENERIC,
+            CompressionMode::Text => BrotliEncoderMode::BROTLI_MODE_TEXT,
+          },
+          quality: 11,
+          size_hint: body.len(),
+          ..default()
+        },
+      )
+      .write_all(body)?,
+      Self::Deflate => {
+        let mut encoder = ZlibEncoder::new(&mut compressed, flate2::Compression::best());
+        encoder.write_all(body)?;
+        encoder.finish()?;
+      }
+      Self::Gzip => {
+        let mut encoder = GzEncoder::new(&mut compressed, flate2::Compression::best());
+        encoder.write_all(body)?;
+        encoder.finish()?;
+      }
+      Self::Zstd => compressed = zstd::encode_all(body, 19)?,
+    }
+
+    Ok(compressed)
+  }
+
+  pub(crate) fn decoder<'a>(
+    self,
+    reader: impl Read + Send + 'a,
+  ) -> io::Result<Box<dyn Read + Send + 'a>> {
+    Ok(match self {
+      Self::Brotli => Box::new(brotli::Decompressor::new(reader, 4096)),
+      // the `deflate` content coding is a zlib stream, not raw deflate
+      Self::Deflate => Box::new(ZlibDecoder::new(reader)),
+      Self::Gzip => Box::new(GzDecoder::new(reader)),
+      Self::Zstd => Box::new(zstd::Decoder::new(reader)?),
+    })
+  }
+
+  pub(crate) fn decompress(self, body: &[u8]) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    self.decoder(body)?.read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+  }
+
+  // Decompresses `body`, failing as soon as more than `limit` bytes have been
+  // decompressed
+  pub(crate) fn decompress_with_limit(self, body: &[u8], limit: u64) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+
+    self
+      .decoder(body)?
+      .take(limit.saturating_add(1))
+      .read_to_end(&mut decompressed)?;
+
+    ensure!(
+      u64::try_from(decompressed.len()).unwrap() <= limit,
+      "decompressed body is larger than {limit} bytes",
+    );
+
+    Ok(decompressed)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn content_encoding_round_trips() {
+    for compression in Compression::value_variants() {
+      assert_eq!(
+        Compression::from_content_encoding(compression.content_encoding()),
+        Some(*compression)
+      );
+    }
+
+    assert_eq!(Compression::from_content_encoding("compress"), None);
+  }
+
+  #[test]
+  fn compression_round_trips() {
+    let body = "hello world ".repeat(100);
+
+    for compression in Compression::value_variants() {
+      for mode in [
+        CompressionMode::Font,
+        CompressionMode::Generic,
+        CompressionMode::Text,
+      ] {
+        let compressed = compression.compress(body.as_bytes(), mode).unwrap();
+        assert!(compressed.len() < body.len());
+        assert_eq!(
+          compression.decompress(&compressed).unwrap(),
+          body.as_bytes()
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn decompression_is_limited() {
+    let body = vec![0; 10_000];
+
+    for compression in Compression::value_variants() {
+      let compressed = compression
+        .compress(&body, CompressionMode::Generic)
+        .unwrap();
+
+      assert_eq!(
+        compression
+          .decompress_with_limit(&compressed, 10_000)
+          .unwrap(),
+        body
+      );
+
+      assert!(compression
+        .decompress_with_limit(&compressed, 9_999)
+        .is_err());
+    }
+  }
+
+  #[test]
+  fn value_names_are_content_encodings() {
+    for compression in Compression::value_variants() {
+      assert_eq!(
+        compression.to_possible_value().unwrap().get_name(),
+        compression.content_encoding()
+      );
+    }
+  }
+}
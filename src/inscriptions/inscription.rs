@@ -1,14 +1,7 @@
-use {
-  super::*,
-  anyhow::ensure,
-  bitcoin::blockdata::opcodes,
-  brotli::enc::{writer::CompressorWriter, BrotliEncoderParams},
-  http::header::HeaderValue,
-  io::Write,
-  std::str,
-};
-
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
+use {super::*, anyhow::ensure, bitcoin::blockdata::opcodes, http::header::HeaderValue, std::str};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default, JsonSchema)]
+#[schemars(rename = "DecodedInscription")]
 pub struct Inscription {
   pub body: Option<Vec<u8>>,
   pub content_encoding: Option<Vec<u8>>,
@@ -27,7 +20,7 @@ pub struct Inscription {
 impl Inscription {
   pub fn new(
     chain: Chain,
-    compress: bool,
+    compress: &[Compression],
     delegate: Option<InscriptionId>,
     metadata: Option<Vec<u8>>,
     metaprotocol: Option<String>,
@@ -41,43 +34,31 @@ impl Inscription {
     let (body, content_type, content_encoding) = if let Some(path) = path {
       let body = fs::read(path).with_context(|| format!("io error reading {}", path.display()))?;
 
-      let content_type = Media::content_type_for_path(path)?.0;
+      let (content_type, compression_mode) = Media::content_type_for_path(path)?;
+
+      let mut smallest: Option<(Compression, Vec<u8>)> = None;
+
+      for &compression in compress {
+        let compressed = compression.compress(&body, compression_mode)?;
 
-      let (body, content_encoding) = if compress {
-        let compression_mode = Media::content_type_for_path(path)?.1;
-        let mut compressed = Vec::new();
+        ensure!(
+          compression.decompress(&compressed)? == body,
+          "{} decompression roundtrip failed",
+          compression.content_encoding(),
+        );
 
+        if compressed.len()
+          < smallest
+            .as_ref()
+            .map_or(body.len(), |(_, smallest)| smallest.len())
         {
-          CompressorWriter::with_params(
-            &mut compressed,
-            body.len(),
-            &BrotliEncoderParams {
-              lgblock: 24,
-              lgwin: 24,
-              mode: compression_mode,
-              quality: 11,
-              size_hint: body.len(),
-              ..default()
-            },
-          )
-          .write_all(&body)?;
-
-          let mut decompressor = brotli::Decompressor::new(compressed.as_slice(), compressed.len());
-
-          let mut decompressed = Vec::new();
-
-          decompressor.read_to_end(&mut decompressed)?;
-
-          ensure!(decompressed == body, "decompression roundtrip failed");
+          smallest = Some((compression, compressed));
         }
+      }
 
-        if compressed.len() < body.len() {
-          (compressed, Some("br".as_bytes().to_vec()))
-        } else {
-          (body, None)
-        }
-      } else {
-        (body, None)
+      let (body, content_encoding) = match smallest {
+        Some((compression, compressed)) => (compressed, Some(compression)),
+        None => (body, None),
       };
 
       if let Some(limit) = chain.inscription_content_size_limit() {
@@ -87,7 +68,11 @@ impl Inscription {
         }
       }
 
-      (Some(body), Some(content_type), content_encoding)
+      (
+        Some(body),
+        Some(content_type),
+        content_encoding.map(|compression| compression.content_encoding().into()),
+      )
     } else {
       (None, None, None)
     };
@@ -242,6 +227,57 @@ impl Inscription {
     ciborium::from_reader(Cursor::new(self.metadata.as_ref()?)).ok()
   }
 
+  pub fn traits(&self) -> BTreeMap<String, String> {
+    let Some(Value::Map(entries)) = self.metadata() else {
+      return BTreeMap::new();
+    };
+
+    let mut traits = BTreeMap::new();
+
+    for (key, value) in entries {
+      let Value::Text(key) = key else {
+        continue;
+      };
+
+      // collections often use the `attributes` list of `trait_type` and
+      // `value` pairs of NFT metadata instead of top-level fields
+      if key == "attributes" {
+        if let Value::Array(attributes) = value {
+          for attribute in attributes {
+            let Value::Map(fields) = attribute else {
+              continue;
+            };
+
+            let field = |name: &str| {
+              fields
+                .iter()
+                .find(|(key, _)| key.as_text() == Some(name))
+                .and_then(|(_, value)| Self::trait_value(value))
+            };
+
+            if let (Some(name), Some(value)) = (field("trait_type"), field("value")) {
+              traits.insert(name, value);
+            }
+          }
+        }
+      } else if let Some(value) = Self::trait_value(&value) {
+        traits.insert(key, value);
+      }
+    }
+
+    traits
+  }
+
+  fn trait_value(value: &Value) -> Option<String> {
+    match value {
+      Value::Bool(x) => Some(x.to_string()),
+      Value::Float(x) => Some(x.to_string()),
+      Value::Integer(x) => Some(i128::from(*x).to_string()),
+      Value::Text(x) => Some(x.clone()),
+      _ => None,
+    }
+  }
+
   pub fn metaprotocol(&self) -> Option<&str> {
     str::from_utf8(self.metaprotocol.as_ref()?).ok()
   }
@@ -304,7 +340,10 @@ impl Inscription {
       .map(|body| BRC_420.is_match(body) || body.starts_with(BVM_NETWORK))
       .unwrap_or_default()
       || self.metaprotocol.is_some()
-      || matches!(self.media(), Media::Code(_) | Media::Text | Media::Unknown)
+      || matches!(
+        self.media(),
+        Media::Code(_) | Media::Csv | Media::Json | Media::Text | Media::Unknown
+      )
   }
 }
 
@@ -682,6 +721,60 @@ mod tests {
     );
   }
 
+  #[test]
+  fn traits_function_collects_top_level_fields_and_attributes() {
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("background".into()), Value::Text("blue".into())),
+        (Value::Text("level".into()), Value::Integer(3.into())),
+        (Value::Text("nested".into()), Value::Array(Vec::new())),
+        (Value::Integer(0.into()), Value::Text("ignored".into())),
+        (
+          Value::Text("attributes".into()),
+          Value::Array(vec![
+            Value::Map(vec![
+              (Value::Text("trait_type".into()), Value::Text("eyes".into())),
+              (Value::Text("value".into()), Value::Text("laser".into())),
+            ]),
+            Value::Map(vec![(
+              Value::Text("trait_type".into()),
+              Value::Text("hat".into()),
+            )]),
+          ]),
+        ),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      Inscription {
+        metadata: Some(metadata),
+        ..default()
+      }
+      .traits(),
+      [
+        ("background".into(), "blue".into()),
+        ("eyes".into(), "laser".into()),
+        ("level".into(), "3".into()),
+      ]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn traits_function_returns_empty_map_if_metadata_is_not_a_map() {
+    assert_eq!(
+      Inscription {
+        metadata: Some(vec![0x44, 0, 1, 2, 3]),
+        ..default()
+      }
+      .traits(),
+      BTreeMap::new(),
+    );
+  }
+
   #[test]
   fn pointer_decode() {
     assert_eq!(
@@ -763,6 +856,73 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compression_keeps_smallest_result() {
+    let mut file = tempfile::Builder::new().suffix(".txt").tempfile().unwrap();
+
+    write!(file, "{}", "hello world ".repeat(100)).unwrap();
+
+    let body = fs::read(file.path()).unwrap();
+
+    let new = |compress: &[Compression]| {
+      Inscription::new(
+        Chain::Mainnet,
+        compress,
+        None,
+        None,
+        None,
+        Vec::new(),
+        Some(file.path().to_path_buf()),
+        None,
+        None,
+      )
+      .unwrap()
+    };
+
+    let all = [
+      Compression::Brotli,
+      Compression::Deflate,
+      Compression::Gzip,
+      Compression::Zstd,
+    ];
+
+    let smallest = all
+      .iter()
+      .map(|compression| new(&[*compression]))
+      .min_by_key(|inscription| inscription.body.as_ref().unwrap().len())
+      .unwrap();
+
+    assert!(smallest.body.as_ref().unwrap().len() < body.len());
+    assert_eq!(new(&all), smallest);
+
+    let uncompressed = new(&[]);
+    assert_eq!(uncompressed.body, Some(body));
+    assert_eq!(uncompressed.content_encoding, None);
+  }
+
+  #[test]
+  fn compression_is_skipped_if_it_saves_no_space() {
+    let mut file = tempfile::Builder::new().suffix(".txt").tempfile().unwrap();
+
+    write!(file, "foo").unwrap();
+
+    let inscription = Inscription::new(
+      Chain::Mainnet,
+      &[Compression::Gzip, Compression::Zstd],
+      None,
+      None,
+      None,
+      Vec::new(),
+      Some(file.path().to_path_buf()),
+      None,
+      None,
+    )
+    .unwrap();
+
+    assert_eq!(inscription.body, Some(b"foo".to_vec()));
+    assert_eq!(inscription.content_encoding, None);
+  }
+
   #[test]
   fn pointer_value() {
     let mut file = tempfile::Builder::new().suffix(".txt").tempfile().unwrap();
@@ -771,7 +931,7 @@ mod tests {
 
     let inscription = Inscription::new(
       Chain::Mainnet,
-      false,
+      &[],
       None,
       None,
       None,
@@ -786,7 +946,7 @@ mod tests {
 
     let inscription = Inscription::new(
       Chain::Mainnet,
-      false,
+      &[],
       None,
       None,
       None,
@@ -801,7 +961,7 @@ mod tests {
 
     let inscription = Inscription::new(
       Chain::Mainnet,
-      false,
+      &[],
       None,
       None,
       None,
@@ -816,7 +976,7 @@ mod tests {
 
     let inscription = Inscription::new(
       Chain::Mainnet,
-      false,
+      &[],
       None,
       None,
       None,
@@ -867,6 +1027,10 @@ mod tests {
       true,
     );
     case(Some("application/yaml"), Some(""), true);
+    case(Some("text/csv"), Some("foo,bar"), true);
+    case(Some("text/x-rust"), Some("fn main() {}"), true);
+    case(Some("audio/midi"), Some("foo"), false);
+    case(Some("model/stl"), Some("foo"), false);
     case(
       Some("text/html;charset=utf-8"),
       Some("/content/09a8d837ec0bcaec668ecf405e696a16bee5990863659c224ff888fb6f8f45e7i0"),
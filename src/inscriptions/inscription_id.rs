@@ -1,4 +1,8 @@
-use super::*;
+use {
+  super::*,
+  schemars::{Schema, SchemaGenerator},
+  std::borrow::Cow,
+};
 
 #[derive(
   Debug, PartialEq, Copy, Clone, Hash, Eq, PartialOrd, Ord, DeserializeFromStr, SerializeDisplay,
@@ -17,6 +21,20 @@ impl Default for InscriptionId {
   }
 }
 
+impl JsonSchema for InscriptionId {
+  fn schema_name() -> Cow<'static, str> {
+    "InscriptionId".into()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    schemars::json_schema!({
+      "type": "string",
+      "description": "Inscription ID, the reveal transaction ID and the index of the inscription within it.",
+      "pattern": "^[0-9a-f]{64}i[0-9]+$",
+    })
+  }
+}
+
 impl InscriptionId {
   pub(crate) fn value(self) -> Vec<u8> {
     let index = self.index.to_le_bytes();
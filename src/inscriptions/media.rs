@@ -1,23 +1,25 @@
 use {
   self::{ImageRendering::*, Language::*, Media::*},
   super::*,
-  brotli::enc::backward_references::BrotliEncoderMode::{
-    self, BROTLI_MODE_FONT as FONT, BROTLI_MODE_GENERIC as GENERIC, BROTLI_MODE_TEXT as TEXT,
-  },
   mp4::{MediaType, Mp4Reader, TrackType},
   std::{fs::File, io::BufReader},
+  CompressionMode::{Font as FONT, Generic as GENERIC, Text as TEXT},
 };
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Media {
   Audio,
   Code(Language),
+  Csv,
   Font,
   Iframe,
   Image(ImageRendering),
+  Json,
   Markdown,
+  Midi,
   Model,
   Pdf,
+  Stl,
   Text,
   Unknown,
   Video,
@@ -25,10 +27,22 @@ pub enum Media {
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Language {
+  Bash,
+  C,
+  Cpp,
   Css,
+  Go,
+  Java,
   JavaScript,
+  // `application/json` is previewed as `Media::Json`, but JSON can still be
+  // highlighted as code
   Json,
+  Lua,
   Python,
+  Ruby,
+  Rust,
+  Sql,
+  TypeScript,
   Yaml,
 }
 
@@ -38,10 +52,20 @@ impl Display for Language {
       f,
       "{}",
       match self {
+        Self::Bash => "bash",
+        Self::C => "c",
+        Self::Cpp => "cpp",
         Self::Css => "css",
+        Self::Go => "go",
+        Self::Java => "java",
         Self::JavaScript => "javascript",
         Self::Json => "json",
+        Self::Lua => "lua",
         Self::Python => "python",
+        Self::Ruby => "ruby",
+        Self::Rust => "rust",
+        Self::Sql => "sql",
+        Self::TypeScript => "typescript",
         Self::Yaml => "yaml",
       }
     )
@@ -69,16 +93,18 @@ impl Display for ImageRendering {
 
 impl Media {
   #[rustfmt::skip]
-  const TABLE: &'static [(&'static str, BrotliEncoderMode, Media, &'static [&'static str])] = &[
+  const TABLE: &'static [(&'static str, CompressionMode, Media, &'static [&'static str])] = &[
     ("application/cbor",            GENERIC, Unknown,          &["cbor"]),
-    ("application/json",            TEXT,    Code(Json),       &["json"]),
+    ("application/json",            TEXT,    Media::Json,      &["json"]),
     ("application/octet-stream",    GENERIC, Unknown,          &["bin"]),
     ("application/pdf",             GENERIC, Pdf,              &["pdf"]),
     ("application/pgp-signature",   TEXT,    Text,             &["asc"]),
     ("application/protobuf",        GENERIC, Unknown,          &["binpb"]),
+    ("application/sql",             TEXT,    Code(Sql),        &["sql"]),
     ("application/x-javascript",    TEXT,    Code(JavaScript), &[]),
     ("application/yaml",            TEXT,    Code(Yaml),       &["yaml", "yml"]),
     ("audio/flac",                  GENERIC, Audio,            &["flac"]),
+    ("audio/midi",                  GENERIC, Midi,             &["mid", "midi"]),
     ("audio/mpeg",                  GENERIC, Audio,            &["mp3"]),
     ("audio/wav",                   GENERIC, Audio,            &["wav"]),
     ("font/otf",                    GENERIC, Font,             &["otf"]),
@@ -95,8 +121,9 @@ impl Media {
     ("image/webp",                  GENERIC, Image(Pixelated), &["webp"]),
     ("model/gltf+json",             TEXT,    Model,            &["gltf"]),
     ("model/gltf-binary",           GENERIC, Model,            &["glb"]),
-    ("model/stl",                   GENERIC, Unknown,          &["stl"]),
+    ("model/stl",                   GENERIC, Stl,              &["stl"]),
     ("text/css",                    TEXT,    Code(Css),        &["css"]),
+    ("text/csv",                    TEXT,    Csv,              &["csv"]),
     ("text/html",                   TEXT,    Iframe,           &[]),
     ("text/html;charset=utf-8",     TEXT,    Iframe,           &["html"]),
     ("text/javascript",             TEXT,    Code(JavaScript), &["js", "mjs"]),
@@ -104,14 +131,24 @@ impl Media {
     ("text/markdown;charset=utf-8", TEXT,    Markdown,         &["md"]),
     ("text/plain",                  TEXT,    Text,             &[]),
     ("text/plain;charset=utf-8",    TEXT,    Text,             &["txt"]),
+    ("text/tab-separated-values",   TEXT,    Csv,              &["tsv"]),
+    ("text/x-c",                    TEXT,    Code(C),          &["c", "h"]),
+    ("text/x-c++src",               TEXT,    Code(Cpp),        &["cpp", "hpp"]),
+    ("text/x-go",                   TEXT,    Code(Go),         &["go"]),
+    ("text/x-java",                 TEXT,    Code(Java),       &["java"]),
+    ("text/x-lua",                  TEXT,    Code(Lua),        &["lua"]),
     ("text/x-python",               TEXT,    Code(Python),     &["py"]),
+    ("text/x-ruby",                 TEXT,    Code(Ruby),       &["rb"]),
+    ("text/x-rust",                 TEXT,    Code(Rust),       &["rs"]),
+    ("text/x-shellscript",          TEXT,    Code(Bash),       &["sh"]),
+    ("text/x-typescript",           TEXT,    Code(TypeScript), &["ts"]),
     ("video/mp4",                   GENERIC, Video,            &["mp4"]),
     ("video/webm",                  GENERIC, Video,            &["webm"]),
   ];
 
   pub(crate) fn content_type_for_path(
     path: &Path,
-  ) -> Result<(&'static str, BrotliEncoderMode), Error> {
+  ) -> Result<(&'static str, CompressionMode), Error> {
     let extension = path
       .extension()
       .ok_or_else(|| anyhow!("file must have extension"))?
@@ -187,22 +224,27 @@ mod tests {
   fn for_extension() {
     assert_eq!(
       Media::content_type_for_path(Path::new("pepe.jpg")).unwrap(),
-      ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
+      ("image/jpeg", CompressionMode::Generic)
     );
     assert_eq!(
       Media::content_type_for_path(Path::new("pepe.jpeg")).unwrap(),
-      ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
+      ("image/jpeg", CompressionMode::Generic)
     );
     assert_eq!(
       Media::content_type_for_path(Path::new("pepe.JPG")).unwrap(),
-      ("image/jpeg", BrotliEncoderMode::BROTLI_MODE_GENERIC)
+      ("image/jpeg", CompressionMode::Generic)
     );
     assert_eq!(
       Media::content_type_for_path(Path::new("pepe.txt")).unwrap(),
-      (
-        "text/plain;charset=utf-8",
-        BrotliEncoderMode::BROTLI_MODE_TEXT
-      )
+      ("text/plain;charset=utf-8", CompressionMode::Text)
+    );
+    assert_eq!(
+      Media::content_type_for_path(Path::new("pepe.tsv")).unwrap(),
+      ("text/tab-separated-values", CompressionMode::Text)
+    );
+    assert_eq!(
+      Media::content_type_for_path(Path::new("pepe.mid")).unwrap(),
+      ("audio/midi", CompressionMode::Generic)
     );
     assert_regex_match!(
       Media::content_type_for_path(Path::new("pepe.foo")).unwrap_err(),
@@ -220,6 +262,18 @@ mod tests {
     assert!(Media::check_mp4_codec(Path::new("examples/av1.mp4")).is_err(),);
   }
 
+  #[test]
+  fn from_content_type() {
+    assert_eq!("application/json".parse::<Media>().unwrap(), Media::Json);
+    assert_eq!("audio/midi".parse::<Media>().unwrap(), Midi);
+    assert_eq!("model/stl".parse::<Media>().unwrap(), Stl);
+    assert_eq!("text/csv".parse::<Media>().unwrap(), Csv);
+    assert_eq!("text/tab-separated-values".parse::<Media>().unwrap(), Csv);
+    assert_eq!("text/x-rust".parse::<Media>().unwrap(), Code(Rust));
+    assert_eq!("text/x-c++src".parse::<Media>().unwrap(), Code(Cpp));
+    assert!("text/x-cobol".parse::<Media>().is_err());
+  }
+
   #[test]
   fn no_duplicate_extensions() {
     let mut set = HashSet::new();
@@ -19,11 +19,13 @@ use {
     deserialize_from_str::DeserializeFromStr,
     index::BitcoinCoreRpcResultExt,
     inscriptions::{
+      compression::CompressionMode,
       inscription_id,
       media::{self, ImageRendering, Media},
       teleburn, ParsedEnvelope,
     },
     into_usize::IntoUsize,
+    locale::Locale,
     representation::Representation,
     settings::Settings,
     subcommand::{OutputFormat, Subcommand, SubcommandResult},
@@ -57,6 +59,7 @@ use {
   },
   regex::Regex,
   reqwest::Url,
+  schemars::JsonSchema,
   serde::{Deserialize, Deserializer, Serialize},
   serde_with::{DeserializeFromStr, SerializeDisplay},
   snafu::{Backtrace, ErrorCompat, Snafu},
@@ -89,7 +92,7 @@ pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
   index::{Index, RuneEntry},
-  inscriptions::{Envelope, Inscription, InscriptionId},
+  inscriptions::{Compression, Envelope, Inscription, InscriptionId},
   object::Object,
   options::Options,
   wallet::transaction_builder::{Target, TransactionBuilder},
@@ -113,6 +116,7 @@ mod fee_rate;
 pub mod index;
 mod inscriptions;
 mod into_usize;
+mod locale;
 mod macros;
 mod object;
 pub mod options;
@@ -0,0 +1,507 @@
+use {
+  super::*,
+  axum::{
+    http::{header, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+  },
+  std::cell::Cell,
+};
+
+// paths of responses which are not localized, and so are linked to without
+// the `lang` query parameter
+const UNLOCALIZED: [&str; 7] = [
+  "/content/",
+  "/favicon.ico",
+  "/feed.xml",
+  "/preview/",
+  "/r/",
+  "/static/",
+  "/thumbnail/",
+];
+
+tokio::task_local! {
+  static LOCALE: Negotiated;
+}
+
+lazy_static! {
+  static ref CATALOGS: BTreeMap<Locale, HashMap<String, String>> = [
+    (Locale::Ja, include_str!("../locales/ja.yaml")),
+    (Locale::Ko, include_str!("../locales/ko.yaml")),
+    (Locale::Zh, include_str!("../locales/zh.yaml")),
+  ]
+  .into_iter()
+  .map(|(locale, catalog)| (locale, serde_yaml::from_str(catalog).unwrap()))
+  .collect();
+}
+
+// Locale negotiated for a request, and whether the response depends on it
+#[derive(Default)]
+struct Negotiated {
+  locale: Locale,
+  used: Cell<bool>,
+}
+
+// Locale that explorer pages are rendered in. Messages are translated with
+// catalogs in `locales/`, which map English messages to their translation, so
+// messages missing from a catalog are shown in English.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, SerializeDisplay)]
+pub(crate) enum Locale {
+  #[default]
+  En,
+  Ja,
+  Ko,
+  Zh,
+}
+
+impl Locale {
+  pub(crate) const ALL: [Self; 4] = [Self::En, Self::Ja, Self::Ko, Self::Zh];
+
+  /// Locale of the request currently being handled, or English outside of a
+  /// request. Responses to requests whose locale is used vary by language.
+  pub(crate) fn current() -> Self {
+    LOCALE
+      .try_with(|negotiated| {
+        negotiated.used.set(true);
+        negotiated.locale
+      })
+      .unwrap_or_default()
+  }
+
+  fn query(query: Option<&str>) -> Option<Self> {
+    query
+      .into_iter()
+      .flat_map(|query| query.split('&'))
+      .find_map(|parameter| parameter.strip_prefix("lang="))
+      .and_then(|lang| lang.parse().ok())
+  }
+
+  /// Negotiate the locale of a request from the `lang` query parameter, if
+  /// present, and otherwise the `Accept-Language` header.
+  pub(crate) fn negotiate(query: Option<&str>, accept_language: Option<&str>) -> Self {
+    if let Some(locale) = Self::query(query) {
+      return locale;
+    }
+
+    let mut ranges = accept_language
+      .into_iter()
+      .flat_map(|header| header.split(','))
+      .filter_map(|range| {
+        let mut parts = range.split(';');
+
+        let tag = parts.next()?.trim();
+
+        let quality = match parts.find_map(|part| part.trim().strip_prefix("q=")) {
+          Some(quality) => quality.parse::<f32>().ok()?,
+          None => 1.0,
+        };
+
+        Some((tag, quality))
+      })
+      .filter(|(_tag, quality)| *quality > 0.0)
+      .collect::<Vec<(&str, f32)>>();
+
+    ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    ranges
+      .into_iter()
+      .find_map(|(tag, _quality)| tag.parse().ok())
+      .unwrap_or_default()
+  }
+
+  pub(crate) async fn middleware<B>(request: Request<B>, next: Next<B>) -> Response {
+    let explicit = Self::query(request.uri().query());
+
+    let negotiated = Negotiated {
+      locale: Self::negotiate(
+        request.uri().query(),
+        request
+          .headers()
+          .get(header::ACCEPT_LANGUAGE)
+          .and_then(|value| value.to_str().ok()),
+      ),
+      used: Cell::new(false),
+    };
+
+    let (mut response, used) = LOCALE
+      .scope(negotiated, async {
+        let response = next.run(request).await;
+        (response, LOCALE.with(|negotiated| negotiated.used.get()))
+      })
+      .await;
+
+    // only responses rendered in the negotiated locale, like explorer pages,
+    // vary by language, and not inscription content, static assets, or JSON
+    if !used {
+      return response;
+    }
+
+    response
+      .headers_mut()
+      .append(header::VARY, HeaderValue::from_static("accept-language"));
+
+    let html = response
+      .headers()
+      .get(header::CONTENT_TYPE)
+      .and_then(|content_type| content_type.to_str().ok())
+      .is_some_and(|content_type| content_type.starts_with("text/html"));
+
+    let Some(locale) = explicit.filter(|_| html) else {
+      return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+
+    let Ok(body) = hyper::body::to_bytes(body).await else {
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    Response::from_parts(
+      parts,
+      axum::body::boxed(axum::body::Full::from(
+        locale.carry(&String::from_utf8_lossy(&body)),
+      )),
+    )
+  }
+
+  // Add the `lang` query parameter to links and forms leading to explorer
+  // pages, so that a language chosen with `?lang=` is kept while browsing
+  fn carry(self, html: &str) -> String {
+    let mut linked = String::with_capacity(html.len());
+
+    let mut rest = html;
+
+    while let Some(start) = rest.find("href=") {
+      let start = start + "href=".len();
+
+      linked.push_str(&rest[..start]);
+      rest = &rest[start..];
+
+      let quote = rest.chars().next().filter(|c| matches!(c, '\'' | '"'));
+
+      let (open, end) = match quote {
+        Some(quote) => (1, rest[1..].find(quote).map_or(rest.len(), |end| end + 1)),
+        None => (
+          0,
+          rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '>')
+            .unwrap_or(rest.len()),
+        ),
+      };
+
+      let url = &rest[open..end];
+
+      linked.push_str(&rest[..open]);
+
+      if url.starts_with('/')
+        && !url.starts_with("//")
+        && !url.contains("lang=")
+        && !UNLOCALIZED.iter().any(|prefix| url.starts_with(prefix))
+      {
+        let (path, fragment) = url.split_at(url.find('#').unwrap_or(url.len()));
+        let separator = if path.contains('?') { '&' } else { '?' };
+        linked.push_str(&format!("{path}{separator}lang={self}{fragment}"));
+      } else {
+        linked.push_str(url);
+      }
+
+      rest = &rest[end..];
+    }
+
+    linked.push_str(rest);
+
+    let mut output = String::with_capacity(linked.len());
+
+    let mut rest = linked.as_str();
+
+    while let Some(start) = rest.find("<form ") {
+      let Some(end) = rest[start..].find('>').map(|end| start + end + 1) else {
+        break;
+      };
+
+      output.push_str(&rest[..end]);
+
+      if rest[start..end].contains("method=get") {
+        output.push_str(&format!("<input type=hidden name=lang value={self}>"));
+      }
+
+      rest = &rest[end..];
+    }
+
+    output.push_str(rest);
+
+    output
+  }
+
+  pub(crate) fn translate(self, message: &'static str) -> &'static str {
+    CATALOGS
+      .get(&self)
+      .and_then(|catalog| catalog.get(message))
+      .map(String::as_str)
+      .unwrap_or(message)
+  }
+
+  pub(crate) fn format_sats(self, sats: u64) -> String {
+    // English pages show ungrouped amounts, which existing users and scrapers
+    // of the explorer's HTML rely on
+    if self == Self::En {
+      return sats.to_string();
+    }
+
+    let digits = sats.to_string();
+
+    let mut formatted = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+      if i > 0 && (digits.len() - i) % 3 == 0 {
+        formatted.push(',');
+      }
+      formatted.push(digit);
+    }
+
+    formatted
+  }
+
+  pub(crate) fn format_time(self, time: DateTime<Utc>) -> String {
+    match self {
+      Self::En => time.to_string(),
+      Self::Ja | Self::Zh => time.format("%Y年%-m月%-d日 %H:%M:%S UTC").to_string(),
+      Self::Ko => time.format("%Y년 %-m월 %-d일 %H:%M:%S UTC").to_string(),
+    }
+  }
+}
+
+impl Display for Locale {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::En => "en",
+        Self::Ja => "ja",
+        Self::Ko => "ko",
+        Self::Zh => "zh",
+      }
+    )
+  }
+}
+
+impl FromStr for Locale {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    // only the primary language subtag is used, so `zh-TW` is shown in the
+    // same locale as `zh-CN`
+    let language = s.split(['-', '_']).next().unwrap_or_default();
+
+    Self::ALL
+      .into_iter()
+      .find(|locale| language.eq_ignore_ascii_case(&locale.to_string()))
+      .ok_or_else(|| anyhow!("unsupported locale: {s}"))
+  }
+}
+
+/// Translate `message` into the locale of the current request.
+pub(crate) fn t(message: &'static str) -> &'static str {
+  Locale::current().translate(message)
+}
+
+/// Translate `message` into the locale of the current request, replacing `{0}`,
+/// `{1}`, and so on with `arguments`, which may be reordered by translations.
+pub(crate) fn tf(message: &'static str, arguments: &[&dyn Display]) -> String {
+  let mut translated = t(message).to_string();
+
+  for (i, argument) in arguments.iter().enumerate() {
+    translated = translated.replace(&format!("{{{i}}}"), &argument.to_string());
+  }
+
+  translated
+}
+
+/// An amount of sats, formatted for the locale of the current request.
+pub(crate) struct Sats(pub(crate) u64);
+
+impl Display for Sats {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", Locale::current().format_sats(self.0))
+  }
+}
+
+/// A `<time>` element, formatted for the locale of the current request, with
+/// a machine-readable `datetime` attribute.
+pub(crate) struct Time(pub(crate) DateTime<Utc>);
+
+impl Display for Time {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "<time datetime={}>{}</time>",
+      self.0.format("%Y-%m-%dT%H:%M:%SZ"),
+      Locale::current().format_time(self.0),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, regex::Regex};
+
+  #[test]
+  fn negotiate() {
+    #[track_caller]
+    fn case(query: Option<&str>, accept_language: Option<&str>, expected: Locale) {
+      assert_eq!(Locale::negotiate(query, accept_language), expected);
+    }
+
+    case(None, None, Locale::En);
+    case(None, Some("ja"), Locale::Ja);
+    case(None, Some("ko-KR,ko;q=0.9"), Locale::Ko);
+    case(None, Some("zh-TW"), Locale::Zh);
+    case(None, Some("fr-FR,fr;q=0.9,zh;q=0.8,en;q=0.7"), Locale::Zh);
+    case(None, Some("en;q=0.5,ja;q=0.8"), Locale::Ja);
+    case(None, Some("ja;q=0,en"), Locale::En);
+    case(None, Some("*"), Locale::En);
+    case(None, Some("fr"), Locale::En);
+    case(Some("lang=ko"), Some("ja"), Locale::Ko);
+    case(Some("foo=bar&lang=zh"), None, Locale::Zh);
+    case(Some("lang=fr"), Some("ja"), Locale::Ja);
+  }
+
+  #[test]
+  fn from_str() {
+    assert_eq!("en".parse::<Locale>().unwrap(), Locale::En);
+    assert_eq!("JA".parse::<Locale>().unwrap(), Locale::Ja);
+    assert_eq!("zh_CN".parse::<Locale>().unwrap(), Locale::Zh);
+    assert!("fr".parse::<Locale>().is_err());
+
+    for locale in Locale::ALL {
+      assert_eq!(locale.to_string().parse::<Locale>().unwrap(), locale);
+    }
+  }
+
+  #[test]
+  fn translate() {
+    assert_eq!(Locale::En.translate("blocks"), "blocks");
+    assert_eq!(Locale::Ja.translate("blocks"), "ブロック");
+    assert_eq!(Locale::Ko.translate("blocks"), "블록");
+    assert_eq!(Locale::Zh.translate("blocks"), "区块");
+    assert_eq!(Locale::Zh.translate("untranslated"), "untranslated");
+  }
+
+  #[test]
+  fn t_uses_current_locale() {
+    assert_eq!(t("blocks"), "blocks");
+
+    assert_eq!(
+      tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(LOCALE.scope(
+          Negotiated {
+            locale: Locale::Zh,
+            ..default()
+          },
+          async { t("blocks") }
+        )),
+      "区块",
+    );
+  }
+
+  #[test]
+  fn carry() {
+    assert_eq!(
+      Locale::Ja.carry(
+        "<a href=/block/0>0</a> <a href='/runes/search?q=A&sort=mints'>mints</a> \
+         <a href=\"/sat/0#top\">sat</a> <a href=/static/index.css>css</a> \
+         <a href=https://docs.ordinals.com/>docs</a> <a href=/?lang=ko>ko</a>"
+      ),
+      "<a href=/block/0?lang=ja>0</a> <a href='/runes/search?q=A&sort=mints&lang=ja'>mints</a> \
+       <a href=\"/sat/0?lang=ja#top\">sat</a> <a href=/static/index.css>css</a> \
+       <a href=https://docs.ordinals.com/>docs</a> <a href=/?lang=ko>ko</a>",
+    );
+
+    assert_eq!(
+      Locale::Zh.carry("<form action=/search method=get><input name=query></form>"),
+      "<form action=/search method=get><input type=hidden name=lang value=zh><input name=query></form>",
+    );
+
+    assert_eq!(
+      Locale::Zh.carry("<form action=/decode method=post></form>"),
+      "<form action=/decode method=post></form>",
+    );
+  }
+
+  #[test]
+  fn tf_replaces_arguments() {
+    assert_eq!(tf("in {0} blocks", &[&3]), "in 3 blocks");
+
+    assert_eq!(
+      tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(LOCALE.scope(
+          Negotiated {
+            locale: Locale::Ja,
+            ..default()
+          },
+          async { tf("Showing {0} of {1} children.", &[&1, &2]) }
+        )),
+      "子2件中1件を表示しています。",
+    );
+  }
+
+  #[test]
+  fn format_sats() {
+    assert_eq!(Locale::En.format_sats(5000000000), "5000000000");
+    assert_eq!(Locale::Ja.format_sats(0), "0");
+    assert_eq!(Locale::Ja.format_sats(100), "100");
+    assert_eq!(Locale::Ko.format_sats(1000), "1,000");
+    assert_eq!(Locale::Zh.format_sats(5000000000), "5,000,000,000");
+  }
+
+  #[test]
+  fn format_time() {
+    let time = timestamp(1231006505);
+    assert_eq!(Locale::En.format_time(time), "2009-01-03 18:15:05 UTC");
+    assert_eq!(Locale::Ja.format_time(time), "2009年1月3日 18:15:05 UTC");
+    assert_eq!(Locale::Ko.format_time(time), "2009년 1월 3일 18:15:05 UTC");
+    assert_eq!(Locale::Zh.format_time(time), "2009年1月3日 18:15:05 UTC");
+    assert_eq!(
+      Time(time).to_string(),
+      "<time datetime=2009-01-03T18:15:05Z>2009-01-03 18:15:05 UTC</time>"
+    );
+  }
+
+  #[test]
+  fn catalogs_translate_every_message() {
+    let re = Regex::new(r#"\btf?\("([^"]+)"[,)]|"([^"]+)"\.tally\("#).unwrap();
+
+    let mut messages = BTreeSet::new();
+
+    for entry in fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("templates")).unwrap() {
+      let template = fs::read_to_string(entry.unwrap().path()).unwrap();
+      for captures in re.captures_iter(&template) {
+        messages.insert(
+          captures
+            .get(1)
+            .or(captures.get(2))
+            .unwrap()
+            .as_str()
+            .to_string(),
+        );
+      }
+    }
+
+    assert!(!messages.is_empty());
+
+    for (locale, catalog) in CATALOGS.iter() {
+      let keys = catalog.keys().cloned().collect::<BTreeSet<String>>();
+      assert_eq!(
+        keys, messages,
+        "catalog for {locale} does not match templates"
+      );
+    }
+  }
+}
@@ -38,6 +38,11 @@ pub struct Options {
   pub(crate) cookie_file: Option<PathBuf>,
   #[arg(long, alias = "datadir", help = "Store index in <DATA_DIR>.")]
   pub(crate) data_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Fetch blocks and transactions from Esplora REST API at <ESPLORA_URL> instead of Bitcoin Core."
+  )]
+  pub(crate) esplora_url: Option<String>,
   #[arg(
     long,
     help = "Don't look for inscriptions below <FIRST_INSCRIPTION_HEIGHT>."
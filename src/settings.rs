@@ -1,8 +1,9 @@
-use {super::*, bitcoincore_rpc::Auth};
+use {super::*, bitcoincore_rpc::Auth, subcommand::server::access::Access};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Settings {
+  access: Option<Access>,
   bitcoin_data_dir: Option<PathBuf>,
   bitcoin_rpc_limit: Option<u32>,
   bitcoin_rpc_password: Option<String>,
@@ -14,6 +15,7 @@ pub struct Settings {
   config_dir: Option<PathBuf>,
   cookie_file: Option<PathBuf>,
   data_dir: Option<PathBuf>,
+  esplora_url: Option<String>,
   first_inscription_height: Option<u32>,
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
@@ -110,6 +112,7 @@ impl Settings {
 
   pub fn or(self, source: Settings) -> Self {
     Self {
+      access: self.access.or(source.access),
       bitcoin_data_dir: self.bitcoin_data_dir.or(source.bitcoin_data_dir),
       bitcoin_rpc_limit: self.bitcoin_rpc_limit.or(source.bitcoin_rpc_limit),
       bitcoin_rpc_password: self.bitcoin_rpc_password.or(source.bitcoin_rpc_password),
@@ -121,6 +124,7 @@ impl Settings {
       config_dir: self.config_dir.or(source.config_dir),
       cookie_file: self.cookie_file.or(source.cookie_file),
       data_dir: self.data_dir.or(source.data_dir),
+      esplora_url: self.esplora_url.or(source.esplora_url),
       first_inscription_height: self
         .first_inscription_height
         .or(source.first_inscription_height),
@@ -152,6 +156,7 @@ impl Settings {
 
   pub fn from_options(options: Options) -> Self {
     Self {
+      access: None,
       bitcoin_data_dir: options.bitcoin_data_dir,
       bitcoin_rpc_limit: options.bitcoin_rpc_limit,
       bitcoin_rpc_password: options.bitcoin_rpc_password,
@@ -168,6 +173,7 @@ impl Settings {
       config_dir: options.config_dir,
       cookie_file: options.cookie_file,
       data_dir: options.data_dir,
+      esplora_url: options.esplora_url,
       first_inscription_height: options.first_inscription_height,
       height_limit: options.height_limit,
       hidden: None,
@@ -247,6 +253,7 @@ impl Settings {
     };
 
     Ok(Self {
+      access: None,
       bitcoin_data_dir: get_path("BITCOIN_DATA_DIR"),
       bitcoin_rpc_limit: get_u32("BITCOIN_RPC_LIMIT")?,
       bitcoin_rpc_password: get_string("BITCOIN_RPC_PASSWORD"),
@@ -258,6 +265,7 @@ impl Settings {
       config_dir: get_path("CONFIG_DIR"),
       cookie_file: get_path("COOKIE_FILE"),
       data_dir: get_path("DATA_DIR"),
+      esplora_url: get_string("ESPLORA_URL"),
       first_inscription_height: get_u32("FIRST_INSCRIPTION_HEIGHT")?,
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
@@ -279,6 +287,7 @@ impl Settings {
 
   pub fn for_env(dir: &Path, rpc_url: &str, server_url: &str) -> Self {
     Self {
+      access: None,
       bitcoin_data_dir: Some(dir.into()),
       bitcoin_rpc_password: None,
       bitcoin_rpc_url: Some(rpc_url.into()),
@@ -290,6 +299,7 @@ impl Settings {
       config_dir: None,
       cookie_file: None,
       data_dir: Some(dir.into()),
+      esplora_url: None,
       first_inscription_height: None,
       height_limit: None,
       hidden: None,
@@ -343,6 +353,7 @@ impl Settings {
     };
 
     Ok(Self {
+      access: self.access,
       bitcoin_data_dir: Some(bitcoin_data_dir),
       bitcoin_rpc_limit: Some(self.bitcoin_rpc_limit.unwrap_or(12)),
       bitcoin_rpc_password: self.bitcoin_rpc_password,
@@ -359,6 +370,7 @@ impl Settings {
       config_dir: None,
       cookie_file: Some(cookie_file),
       data_dir: Some(data_dir),
+      esplora_url: self.esplora_url,
       first_inscription_height: Some(if self.integration_test {
         0
       } else {
@@ -502,6 +514,10 @@ impl Settings {
     Ok(path.join(".cookie"))
   }
 
+  pub(crate) fn access(&self) -> Option<&Access> {
+    self.access.as_ref()
+  }
+
   pub fn credentials(&self) -> Option<(&str, &str)> {
     self
       .server_username
@@ -513,6 +529,10 @@ impl Settings {
     self.data_dir.as_ref().unwrap().into()
   }
 
+  pub fn esplora_url(&self) -> Option<&str> {
+    self.esplora_url.as_deref()
+  }
+
   pub fn first_inscription_height(&self) -> u32 {
     self.first_inscription_height.unwrap()
   }
@@ -1023,6 +1043,7 @@ mod tests {
       ("CONFIG_DIR", "config dir"),
       ("COOKIE_FILE", "cookie file"),
       ("DATA_DIR", "/data/dir"),
+      ("ESPLORA_URL", "esplora url"),
       ("FIRST_INSCRIPTION_HEIGHT", "2"),
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
@@ -1047,6 +1068,7 @@ mod tests {
     pretty_assert_eq!(
       Settings::from_env(env).unwrap(),
       Settings {
+        access: None,
         bitcoin_data_dir: Some("/bitcoin/data/dir".into()),
         bitcoin_rpc_limit: Some(12),
         bitcoin_rpc_password: Some("bitcoin password".into()),
@@ -1058,6 +1080,7 @@ mod tests {
         config_dir: Some("config dir".into()),
         cookie_file: Some("cookie file".into()),
         data_dir: Some("/data/dir".into()),
+        esplora_url: Some("esplora url".into()),
         first_inscription_height: Some(2),
         height_limit: Some(3),
         hidden: Some(
@@ -1106,6 +1129,7 @@ mod tests {
           "--config-dir=config dir",
           "--cookie-file=cookie file",
           "--datadir=/data/dir",
+          "--esplora-url=esplora url",
           "--first-inscription-height=2",
           "--height-limit=3",
           "--index-addresses",
@@ -1123,6 +1147,7 @@ mod tests {
         .unwrap()
       ),
       Settings {
+        access: None,
         bitcoin_data_dir: Some("/bitcoin/data/dir".into()),
         bitcoin_rpc_limit: Some(12),
         bitcoin_rpc_password: Some("bitcoin password".into()),
@@ -1134,6 +1159,7 @@ mod tests {
         config_dir: Some("config dir".into()),
         cookie_file: Some("cookie file".into()),
         data_dir: Some("/data/dir".into()),
+        esplora_url: Some("esplora url".into()),
         first_inscription_height: Some(2),
         height_limit: Some(3),
         hidden: None,
@@ -6,7 +6,8 @@ pub struct CompactOutput {
   pub runestone: Option<Artifact>,
 }
 
-#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+#[derive(Serialize, Eq, PartialEq, Deserialize, Debug, JsonSchema)]
+#[schemars(rename = "Decode")]
 pub struct RawOutput {
   pub inscriptions: Vec<ParsedEnvelope>,
   pub runestone: Option<Artifact>,
@@ -6,7 +6,7 @@ mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
-  #[command(about = "Write inscription numbers and ids to a tab-separated file")]
+  #[command(about = "Write inscriptions, runes or rune balances to a file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
@@ -1,19 +1,75 @@
-use super::*;
+use {
+  super::*,
+  crate::index::export::{Column, ExportOptions, Format, Table},
+};
 
 #[derive(Debug, Parser)]
 pub(crate) struct Export {
+  #[arg(
+    long,
+    value_enum,
+    value_delimiter = ',',
+    help = "Export comma-separated <COLUMNS> of inscriptions. [default: number,id,satpoint]"
+  )]
+  columns: Vec<Column>,
+  #[arg(long, value_enum, default_value_t, help = "Write export as <FORMAT>.")]
+  format: Format,
+  #[arg(long, help = "Only export records created at or after <FROM_HEIGHT>.")]
+  from_height: Option<u32>,
   #[arg(long, help = "Include addresses in export")]
   include_addresses: bool,
-  #[arg(long, help = "Write export to <TSV>")]
-  tsv: String,
+  #[arg(
+    long,
+    required_unless_present = "tsv",
+    help = "Write export to <OUTPUT>"
+  )]
+  output: Option<String>,
+  #[arg(long, value_enum, default_value_t, help = "Export <TABLE>.")]
+  table: Table,
+  #[arg(long, help = "Only export records created at or before <TO_HEIGHT>.")]
+  to_height: Option<u32>,
+  #[arg(
+    long,
+    conflicts_with_all = ["format", "output"],
+    help = "Write export to <TSV>"
+  )]
+  tsv: Option<String>,
 }
 
 impl Export {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let mut columns = self.columns;
+
+    if self.include_addresses {
+      if columns.is_empty() {
+        columns = vec![Column::Number, Column::Id, Column::Satpoint];
+      }
+
+      if !columns.contains(&Column::Address) {
+        columns.push(Column::Address);
+      }
+    }
+
+    // the legacy `--tsv` export has no header row
+    let (filename, format, header) = match self.tsv {
+      Some(tsv) => (tsv, Format::Tsv, false),
+      None => (self.output.unwrap(), self.format, true),
+    };
+
     let index = Index::open(&settings)?;
 
     index.update()?;
-    index.export(&self.tsv, self.include_addresses)?;
+    index.export(
+      &filename,
+      &ExportOptions {
+        columns,
+        format,
+        from_height: self.from_height,
+        header,
+        table: self.table,
+        to_height: self.to_height,
+      },
+    )?;
 
     Ok(None)
   }
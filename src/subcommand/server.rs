@@ -2,26 +2,41 @@ use {
   self::{
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
+    access::AccessControl,
+    byte_ranges::ByteRanges,
+    collection_cache::CollectionCache,
+    conditional::{Conditional, Validator},
+    content_body::ContentBody,
     error::{OptionExt, ServerError, ServerResult},
+    moderation::{Hidden, Moderation},
+    openapi::OpenApiRouter,
+    theme::{ColorScheme, Theme},
+    thumbnail::ThumbnailCache,
   },
   super::*,
-  crate::templates::{
-    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml,
-    InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent,
-    PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+  crate::{
+    index::pagination::{Cursor, Order, Pagination},
+    templates::{
+      AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionHtml, CollectionsHtml,
+      HomeHtml, InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml,
+      PageContent, PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewCsvHtml,
+      PreviewFontHtml, PreviewImageHtml, PreviewJsonHtml, PreviewMarkdownHtml, PreviewMidiHtml,
+      PreviewModelHtml, PreviewPdfHtml, PreviewStlHtml, PreviewTextHtml, PreviewUnknownHtml,
+      PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RuneSearchHtml, RunesHtml, RunesMintingHtml,
+      SatHtml, TransactionHtml,
+    },
   },
   axum::{
     body,
     extract::{DefaultBodyLimit, Extension, Json, Path, Query},
-    http::{header, HeaderValue, StatusCode, Uri},
+    http::{header, HeaderName, HeaderValue, StatusCode, Uri},
+    middleware,
     response::{IntoResponse, Redirect, Response},
-    routing::{get, post},
     Router,
   },
   axum_server::Handle,
-  brotli::Decompressor,
+  base64::Engine,
+  bitcoin::{hashes::sha256, psbt::Psbt},
   rust_embed::RustEmbed,
   rustls_acme::{
     acme::{LETS_ENCRYPT_PRODUCTION_DIRECTORY, LETS_ENCRYPT_STAGING_DIRECTORY},
@@ -29,10 +44,18 @@ use {
     caches::DirCache,
     AcmeConfig,
   },
-  std::{cmp::Ordering, str, sync::Arc},
+  std::{
+    cmp::{Ordering, Reverse},
+    net::SocketAddr,
+    str,
+    sync::Arc,
+  },
   tokio_stream::StreamExt,
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{DefaultPredicate, Predicate},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
     validate_request::ValidateRequestHeaderLayer,
@@ -43,9 +66,19 @@ pub(crate) use server_config::ServerConfig;
 
 mod accept_encoding;
 mod accept_json;
+pub(crate) mod access;
+mod byte_ranges;
+mod collection_cache;
+mod conditional;
+mod content_body;
 mod error;
+mod graphql;
+mod moderation;
+mod openapi;
 pub mod query;
 mod server_config;
+pub(crate) mod theme;
+mod thumbnail;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -58,6 +91,100 @@ struct Search {
   query: String,
 }
 
+// number of blocks over which recent mints are counted, both to rank runes
+// being minted and to project when a rune will mint out
+const RECENT_MINT_BLOCKS: u32 = 6;
+
+const COLLECTION_PAGE_SIZE: usize = 100;
+
+#[derive(Deserialize, JsonSchema)]
+struct RuneSearchQuery {
+  /// Maximum number of runes to return, at most the server's `--max-page-size`.
+  limit: Option<usize>,
+  /// Rune name prefix, with or without spacers.
+  q: String,
+  /// Rank by unspent outputs holding the rune, mints, or number. Defaults to `outputs`.
+  sort: Option<api::RuneSort>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct PaginationQuery {
+  /// Return the page after or before this cursor.
+  cursor: Option<Cursor>,
+  /// Maximum number of items to return, at most the server's `--max-page-size`.
+  limit: Option<usize>,
+  /// Sort order.
+  order: Option<Order>,
+}
+
+impl PaginationQuery {
+  // JSON clients may page with cursors and choose the page size and order,
+  // while HTML pages always link to numbered pages of the default size.
+  fn pagination(
+    self,
+    server_config: &ServerConfig,
+    accept_json: bool,
+    page: usize,
+    limit: usize,
+    order: Order,
+  ) -> ServerResult<Pagination> {
+    if !accept_json {
+      return Ok(Pagination {
+        cursor: None,
+        limit,
+        order,
+        page,
+      });
+    }
+
+    let limit = self.limit.unwrap_or(limit);
+
+    if limit == 0 || limit > server_config.max_page_size {
+      return Err(ServerError::BadRequest(format!(
+        "limit must be between 1 and {}",
+        server_config.max_page_size
+      )));
+    }
+
+    Ok(Pagination {
+      cursor: self.cursor,
+      limit,
+      order: self.order.unwrap_or(order),
+      page,
+    })
+  }
+}
+
+#[derive(Deserialize)]
+struct ThumbnailQuery {
+  size: Option<u32>,
+}
+
+// Items of batch requests are queries, like those in the path of the
+// corresponding `GET` route, which may also be given as integers.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum BatchQuery {
+  Integer(serde_json::Number),
+  String(String),
+}
+
+impl BatchQuery {
+  fn parse<T: FromStr>(self, what: &str) -> ServerResult<T>
+  where
+    T::Err: Display,
+  {
+    let query = match self {
+      Self::Integer(number) => number.to_string(),
+      Self::String(string) => string,
+    };
+
+    query
+      .parse()
+      .map_err(|err| ServerError::BadRequest(format!("invalid {what} `{query}`: {err}")))
+  }
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -81,11 +208,42 @@ pub struct Server {
   pub(crate) csp_origin: Option<String>,
   #[arg(
     long,
-    help = "Decompress encoded content. Currently only supports brotli. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
+    help = "Decompress encoded content that the client does not accept, re-encoding it with an encoding the client does accept. Supports brotli, deflate, gzip, and zstd. Be careful using this on production instances. A decompressed inscription may be arbitrarily large, making decompression a DoS vector."
   )]
   pub(crate) decompress: bool,
   #[arg(long, help = "Disable JSON API.")]
   pub(crate) disable_json_api: bool,
+  #[arg(long, help = "Serve GraphQL API at `/graphql`.")]
+  pub(crate) enable_graphql: bool,
+  #[arg(
+    long,
+    default_value = "10",
+    help = "Reject GraphQL queries nested more than <GRAPHQL_MAX_DEPTH> levels deep."
+  )]
+  pub(crate) graphql_max_depth: usize,
+  #[arg(
+    long,
+    default_value = "10000",
+    help = "Reject GraphQL queries with complexity over <GRAPHQL_MAX_COMPLEXITY>. Each field adds one, and list fields multiply the complexity of their items by their limit."
+  )]
+  pub(crate) graphql_max_complexity: usize,
+  #[arg(
+    long,
+    default_value = "1000",
+    help = "Allow JSON API clients to request pages of up to <MAX_PAGE_SIZE> items."
+  )]
+  pub(crate) max_page_size: usize,
+  #[arg(
+    long,
+    default_value = "100",
+    help = "Allow batch JSON requests of up to <MAX_BATCH_SIZE> items."
+  )]
+  pub(crate) max_batch_size: usize,
+  #[arg(
+    long,
+    help = "Persist inscriptions hidden with the admin API in <HIDDEN_FILE>, which is reloaded when modified. [default: hidden.json next to the index]"
+  )]
+  pub(crate) hidden_file: Option<PathBuf>,
   #[arg(
     long,
     help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]"
@@ -107,8 +265,25 @@ pub struct Server {
   pub(crate) https: bool,
   #[arg(long, help = "Redirect HTTP traffic to HTTPS.")]
   pub(crate) redirect_http_to_https: bool,
+  #[arg(
+    long,
+    conflicts_with_all = ["no_sync", "publish"],
+    help = "Serve index snapshots published to <FOLLOW> by `ord server --publish` instead of updating the index."
+  )]
+  pub(crate) follow: Option<PathBuf>,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
+  #[arg(
+    long,
+    help = "Publish a read-only snapshot of the index to <PUBLISH> after updates, for `ord server --follow` instances."
+  )]
+  pub(crate) publish: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value = "10m",
+    help = "Publish index snapshots at most once every <PUBLISH_INTERVAL>."
+  )]
+  pub(crate) publish_interval: humantime::Duration,
   #[arg(
     long,
     help = "Proxy `/content/INSCRIPTION_ID` and other recursive endpoints to `<PROXY>` if the inscription is not present on current chain."
@@ -120,152 +295,354 @@ pub struct Server {
     help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
   )]
   pub(crate) polling_interval: humantime::Duration,
+  #[arg(
+    long,
+    help = "Store rendered thumbnails in <THUMBNAIL_CACHE>. [default: <DATA_DIR>/thumbnails]"
+  )]
+  pub(crate) thumbnail_cache: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value = "1073741824",
+    help = "Keep up to <THUMBNAIL_CACHE_SIZE> bytes of thumbnails in the thumbnail cache, removing the least recently used."
+  )]
+  pub(crate) thumbnail_cache_size: u64,
+  #[arg(
+    long,
+    default_value = "dark",
+    help = "Render pages with <COLOR_SCHEME>. `auto` follows the color scheme preferred by the browser."
+  )]
+  pub(crate) color_scheme: ColorScheme,
+  #[arg(
+    long,
+    help = "Override templates with files of the same name in <THEME_DIR>/templates, the page layout with <THEME_DIR>/templates/page.html, and static assets with files in <THEME_DIR>/static."
+  )]
+  pub(crate) theme_dir: Option<PathBuf>,
+  #[arg(long, help = "Show image at <SITE_LOGO> URL in page header.")]
+  pub(crate) site_logo: Option<String>,
+  #[arg(long, help = "Show <SITE_TITLE> in page header instead of `Ordinals`.")]
+  pub(crate) site_title: Option<String>,
 }
 
 impl Server {
+  pub(crate) fn router() -> OpenApiRouter<Arc<ServerConfig>> {
+    OpenApiRouter::new()
+      .get("/", Self::home)
+      .get("/address/:address", Self::address)
+      .json::<Vec<api::schema::OutPoint>>()
+      .get("/address/:address/:page", Self::address_paginated)
+      .json::<api::AddressInfo>()
+      .get("/admin/hidden", Self::admin_hidden)
+      .post("/admin/hidden", Self::admin_hide)
+      .delete("/admin/hidden", Self::admin_unhide)
+      .post("/addresses", Self::addresses_json)
+      .body::<Vec<BatchQuery>>()
+      .json::<Vec<api::BatchResult<Vec<api::schema::OutPoint>>>>()
+      .get("/block/:query", Self::block)
+      .json::<api::Block>()
+      .get("/blockcount", Self::block_count)
+      .get("/blockhash", Self::block_hash)
+      .get("/blockhash/:height", Self::block_hash_from_height)
+      .get("/blockheight", Self::block_height)
+      .get("/blocks", Self::blocks)
+      .json::<api::Blocks>()
+      .post("/blocks", Self::blocks_json)
+      .body::<Vec<BatchQuery>>()
+      .json::<Vec<api::BatchResult<api::Block>>>()
+      .get("/blocktime", Self::block_time)
+      .get("/bounties", Self::bounties)
+      .get("/children/:inscription_id", Self::children)
+      .query::<PaginationQuery>()
+      .json::<api::Inscriptions>()
+      .get("/children/:inscription_id/:page", Self::children_paginated)
+      .query::<PaginationQuery>()
+      .json::<api::Inscriptions>()
+      .get("/clock", Self::clock)
+      .get("/collection/:inscription_id", Self::collection)
+      .json::<api::Collection>()
+      .get(
+        "/collection/:inscription_id/:page",
+        Self::collection_paginated,
+      )
+      .json::<api::Collection>()
+      .get("/collections", Self::collections)
+      .query::<PaginationQuery>()
+      .json::<api::Inscriptions>()
+      .get("/collections/:page", Self::collections_paginated)
+      .query::<PaginationQuery>()
+      .json::<api::Inscriptions>()
+      .get("/content/:inscription_id", Self::content)
+      .post("/decode", Self::decode_json)
+      .body::<api::schema::EncodedTransaction>()
+      .json::<api::DecodePreview>()
+      .get("/decode/:txid", Self::decode)
+      .json::<api::Decode>()
+      .get("/faq", Self::faq)
+      .get("/favicon.ico", Self::favicon)
+      .get("/feed.xml", Self::feed)
+      .post("/graphql", Self::graphql)
+      .body::<api::schema::GraphQLRequest>()
+      .json::<api::schema::GraphQLResponse>()
+      .get("/input/:block/:transaction/:input", Self::input)
+      .get("/inscription/:inscription_query", Self::inscription)
+      .json::<api::Inscription>()
+      .get(
+        "/inscription/:inscription_query/:child",
+        Self::inscription_child,
+      )
+      .json::<api::Inscription>()
+      .get("/inscriptions", Self::inscriptions)
+      .query::<PaginationQuery>()
+      .json::<api::Inscriptions>()
+      .post("/inscriptions", Self::inscriptions_json)
+      .body::<Vec<InscriptionId>>()
+      .json::<Vec<api::Inscription>>()
+      .post("/inscriptions/numbers", Self::inscriptions_by_number_json)
+      .body::<Vec<i32>>()
+      .json::<Vec<api::BatchResult<api::Inscription>>>()
+      .get("/inscriptions/:page", Self::inscriptions_paginated)
+      .query::<PaginationQuery>()
+      .json::<api::Inscriptions>()
+      .get("/inscriptions/block/:height", Self::inscriptions_in_block)
+      .json::<api::Inscriptions>()
+      .get(
+        "/inscriptions/block/:height/:page",
+        Self::inscriptions_in_block_paginated,
+      )
+      .json::<api::Inscriptions>()
+      .get("/install.sh", Self::install_script)
+      .get("/ordinal/:sat", Self::ordinal)
+      .get("/openapi.json", Self::openapi)
+      .get("/output/:output", Self::output)
+      .json::<api::Output>()
+      .post("/outputs", Self::outputs)
+      .body::<Vec<api::schema::OutPoint>>()
+      .json::<Vec<api::Output>>()
+      .get("/parents/:inscription_id", Self::parents)
+      .get("/parents/:inscription_id/:page", Self::parents_paginated)
+      .get("/preview/:inscription_id", Self::preview)
+      .get(
+        "/r/address/:address/inscriptions",
+        Self::address_inscriptions_recursive,
+      )
+      .json::<api::AddressInscriptions>()
+      .get(
+        "/r/address/:address/inscriptions/:page",
+        Self::address_inscriptions_recursive_paginated,
+      )
+      .json::<api::AddressInscriptions>()
+      .get("/r/blockhash", Self::block_hash_json)
+      .json::<api::schema::BlockHash>()
+      .get("/r/blockhash/:height", Self::block_hash_from_height_json)
+      .json::<api::schema::BlockHash>()
+      .get("/r/blockheight", Self::block_height)
+      .text()
+      .get("/r/blocktime", Self::block_time)
+      .text()
+      .get("/r/blockinfo/:query", Self::block_info)
+      .json::<api::BlockInfo>()
+      .get(
+        "/r/inscription/:inscription_id",
+        Self::inscription_recursive,
+      )
+      .json::<api::InscriptionRecursive>()
+      .get("/r/children/:inscription_id", Self::children_recursive)
+      .json::<api::Children>()
+      .get(
+        "/r/children/:inscription_id/:page",
+        Self::children_recursive_paginated,
+      )
+      .json::<api::Children>()
+      .get(
+        "/r/children/:inscription_id/inscriptions",
+        Self::child_inscriptions_recursive,
+      )
+      .json::<api::ChildInscriptions>()
+      .get(
+        "/r/children/:inscription_id/inscriptions/:page",
+        Self::child_inscriptions_recursive_paginated,
+      )
+      .json::<api::ChildInscriptions>()
+      .get("/r/metadata/:inscription_id", Self::metadata)
+      .json::<api::schema::Hex>()
+      .get("/r/parents/:inscription_id", Self::parents_recursive)
+      .json::<api::Inscriptions>()
+      .get(
+        "/r/parents/:inscription_id/:page",
+        Self::parents_recursive_paginated,
+      )
+      .json::<api::Inscriptions>()
+      .get("/r/rune/:rune", Self::rune_recursive)
+      .json::<api::RuneRecursive>()
+      .get("/r/sat/:sat_number", Self::sat_inscriptions)
+      .json::<api::SatInscriptions>()
+      .get("/r/sat/:sat_number/:page", Self::sat_inscriptions_paginated)
+      .json::<api::SatInscriptions>()
+      .get(
+        "/r/sat/:sat_number/at/:index",
+        Self::sat_inscription_at_index,
+      )
+      .json::<api::SatInscription>()
+      .get("/r/utxo/:outpoint", Self::utxo_recursive)
+      .json::<api::UtxoRecursive>()
+      .get("/range/:start/:end", Self::range)
+      .get("/rare.txt", Self::rare_txt)
+      .get("/rune/:rune", Self::rune)
+      .json::<api::Rune>()
+      .get("/rune/:rune/mints", Self::rune_mints)
+      .json::<api::RuneMints>()
+      .get("/runes", Self::runes)
+      .query::<PaginationQuery>()
+      .json::<api::Runes>()
+      .post("/runes", Self::runes_json)
+      .body::<Vec<BatchQuery>>()
+      .json::<Vec<api::BatchResult<api::Rune>>>()
+      .get("/runes/:page", Self::runes_paginated)
+      .query::<PaginationQuery>()
+      .json::<api::Runes>()
+      .get("/runes/balances", Self::runes_balances)
+      .json::<api::schema::RuneBalances>()
+      .get("/runes/minting", Self::runes_minting)
+      .json::<api::RunesMinting>()
+      .get("/runes/minting/:page", Self::runes_minting_paginated)
+      .json::<api::RunesMinting>()
+      .get("/runes/search", Self::runes_search)
+      .query::<RuneSearchQuery>()
+      .json::<api::RuneSearch>()
+      .get("/sat/:sat", Self::sat)
+      .json::<api::Sat>()
+      .post("/sats", Self::sats_json)
+      .body::<Vec<BatchQuery>>()
+      .json::<Vec<api::BatchResult<api::Sat>>>()
+      .get("/search", Self::search_by_query)
+      .get("/search/*query", Self::search_by_path)
+      .get("/static/*path", Self::static_asset)
+      .get("/status", Self::status)
+      .json::<api::Status>()
+      .get("/thumbnail/:inscription_id", Self::thumbnail)
+      .get("/tx/:txid", Self::transaction)
+      .json::<api::Transaction>()
+      .post("/txs", Self::transactions_json)
+      .body::<Vec<BatchQuery>>()
+      .json::<Vec<api::BatchResult<api::Transaction>>>()
+      .get("/update", Self::update)
+  }
+
   pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
     Runtime::new()?.block_on(async {
       let index_clone = index.clone();
       let integration_test = settings.integration_test();
+      let follow = self.follow.clone();
+      let publish = self.publish.clone();
 
-      let index_thread = thread::spawn(move || loop {
-        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
-          break;
-        }
+      let index_thread = thread::spawn(move || {
+        let mut last_published: Option<Instant> = None;
 
-        if !self.no_sync {
-          if let Err(error) = index_clone.update() {
-            log::warn!("Updating index: {error}");
+        loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
           }
-        }
 
-        thread::sleep(if integration_test {
-          Duration::from_millis(100)
-        } else {
-          self.polling_interval.into()
-        });
+          if let Some(dir) = &follow {
+            if let Err(error) = index_clone.follow_snapshot(dir) {
+              log::warn!("Following index snapshot: {error}");
+            }
+          } else if !self.no_sync {
+            if let Err(error) = index_clone.update() {
+              log::warn!("Updating index: {error}");
+            } else if let Some(dir) = &publish {
+              if last_published
+                .map(|published| published.elapsed() >= *self.publish_interval)
+                .unwrap_or(true)
+              {
+                match index_clone.publish_snapshot(dir) {
+                  Ok(()) => last_published = Some(Instant::now()),
+                  Err(error) => log::warn!("Publishing index snapshot: {error}"),
+                }
+              }
+            }
+          }
+
+          thread::sleep(if integration_test {
+            Duration::from_millis(100)
+          } else {
+            self.polling_interval.into()
+          });
+        }
       });
 
       INDEXER.lock().unwrap().replace(index_thread);
 
       let settings = Arc::new(settings);
+
+      let access_control = settings
+        .access()
+        .map(AccessControl::new)
+        .transpose()?
+        .map(Arc::new);
+
       let acme_domains = self.acme_domains()?;
 
       let server_config = Arc::new(ServerConfig {
         chain: settings.chain(),
+        collections: CollectionCache::default(),
         proxy: self.proxy.clone(),
         csp_origin: self.csp_origin.clone(),
         decompress: self.decompress,
         domain: acme_domains.first().cloned(),
+        graphql_schema: self
+          .enable_graphql
+          .then(|| graphql::schema(self.graphql_max_depth, self.graphql_max_complexity)),
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        max_batch_size: self.max_batch_size,
+        max_page_size: self.max_page_size,
+        moderation: Moderation::load(
+          self
+            .hidden_file
+            .clone()
+            .unwrap_or_else(|| settings.index().with_file_name("hidden.json")),
+        )?,
+        theme: Theme::load(
+          self.theme_dir.clone(),
+          self.color_scheme,
+          self.site_logo.clone(),
+          self.site_title.clone(),
+        )?,
+        thumbnail_cache: Some(ThumbnailCache::new(
+          self
+            .thumbnail_cache
+            .clone()
+            .unwrap_or_else(|| settings.data_dir().join("thumbnails")),
+          self.thumbnail_cache_size,
+        )),
       });
 
-      let router = Router::new()
-        .route("/", get(Self::home))
-        .route("/address/:address", get(Self::address))
-        .route("/block/:query", get(Self::block))
-        .route("/blockcount", get(Self::block_count))
-        .route("/blockhash", get(Self::block_hash))
-        .route("/blockhash/:height", get(Self::block_hash_from_height))
-        .route("/blockheight", get(Self::block_height))
-        .route("/blocks", get(Self::blocks))
-        .route("/blocktime", get(Self::block_time))
-        .route("/bounties", get(Self::bounties))
-        .route("/children/:inscription_id", get(Self::children))
-        .route(
-          "/children/:inscription_id/:page",
-          get(Self::children_paginated),
-        )
-        .route("/clock", get(Self::clock))
-        .route("/collections", get(Self::collections))
-        .route("/collections/:page", get(Self::collections_paginated))
-        .route("/content/:inscription_id", get(Self::content))
-        .route("/faq", get(Self::faq))
-        .route("/favicon.ico", get(Self::favicon))
-        .route("/feed.xml", get(Self::feed))
-        .route("/input/:block/:transaction/:input", get(Self::input))
-        .route("/inscription/:inscription_query", get(Self::inscription))
-        .route(
-          "/inscription/:inscription_query/:child",
-          get(Self::inscription_child),
-        )
-        .route("/inscriptions", get(Self::inscriptions))
-        .route("/inscriptions", post(Self::inscriptions_json))
-        .route("/inscriptions/:page", get(Self::inscriptions_paginated))
-        .route(
-          "/inscriptions/block/:height",
-          get(Self::inscriptions_in_block),
-        )
-        .route(
-          "/inscriptions/block/:height/:page",
-          get(Self::inscriptions_in_block_paginated),
-        )
-        .route("/install.sh", get(Self::install_script))
-        .route("/ordinal/:sat", get(Self::ordinal))
-        .route("/output/:output", get(Self::output))
-        .route("/outputs", post(Self::outputs))
-        .route("/parents/:inscription_id", get(Self::parents))
-        .route(
-          "/parents/:inscription_id/:page",
-          get(Self::parents_paginated),
-        )
-        .route("/preview/:inscription_id", get(Self::preview))
-        .route("/r/blockhash", get(Self::block_hash_json))
-        .route(
-          "/r/blockhash/:height",
-          get(Self::block_hash_from_height_json),
-        )
-        .route("/r/blockheight", get(Self::block_height))
-        .route("/r/blocktime", get(Self::block_time))
-        .route("/r/blockinfo/:query", get(Self::block_info))
-        .route(
-          "/r/inscription/:inscription_id",
-          get(Self::inscription_recursive),
-        )
-        .route("/r/children/:inscription_id", get(Self::children_recursive))
-        .route(
-          "/r/children/:inscription_id/:page",
-          get(Self::children_recursive_paginated),
-        )
-        .route(
-          "/r/children/:inscription_id/inscriptions",
-          get(Self::child_inscriptions_recursive),
-        )
-        .route(
-          "/r/children/:inscription_id/inscriptions/:page",
-          get(Self::child_inscriptions_recursive_paginated),
-        )
-        .route("/r/metadata/:inscription_id", get(Self::metadata))
-        .route("/r/parents/:inscription_id", get(Self::parents_recursive))
-        .route(
-          "/r/parents/:inscription_id/:page",
-          get(Self::parents_recursive_paginated),
-        )
-        .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
-        .route(
-          "/r/sat/:sat_number/:page",
-          get(Self::sat_inscriptions_paginated),
-        )
-        .route(
-          "/r/sat/:sat_number/at/:index",
-          get(Self::sat_inscription_at_index),
-        )
-        .route("/range/:start/:end", get(Self::range))
-        .route("/rare.txt", get(Self::rare_txt))
-        .route("/rune/:rune", get(Self::rune))
-        .route("/runes", get(Self::runes))
-        .route("/runes/:page", get(Self::runes_paginated))
-        .route("/runes/balances", get(Self::runes_balances))
-        .route("/sat/:sat", get(Self::sat))
-        .route("/search", get(Self::search_by_query))
-        .route("/search/*query", get(Self::search_by_path))
-        .route("/static/*path", get(Self::static_asset))
-        .route("/status", get(Self::status))
-        .route("/tx/:txid", get(Self::transaction))
-        .route("/decode/:txid", get(Self::decode))
-        .route("/update", get(Self::update))
+      let server_config_clone = server_config.clone();
+
+      thread::spawn(move || loop {
+        if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+          break;
+        }
+
+        if let Err(error) = server_config_clone.moderation.reload() {
+          log::warn!("Reloading hidden inscriptions: {error}");
+        }
+
+        thread::sleep(if integration_test {
+          Duration::from_millis(100)
+        } else {
+          Duration::from_secs(1)
+        });
+      });
+
+      let router = Self::router()
+        .into_router()
         .fallback(Self::fallback)
+        .layer(middleware::from_fn_with_state(
+          access_control,
+          AccessControl::middleware,
+        ))
+        .layer(middleware::from_fn(Locale::middleware))
         .layer(Extension(index))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
@@ -280,9 +657,20 @@ impl Server {
         .layer(
           CorsLayer::new()
             .allow_methods([http::Method::GET])
-            .allow_origin(Any),
+            .allow_headers([HeaderName::from_static(access::API_KEY_HEADER)])
+            .allow_origin(Any)
+            // CORS headers do not depend on the request, and the default
+            // `Vary` header would replace the one set by handlers
+            .vary([]),
+        )
+        .layer(
+          CompressionLayer::new().compress_when(DefaultPredicate::new().and(
+            // partial content is a slice of the uncompressed body
+            |status: StatusCode, _: http::Version, _: &HeaderMap, _: &http::Extensions| {
+              status != StatusCode::PARTIAL_CONTENT
+            },
+          )),
         )
-        .layer(CompressionLayer::new())
         .with_state(server_config.clone());
 
       let router = if server_config.json_api_enabled {
@@ -390,7 +778,7 @@ impl Server {
           axum_server::Server::bind(addr)
             .handle(handle)
             .acceptor(acceptor)
-            .serve(router.into_make_service())
+            .serve(router.into_make_service_with_connect_info::<SocketAddr>())
             .await
         }
         SpawnConfig::Redirect(destination) => {
@@ -407,7 +795,7 @@ impl Server {
         SpawnConfig::Http => {
           axum_server::Server::bind(addr)
             .handle(handle)
-            .serve(router.into_make_service())
+            .serve(router.into_make_service_with_connect_info::<SocketAddr>())
             .await
         }
       }
@@ -533,43 +921,14 @@ impl Server {
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let inscriptions = index.get_inscription_ids_by_sat(sat)?;
-      let satpoint = index.rare_sat_satpoint(sat)?.or_else(|| {
-        inscriptions.first().and_then(|&first_inscription_id| {
-          index
-            .get_inscription_satpoint_by_id(first_inscription_id)
-            .ok()
-            .flatten()
-        })
-      });
-      let blocktime = index.block_time(sat.height())?;
-
-      let charms = sat.charms();
-
       Ok(if accept_json {
-        Json(api::Sat {
-          number: sat.0,
-          decimal: sat.decimal().to_string(),
-          degree: sat.degree().to_string(),
-          name: sat.name(),
-          block: sat.height().0,
-          cycle: sat.cycle(),
-          epoch: sat.epoch().0,
-          period: sat.period(),
-          offset: sat.third(),
-          rarity: sat.rarity(),
-          percentile: sat.percentile(),
-          satpoint,
-          timestamp: blocktime.timestamp().timestamp(),
-          inscriptions,
-          charms: Charm::charms(charms),
-        })
-        .into_response()
+        Json(Self::sat_info(&index, sat)?).into_response()
       } else {
+        let inscriptions = index.get_inscription_ids_by_sat(sat)?;
         SatHtml {
           sat,
-          satpoint,
-          blocktime,
+          satpoint: Self::sat_satpoint(&index, sat, &inscriptions)?,
+          blocktime: index.block_time(sat.height())?,
           inscriptions,
         }
         .page(server_config)
@@ -578,36 +937,102 @@ impl Server {
     })
   }
 
+  fn sat_info(index: &Index, sat: Sat) -> ServerResult<api::Sat> {
+    let inscriptions = index.get_inscription_ids_by_sat(sat)?;
+
+    Ok(api::Sat {
+      number: sat.0,
+      decimal: sat.decimal().to_string(),
+      degree: sat.degree().to_string(),
+      name: sat.name(),
+      block: sat.height().0,
+      cycle: sat.cycle(),
+      epoch: sat.epoch().0,
+      period: sat.period(),
+      offset: sat.third(),
+      rarity: sat.rarity(),
+      percentile: sat.percentile(),
+      satpoint: Self::sat_satpoint(index, sat, &inscriptions)?,
+      timestamp: index.block_time(sat.height())?.timestamp().timestamp(),
+      inscriptions,
+      charms: Charm::charms(sat.charms()),
+    })
+  }
+
+  fn sat_satpoint(
+    index: &Index,
+    sat: Sat,
+    inscriptions: &[InscriptionId],
+  ) -> Result<Option<SatPoint>> {
+    Ok(index.rare_sat_satpoint(sat)?.or_else(|| {
+      inscriptions.first().and_then(|&first_inscription_id| {
+        index
+          .get_inscription_satpoint_by_id(first_inscription_id)
+          .ok()
+          .flatten()
+      })
+    }))
+  }
+
+  async fn sats_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(queries): Json<Vec<BatchQuery>>,
+  ) -> ServerResult {
+    Self::batch(&server_config, accept_json, queries, |query| {
+      Self::sat_info(&index, query.parse("sat")?)
+    })
+  }
+
   async fn ordinal(Path(sat): Path<String>) -> Redirect {
     Redirect::to(&format!("/sat/{sat}"))
   }
 
+  async fn openapi(Extension(server_config): Extension<Arc<ServerConfig>>) -> ServerResult {
+    if !server_config.json_api_enabled {
+      return Err(ServerError::NotFound("JSON API disabled".into()));
+    }
+
+    lazy_static! {
+      static ref DOCUMENT: serde_json::Value = Server::router().document();
+    }
+
+    Ok(Json(&*DOCUMENT).into_response())
+  }
+
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(outpoint): Path<OutPoint>,
     AcceptJson(accept_json): AcceptJson,
+    conditional: Conditional,
   ) -> ServerResult {
     task::block_in_place(|| {
       let (output_info, txout) = index
         .get_output_info(outpoint)?
         .ok_or_not_found(|| format!("output {outpoint}"))?;
 
-      Ok(if accept_json {
-        Json(output_info).into_response()
-      } else {
-        OutputHtml {
-          chain: server_config.chain,
-          inscriptions: output_info.inscriptions,
-          outpoint,
-          output: txout,
-          runes: output_info.runes,
-          sat_ranges: output_info.sat_ranges,
-          spent: output_info.spent,
-        }
-        .page(server_config)
-        .into_response()
-      })
+      conditional.respond(
+        Validator::new(accept_json, &server_config.theme, &output_info),
+        || {
+          Ok(if accept_json {
+            Json(output_info).into_response()
+          } else {
+            OutputHtml {
+              chain: server_config.chain,
+              inscriptions: output_info.inscriptions,
+              outpoint,
+              output: txout,
+              runes: output_info.runes,
+              sat_ranges: output_info.sat_ranges,
+              spent: output_info.spent,
+            }
+            .page(server_config)
+            .into_response()
+          })
+        },
+      )
     })
   }
 
@@ -658,6 +1083,141 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
     AcceptJson(accept_json): AcceptJson,
+    conditional: Conditional,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let rune = Self::rune_info(&index, rune_query)?;
+
+      conditional.respond(
+        Validator::new(accept_json, &server_config.theme, &rune),
+        || {
+          Ok(if accept_json {
+            Json(rune).into_response()
+          } else {
+            rune.page(server_config).into_response()
+          })
+        },
+      )
+    })
+  }
+
+  fn rune_info(index: &Index, rune_query: query::Rune) -> ServerResult<RuneHtml> {
+    if !index.has_rune_index() {
+      return Err(ServerError::NotFound(
+        "this server has no rune index".to_string(),
+      ));
+    }
+
+    let rune = match rune_query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    };
+
+    let (id, entry, parent) = index
+      .rune(rune)?
+      .ok_or_not_found(|| format!("rune {rune}"))?;
+
+    let block_height = index.block_height()?.unwrap_or(Height(0));
+
+    let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
+
+    Ok(RuneHtml {
+      entry,
+      id,
+      mintable,
+      parent,
+    })
+  }
+
+  async fn rune_mints(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let RuneHtml {
+        entry,
+        id,
+        mintable,
+        ..
+      } = Self::rune_info(&index, rune_query)?;
+
+      let next = index.block_count()?;
+
+      let remaining = entry
+        .terms
+        .and_then(|terms| terms.cap)
+        .unwrap_or_default()
+        .saturating_sub(entry.mints);
+
+      let recent_mints = index.get_rune_mints_since(id, next.saturating_sub(RECENT_MINT_BLOCKS))?;
+
+      // assume that mints continue at the recent rate, and that the rune only
+      // mints out if that happens before minting ends
+      let projected_mint_out = if mintable && recent_mints > 0 {
+        u64::try_from(
+          remaining
+            .saturating_mul(RECENT_MINT_BLOCKS.into())
+            .div_ceil(recent_mints.into()),
+        )
+        .ok()
+        .and_then(|blocks| u64::from(next).checked_add(blocks.saturating_sub(1)))
+        .filter(|&height| entry.end().map_or(true, |end| height < end))
+      } else {
+        None
+      };
+
+      let mints = api::RuneMints {
+        activity: index
+          .get_rune_activity(id, 100)?
+          .into_iter()
+          .map(|(height, mints, burned)| api::RuneBlockActivity {
+            burned,
+            height,
+            mints,
+          })
+          .collect(),
+        blocks_until_end: entry.end().map(|end| end.saturating_sub(next.into())),
+        blocks_until_start: entry.start().map(|start| start.saturating_sub(next.into())),
+        entry,
+        id,
+        mintable,
+        projected_mint_out,
+        recent_mints: index
+          .get_recent_rune_mints(id)?
+          .into_iter()
+          .map(|(txid, height)| api::RuneMint { height, txid })
+          .collect(),
+        remaining,
+      };
+
+      Ok(if accept_json {
+        Json(mints).into_response()
+      } else {
+        mints.page(server_config).into_response()
+      })
+    })
+  }
+
+  async fn runes_minting(
+    server_config: Extension<Arc<ServerConfig>>,
+    index: Extension<Arc<Index>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::runes_minting_paginated(server_config, index, Path(0), accept_json).await
+  }
+
+  async fn runes_minting_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page): Path<usize>,
+    AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
       if !index.has_rune_index() {
@@ -666,55 +1226,69 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let mut entries = index
+        .get_runes_minting(RECENT_MINT_BLOCKS)?
+        .into_iter()
+        .map(|(id, entry, recent_mints)| api::MintingRune {
+          end: entry.end(),
+          id,
+          mints: entry.mints,
+          recent_mints,
+          remaining: entry
+            .terms
+            .and_then(|terms| terms.cap)
+            .unwrap_or_default()
+            .saturating_sub(entry.mints),
+          spaced_rune: entry.spaced_rune,
+          symbol: entry.symbol,
+        })
+        .collect::<Vec<api::MintingRune>>();
 
-      let (id, entry, parent) = index
-        .rune(rune)?
-        .ok_or_not_found(|| format!("rune {rune}"))?;
+      // most recently active first, then most minted, then oldest
+      entries.sort_by_key(|entry| (Reverse(entry.recent_mints), Reverse(entry.mints), entry.id));
 
-      let block_height = index.block_height()?.unwrap_or(Height(0));
+      let start = page.saturating_mul(100);
 
-      let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
+      let more = entries.len() > start.saturating_add(100);
+
+      let minting = RunesMintingHtml {
+        entries: entries.into_iter().skip(start).take(100).collect(),
+        more,
+        next: more.then_some(page + 1),
+        prev: page.checked_sub(1),
+      };
 
       Ok(if accept_json {
-        Json(api::Rune {
-          entry,
-          id,
-          mintable,
-          parent,
-        })
-        .into_response()
+        Json(minting).into_response()
       } else {
-        RuneHtml {
-          entry,
-          id,
-          mintable,
-          parent,
-        }
-        .page(server_config)
-        .into_response()
+        minting.page(server_config).into_response()
       })
     })
   }
 
+  async fn runes_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(queries): Json<Vec<BatchQuery>>,
+  ) -> ServerResult {
+    Self::batch(&server_config, accept_json, queries, |query| {
+      Self::rune_info(&index, query.parse("rune")?)
+    })
+  }
+
   async fn runes(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     accept_json: AcceptJson,
+    query: Query<PaginationQuery>,
   ) -> ServerResult<Response> {
     Self::runes_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
       accept_json,
+      query,
     )
     .await
   }
@@ -724,28 +1298,33 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<usize>,
     AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<PaginationQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (entries, more) = index.runes_paginated(50, page_index)?;
-
-      let prev = page_index.checked_sub(1);
-
-      let next = more.then_some(page_index + 1);
+      let page = index.runes_page(query.pagination(
+        &server_config,
+        accept_json,
+        page_index,
+        50,
+        Order::Desc,
+      )?)?;
 
       Ok(if accept_json {
-        Json(RunesHtml {
-          entries,
-          more,
-          prev,
-          next,
+        Json(api::Runes {
+          entries: page.items,
+          more: page.more,
+          prev: page_index.checked_sub(1),
+          next: page.more.then_some(page_index + 1),
+          next_cursor: page.next,
+          prev_cursor: page.prev,
         })
         .into_response()
       } else {
         RunesHtml {
-          entries,
-          more,
-          prev,
-          next,
+          entries: page.items,
+          more: page.more,
+          prev: page_index.checked_sub(1),
+          next: page.more.then_some(page_index + 1),
         }
         .page(server_config)
         .into_response()
@@ -753,21 +1332,105 @@ impl Server {
     })
   }
 
-  async fn runes_balances(
+  async fn runes_search(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
+    Query(search): Query<RuneSearchQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      Ok(if accept_json {
-        Json(
-          index
-            .get_rune_balance_map()?
-            .into_iter()
-            .map(|(rune, balances)| {
-              (
-                rune,
-                balances
-                  .into_iter()
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let limit = if accept_json {
+        search.limit.unwrap_or(100)
+      } else {
+        100
+      };
+
+      if limit == 0 || limit > server_config.max_page_size {
+        return Err(ServerError::BadRequest(format!(
+          "limit must be between 1 and {}",
+          server_config.max_page_size
+        )));
+      }
+
+      let query = search.q.trim().to_string();
+
+      // spacers are easy to misplace, so match on the letters of rune names
+      // alone, regardless of case
+      let prefix = query
+        .chars()
+        .filter(|c| !matches!(c, '•' | '.' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect::<String>();
+
+      let sort = search.sort.unwrap_or_default();
+
+      let exact = prefix.parse::<Rune>().ok();
+
+      // exact matches first, then runes starting with the query, then runes
+      // whose names are a typo away, each by the sort key, then oldest first
+      let (runes, more) = if prefix.chars().all(|c| c.is_ascii_uppercase()) {
+        index.search_runes(&prefix, limit, |entry, outputs| {
+          (
+            Some(entry.spaced_rune.rune) != exact,
+            !entry.spaced_rune.rune.to_string().starts_with(&prefix),
+            Reverse(match sort {
+              api::RuneSort::Mints => entry.mints,
+              api::RuneSort::Number => 0,
+              api::RuneSort::Outputs => u128::from(outputs),
+            }),
+            entry.number,
+          )
+        })?
+      } else {
+        (Vec::new(), false)
+      };
+
+      let search = RuneSearchHtml {
+        more,
+        query,
+        results: runes
+          .into_iter()
+          .map(|(id, entry, outputs)| api::RuneSearchResult {
+            id,
+            mints: entry.mints,
+            number: entry.number,
+            outputs,
+            spaced_rune: entry.spaced_rune,
+            symbol: entry.symbol,
+          })
+          .collect(),
+        sort,
+      };
+
+      Ok(if accept_json {
+        Json(search).into_response()
+      } else {
+        search.page(server_config).into_response()
+      })
+    })
+  }
+
+  async fn runes_balances(
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      Ok(if accept_json {
+        Json(
+          index
+            .get_rune_balance_map()?
+            .into_iter()
+            .map(|(rune, balances)| {
+              (
+                rune,
+                balances
+                  .into_iter()
                   .map(|(outpoint, pile)| (outpoint, pile.amount))
                   .collect(),
               )
@@ -788,7 +1451,9 @@ impl Server {
     task::block_in_place(|| {
       Ok(
         HomeHtml {
-          inscriptions: index.get_home_inscriptions()?,
+          inscriptions: server_config
+            .moderation
+            .filter(&index, index.get_home_inscriptions()?)?,
         }
         .page(server_config),
       )
@@ -807,7 +1472,10 @@ impl Server {
         let (inscriptions, _total_num) =
           index.get_highest_paying_inscriptions_in_block(*height, 8)?;
 
-        featured_blocks.insert(*hash, inscriptions);
+        featured_blocks.insert(
+          *hash,
+          server_config.moderation.filter(&index, inscriptions)?,
+        );
       }
 
       Ok(if accept_json {
@@ -831,39 +1499,155 @@ impl Server {
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      if !index.has_address_index() {
-        return Err(ServerError::NotFound(
-          "this server has no address index".to_string(),
-        ));
+      let (address, outputs) = Self::address_outputs(&server_config, &index, address)?;
+
+      if accept_json {
+        Ok(Json(outputs).into_response())
+      } else {
+        Self::address_info(server_config, &index, address, outputs, 0, false)
       }
+    })
+  }
 
-      let address = address
-        .require_network(server_config.chain.network())
-        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+  async fn address_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((address, page)): Path<(Address<NetworkUnchecked>, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (address, outputs) = Self::address_outputs(&server_config, &index, address)?;
 
-      let mut outputs = index.get_address_info(&address)?;
+      Self::address_info(server_config, &index, address, outputs, page, accept_json)
+    })
+  }
 
-      outputs.sort();
+  // Balances, rarities, and transaction count are for the whole address,
+  // while outputs, and the inscriptions on them, are paginated.
+  fn address_info(
+    server_config: Arc<ServerConfig>,
+    index: &Index,
+    address: Address,
+    outputs: Vec<OutPoint>,
+    page: usize,
+    accept_json: bool,
+  ) -> ServerResult {
+    let sat_balance = index.get_sat_balances_for_outputs(&outputs)?;
 
-      let sat_balance = index.get_sat_balances_for_outputs(&outputs)?;
+    let rarities = index.get_sat_rarities_for_outputs(&outputs)?;
 
-      let inscriptions = index.get_inscriptions_for_outputs(&outputs)?;
+    let runes_balances = if index.has_rune_index() {
+      Some(index.get_aggregated_rune_balances_for_outputs(&outputs)?)
+    } else {
+      None
+    };
 
-      let runes_balances = index.get_aggregated_rune_balances_for_outputs(&outputs)?;
+    let transactions = index.get_address_transaction_count(&address)?;
 
-      Ok(if accept_json {
-        Json(outputs).into_response()
-      } else {
-        AddressHtml {
-          address,
-          outputs,
-          inscriptions,
-          sat_balance,
-          runes_balances,
-        }
-        .page(server_config)
-        .into_response()
+    let start = page.saturating_mul(100);
+
+    let more = outputs.len() > start.saturating_add(100);
+
+    let outputs = outputs
+      .into_iter()
+      .skip(start)
+      .take(100)
+      .collect::<Vec<OutPoint>>();
+
+    let inscriptions = index.get_inscriptions_for_outputs(&outputs)?;
+
+    Ok(if accept_json {
+      Json(api::AddressInfo {
+        inscriptions,
+        more,
+        outputs,
+        page,
+        rarities,
+        runes_balances: runes_balances.map(|balances| {
+          balances
+            .into_iter()
+            .map(|(spaced_rune, decimal, symbol)| {
+              (
+                spaced_rune,
+                Pile {
+                  amount: decimal.value,
+                  divisibility: decimal.scale,
+                  symbol,
+                },
+              )
+            })
+            .collect()
+        }),
+        sat_balance,
+        transactions,
       })
+      .into_response()
+    } else {
+      AddressHtml {
+        address,
+        outputs,
+        inscriptions,
+        sat_balance,
+        runes_balances: runes_balances.unwrap_or_default(),
+        rarities,
+        transactions,
+        prev: page.checked_sub(1),
+        next: more.then_some(page + 1),
+      }
+      .page(server_config)
+      .into_response()
+    })
+  }
+
+  async fn admin_hidden(Extension(server_config): Extension<Arc<ServerConfig>>) -> Json<Hidden> {
+    Json(server_config.moderation.hidden())
+  }
+
+  async fn admin_hide(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Json(hidden): Json<Hidden>,
+  ) -> ServerResult<Json<Hidden>> {
+    task::block_in_place(|| Ok(Json(server_config.moderation.hide(hidden)?)))
+  }
+
+  async fn admin_unhide(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Json(hidden): Json<Hidden>,
+  ) -> ServerResult<Json<Hidden>> {
+    task::block_in_place(|| Ok(Json(server_config.moderation.unhide(&hidden)?)))
+  }
+
+  fn address_outputs(
+    server_config: &ServerConfig,
+    index: &Index,
+    address: Address<NetworkUnchecked>,
+  ) -> ServerResult<(Address, Vec<OutPoint>)> {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let address = address
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let mut outputs = index.get_address_info(&address)?;
+
+    outputs.sort();
+
+    Ok((address, outputs))
+  }
+
+  async fn addresses_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(queries): Json<Vec<BatchQuery>>,
+  ) -> ServerResult {
+    Self::batch(&server_config, accept_json, queries, |query| {
+      Self::address_outputs(&server_config, &index, query.parse("address")?)
+        .map(|(_address, outputs)| outputs)
     })
   }
 
@@ -872,90 +1656,252 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Block>>,
     AcceptJson(accept_json): AcceptJson,
+    conditional: Conditional,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (block, height) = match query {
-        query::Block::Height(height) => {
-          let block = index
-            .get_block_by_height(height)?
-            .ok_or_not_found(|| format!("block {height}"))?;
-
-          (block, height)
-        }
-        query::Block::Hash(hash) => {
-          let info = index
-            .block_header_info(hash)?
-            .ok_or_not_found(|| format!("block {hash}"))?;
+      let (block, height) = Self::block_by_query(&index, query)?;
 
-          let block = index
-            .get_block_by_hash(hash)?
-            .ok_or_not_found(|| format!("block {hash}"))?;
+      let index_height = Self::index_height(&index)?;
 
-          (block, u32::try_from(info.height).unwrap())
-        }
-      };
+      // a block never changes, but what is shown alongside it depends on the
+      // chain tip, like the link to the next block, and the runes and
+      // inscriptions of a block that has been reorged out
+      let validator = Validator::new(
+        accept_json,
+        &server_config.theme,
+        (block.block_hash(), index.block_hash(None)?),
+      );
 
-      let runes = index.get_runes_in_block(u64::from(height))?;
-      Ok(if accept_json {
-        let inscriptions = index.get_inscriptions_in_block(height)?;
-        Json(api::Block::new(
-          block,
-          Height(height),
-          Self::index_height(&index)?,
-          inscriptions,
-          runes,
-        ))
-        .into_response()
-      } else {
-        let (featured_inscriptions, total_num) =
-          index.get_highest_paying_inscriptions_in_block(height, 8)?;
-        BlockHtml::new(
-          block,
-          Height(height),
-          Self::index_height(&index)?,
-          total_num,
-          featured_inscriptions,
-          runes,
-        )
-        .page(server_config)
-        .into_response()
+      conditional.respond(validator, || {
+        let runes = index.get_runes_in_block(u64::from(height))?;
+        Ok(if accept_json {
+          let inscriptions = index.get_inscriptions_in_block(height)?;
+          Json(api::Block::new(
+            block,
+            Height(height),
+            index_height,
+            inscriptions,
+            runes,
+          ))
+          .into_response()
+        } else {
+          let (featured_inscriptions, total_num) =
+            index.get_highest_paying_inscriptions_in_block(height, 8)?;
+          BlockHtml::new(
+            block,
+            Height(height),
+            index_height,
+            total_num,
+            featured_inscriptions,
+            runes,
+          )
+          .page(server_config)
+          .into_response()
+        })
       })
     })
   }
 
+  fn block_by_query(index: &Index, query: query::Block) -> ServerResult<(bitcoin::Block, u32)> {
+    match query {
+      query::Block::Height(height) => {
+        let block = index
+          .get_block_by_height(height)?
+          .ok_or_not_found(|| format!("block {height}"))?;
+
+        Ok((block, height))
+      }
+      query::Block::Hash(hash) => {
+        let height = index
+          .block_height_by_hash(hash)?
+          .ok_or_not_found(|| format!("block {hash}"))?;
+
+        let block = index
+          .get_block_by_hash(hash)?
+          .ok_or_not_found(|| format!("block {hash}"))?;
+
+        Ok((block, height))
+      }
+    }
+  }
+
+  async fn blocks_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(queries): Json<Vec<BatchQuery>>,
+  ) -> ServerResult {
+    Self::batch(&server_config, accept_json, queries, |query| {
+      let (block, height) = Self::block_by_query(&index, query.parse("block")?)?;
+
+      Ok(api::Block::new(
+        block,
+        Height(height),
+        Self::index_height(&index)?,
+        index.get_inscriptions_in_block(height)?,
+        index.get_runes_in_block(u64::from(height))?,
+      ))
+    })
+  }
+
   async fn transaction(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(txid): Path<Txid>,
     AcceptJson(accept_json): AcceptJson,
+    conditional: Conditional,
   ) -> ServerResult {
     task::block_in_place(|| {
       let transaction = index
         .get_transaction(txid)?
         .ok_or_not_found(|| format!("transaction {txid}"))?;
 
-      let inscription_count = index.inscription_count(txid)?;
-
-      Ok(if accept_json {
-        Json(api::Transaction {
-          chain: server_config.chain,
-          etching: index.get_etching(txid)?,
-          inscription_count,
-          transaction,
+      // the sat flow is only computed for fresh responses, since it looks up
+      // every input, and changes when the transaction's outputs are spent,
+      // which can only happen in a new block
+      let validator = Validator::new(
+        accept_json,
+        &server_config.theme,
+        (
           txid,
+          index.get_etching(txid)?,
+          index.inscription_count(txid)?,
+          index.block_hash(None)?,
+        ),
+      );
+
+      conditional.respond(validator, || {
+        let transaction = Self::transaction_info(&server_config, &index, txid, transaction)?;
+
+        Ok(if accept_json {
+          Json(transaction).into_response()
+        } else {
+          transaction.page(server_config).into_response()
         })
-        .into_response()
-      } else {
-        TransactionHtml {
-          chain: server_config.chain,
-          etching: index.get_etching(txid)?,
-          inscription_count,
-          transaction,
+      })
+    })
+  }
+
+  fn transaction_info(
+    server_config: &ServerConfig,
+    index: &Index,
+    txid: Txid,
+    transaction: Transaction,
+  ) -> ServerResult<TransactionHtml> {
+    Ok(TransactionHtml {
+      chain: server_config.chain,
+      etching: index.get_etching(txid)?,
+      inscription_count: index.inscription_count(txid)?,
+      sat_flow: Self::sat_flow(index, txid, &transaction)?,
+      transaction,
+      txid,
+    })
+  }
+
+  fn sat_flow(
+    index: &Index,
+    txid: Txid,
+    transaction: &Transaction,
+  ) -> Result<Option<api::SatFlow>> {
+    // sat ranges, inscriptions, and rune balances are only known for unspent
+    // outputs, so once an output is spent they are omitted from the flow
+    let mut spent = false;
+    for (vout, output) in transaction.output.iter().enumerate() {
+      if !output.script_pubkey.is_op_return()
+        && index.is_output_spent(OutPoint {
           txid,
+          vout: vout.try_into().unwrap(),
+        })?
+      {
+        spent = true;
+        break;
+      }
+    }
+
+    let inputs = if transaction.is_coin_base() {
+      vec![transaction.output.iter().map(|output| output.value).sum()]
+    } else {
+      let mut inputs = Vec::new();
+
+      for input in &transaction.input {
+        let previous_output = input.previous_output;
+
+        let Some(value) = index
+          .get_transaction(previous_output.txid)?
+          .and_then(|transaction| {
+            transaction
+              .output
+              .into_iter()
+              .nth(previous_output.vout.try_into().unwrap())
+          })
+          .map(|output| output.value)
+        else {
+          return Ok(None);
+        };
+
+        inputs.push(value);
+      }
+
+      inputs
+    };
+
+    let mut outputs = Vec::new();
+    let mut inscriptions = Vec::new();
+    let mut runes = Vec::new();
+
+    for (vout, output) in transaction.output.iter().enumerate() {
+      let vout = u32::try_from(vout).unwrap();
+      let outpoint = OutPoint { txid, vout };
+
+      outputs.push((
+        output.value,
+        if index.has_sat_index() && !spent {
+          index.list(outpoint)?
+        } else {
+          None
+        },
+      ));
+
+      if spent {
+        continue;
+      }
+
+      inscriptions.extend(index.get_inscriptions_on_output_with_satpoints(outpoint)?);
+
+      if index.has_rune_index() {
+        for (rune, amount) in index.get_rune_balances_for_output(outpoint)? {
+          runes.push(api::SatFlowRune {
+            amount,
+            output: vout,
+            rune,
+          });
         }
-        .page(server_config)
-        .into_response()
-      })
+      }
+    }
+
+    Ok(Some(api::SatFlow::new(
+      txid,
+      inputs,
+      outputs,
+      (!spent).then_some(inscriptions),
+      (!spent).then_some(runes),
+    )))
+  }
+
+  async fn transactions_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(queries): Json<Vec<BatchQuery>>,
+  ) -> ServerResult {
+    Self::batch(&server_config, accept_json, queries, |query| {
+      let txid = query.parse("transaction ID")?;
+
+      let transaction = index
+        .get_transaction(txid)?
+        .ok_or_not_found(|| format!("transaction {txid}"))?;
+
+      Self::transaction_info(&server_config, &index, txid, transaction)
     })
   }
 
@@ -984,6 +1930,124 @@ impl Server {
     })
   }
 
+  async fn decode_json(
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(encoded): Json<String>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let (transaction, prevouts) = Self::decode_transaction_or_psbt(&encoded)?;
+
+      // input values are taken from the index, since a PSBT's values could
+      // misstate the fee and where sats and inscriptions end up
+      let mut input_values = Vec::new();
+      for (input, prevout) in transaction.input.iter().zip(prevouts) {
+        let value = index
+          .get_transaction(input.previous_output.txid)?
+          .and_then(|tx| {
+            tx.output
+              .into_iter()
+              .nth(input.previous_output.vout.into_usize())
+          })
+          .map(|tx_out| tx_out.value)
+          .ok_or_else(|| {
+            ServerError::BadRequest(format!("input {} not found", input.previous_output))
+          })?;
+
+        if let Some(prevout) = prevout {
+          if prevout.value != value {
+            return Err(ServerError::BadRequest(format!(
+              "PSBT value of input {} is {}, but output has value {value}",
+              input.previous_output, prevout.value,
+            )));
+          }
+        }
+
+        input_values.push(value);
+      }
+
+      let total_input_value = input_values
+        .iter()
+        .try_fold(0u64, |total, value| total.checked_add(*value))
+        .ok_or_else(|| ServerError::BadRequest("transaction input value overflows".into()))?;
+
+      let total_output_value = transaction
+        .output
+        .iter()
+        .try_fold(0u64, |total, tx_out| total.checked_add(tx_out.value))
+        .ok_or_else(|| ServerError::BadRequest("transaction output value overflows".into()))?;
+
+      if total_input_value < total_output_value {
+        return Err(ServerError::BadRequest(
+          "transaction output value exceeds input value".into(),
+        ));
+      }
+
+      let simulation = index.simulate_transaction(&transaction, &input_values)?;
+
+      Ok(
+        Json(api::DecodePreview {
+          inscriptions: ParsedEnvelope::from_transaction(&transaction),
+          runestone: Runestone::decipher(&transaction),
+          simulation,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  /// Decode a hex-encoded transaction or PSBT, or a base64-encoded PSBT,
+  /// returning the transaction and, for a PSBT, the outputs its inputs spend,
+  /// if included.
+  fn decode_transaction_or_psbt(encoded: &str) -> ServerResult<(Transaction, Vec<Option<TxOut>>)> {
+    let encoded = encoded.trim();
+
+    let bytes = if encoded.len() % 2 == 0 && encoded.bytes().all(|c| c.is_ascii_hexdigit()) {
+      hex::decode(encoded).map_err(|err| ServerError::BadRequest(err.to_string()))?
+    } else {
+      base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|err| {
+          ServerError::BadRequest(format!(
+            "expected hex-encoded transaction or PSBT, or base64-encoded PSBT: {err}"
+          ))
+        })?
+    };
+
+    if bytes.starts_with(b"psbt\xff") {
+      let psbt = Psbt::deserialize(&bytes)
+        .map_err(|err| ServerError::BadRequest(format!("invalid PSBT: {err}")))?;
+
+      let prevouts = psbt
+        .inputs
+        .iter()
+        .zip(&psbt.unsigned_tx.input)
+        .map(|(input, txin)| {
+          input.witness_utxo.clone().or_else(|| {
+            input.non_witness_utxo.as_ref().and_then(|tx| {
+              tx.output
+                .get(txin.previous_output.vout.into_usize())
+                .cloned()
+            })
+          })
+        })
+        .collect();
+
+      Ok((psbt.extract_tx(), prevouts))
+    } else {
+      let transaction = consensus::encode::deserialize::<Transaction>(&bytes)
+        .map_err(|err| ServerError::BadRequest(format!("invalid transaction: {err}")))?;
+
+      let prevouts = vec![None; transaction.input.len()];
+
+      Ok((transaction, prevouts))
+    }
+  }
+
   async fn update(
     Extension(settings): Extension<Arc<Settings>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1015,6 +2079,15 @@ impl Server {
         };
       };
 
+      if server_config
+        .moderation
+        .is_hidden(&index, inscription_id, &inscription)?
+      {
+        return Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} not found"
+        )));
+      }
+
       let metadata = inscription
         .metadata
         .ok_or_not_found(|| format!("inscription {inscription_id} metadata"))?;
@@ -1040,6 +2113,15 @@ impl Server {
         };
       };
 
+      if server_config
+        .moderation
+        .is_hidden(&index, inscription_id, &inscription)?
+      {
+        return Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} not found"
+        )));
+      }
+
       let entry = index
         .get_inscription_entry(inscription_id)
         .unwrap()
@@ -1135,7 +2217,19 @@ impl Server {
       } else if re::INSCRIPTION_ID.is_match(query) || re::INSCRIPTION_NUMBER.is_match(query) {
         Ok(Redirect::to(&format!("/inscription/{query}")))
       } else if re::SPACED_RUNE.is_match(query) {
-        Ok(Redirect::to(&format!("/rune/{query}")))
+        let exists = match query.parse::<SpacedRune>() {
+          Ok(spaced_rune) => index.rune(spaced_rune.rune)?.is_some(),
+          Err(_) => false,
+        };
+
+        if exists || !index.has_rune_index() {
+          Ok(Redirect::to(&format!("/rune/{query}")))
+        } else {
+          Ok(Redirect::to(&format!(
+            "/runes/search?q={}",
+            urlencoding::encode(query)
+          )))
+        }
       } else if re::RUNE_ID.is_match(query) {
         let id = query
           .parse::<RuneId>()
@@ -1152,9 +2246,9 @@ impl Server {
     })
   }
 
-  async fn favicon() -> ServerResult {
+  async fn favicon(server_config: Extension<Arc<ServerConfig>>) -> ServerResult {
     Ok(
-      Self::static_asset(Path("/favicon.png".to_string()))
+      Self::static_asset(server_config, Path("/favicon.png".to_string()))
         .await
         .into_response(),
     )
@@ -1176,6 +2270,10 @@ impl Server {
       builder.generator(Some("ord".to_string()));
 
       for (number, id) in index.get_feed_inscriptions(300)? {
+        if server_config.moderation.is_inscription_hidden(&index, id)? {
+          continue;
+        }
+
         builder.item(
           rss::ItemBuilder::default()
             .title(Some(format!("Inscription {number}")))
@@ -1204,15 +2302,22 @@ impl Server {
     })
   }
 
-  async fn static_asset(Path(path): Path<String>) -> ServerResult {
-    let content = StaticAssets::get(if let Some(stripped) = path.strip_prefix('/') {
-      stripped
-    } else {
-      &path
-    })
-    .ok_or_not_found(|| format!("asset {path}"))?;
-    let body = body::boxed(body::Full::from(content.data));
-    let mime = mime_guess::from_path(path).first_or_octet_stream();
+  async fn static_asset(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(path): Path<String>,
+  ) -> ServerResult {
+    let stripped = path.strip_prefix('/').unwrap_or(&path);
+
+    let content = match task::block_in_place(|| server_config.theme.static_asset(stripped))? {
+      Some(content) => content,
+      None => StaticAssets::get(stripped)
+        .ok_or_not_found(|| format!("asset {path}"))?
+        .data
+        .into_owned(),
+    };
+
+    let body = body::boxed(body::Full::from(content));
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
     Ok(
       Response::builder()
         .header(header::CONTENT_TYPE, mime.as_ref())
@@ -1363,6 +2468,25 @@ impl Server {
     })
   }
 
+  async fn graphql(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Json(request): Json<async_graphql::Request>,
+  ) -> ServerResult {
+    let schema = server_config
+      .graphql_schema
+      .clone()
+      .ok_or_else(|| ServerError::NotFound("GraphQL API disabled".into()))?;
+
+    let request = request.data(index).data(server_config);
+
+    // resolvers read from the index synchronously, so the query is executed
+    // on this thread instead of being polled by the runtime
+    let response = task::block_in_place(|| futures::executor::block_on(schema.execute(request)));
+
+    Ok(Json(response).into_response())
+  }
+
   async fn input(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1431,6 +2555,8 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
+    byte_ranges: ByteRanges,
+    conditional: Conditional,
   ) -> ServerResult {
     task::block_in_place(|| {
       if settings.is_hidden(inscription_id) {
@@ -1448,16 +2574,42 @@ impl Server {
         };
       };
 
+      if server_config
+        .moderation
+        .is_hidden(&index, inscription_id, &inscription)?
+      {
+        return Ok(PreviewUnknownHtml.into_response());
+      }
+
+      let mut content_id = inscription_id;
+
       if let Some(delegate) = inscription.delegate() {
         inscription = index
           .get_inscription_by_id(delegate)?
-          .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+          .ok_or_not_found(|| format!("delegate {inscription_id}"))?;
+
+        if server_config
+          .moderation
+          .is_hidden(&index, delegate, &inscription)?
+        {
+          return Ok(PreviewUnknownHtml.into_response());
+        }
+
+        content_id = delegate;
       }
 
-      Ok(
-        Self::content_response(inscription, accept_encoding, &server_config)?
-          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-          .into_response(),
+      let entry = index
+        .get_inscription_entry(content_id)?
+        .ok_or_not_found(|| format!("inscription {content_id}"))?;
+
+      let (headers, body) = Self::content_response(inscription, accept_encoding, &server_config)?
+        .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+      let etag = body.etag(content_id);
+
+      conditional.respond(
+        Validator::immutable(etag.clone(), timestamp(entry.timestamp.into())),
+        || Ok(body.into_response(headers, &etag, &byte_ranges)),
       )
     })
   }
@@ -1466,7 +2618,7 @@ impl Server {
     inscription: Inscription,
     accept_encoding: AcceptEncoding,
     server_config: &ServerConfig,
-  ) -> ServerResult<Option<(HeaderMap, Vec<u8>)>> {
+  ) -> ServerResult<Option<(HeaderMap, ContentBody)>> {
     let mut headers = HeaderMap::new();
 
     match &server_config.csp_origin {
@@ -1505,18 +2657,19 @@ impl Server {
     if let Some(content_encoding) = inscription.content_encoding() {
       if accept_encoding.is_acceptable(&content_encoding) {
         headers.insert(header::CONTENT_ENCODING, content_encoding);
-      } else if server_config.decompress && content_encoding == "br" {
-        let Some(body) = inscription.into_body() else {
-          return Ok(None);
-        };
-
-        let mut decompressed = Vec::new();
-
-        Decompressor::new(body.as_slice(), 4096)
-          .read_to_end(&mut decompressed)
-          .map_err(|err| ServerError::Internal(err.into()))?;
-
-        return Ok(Some((headers, decompressed)));
+      } else if let Some(compression) = content_encoding
+        .to_str()
+        .ok()
+        .and_then(Compression::from_content_encoding)
+        .filter(|_| server_config.decompress)
+      {
+        // the compression layer re-encodes the decompressed body with an
+        // encoding the client accepts, if any
+        return Ok(
+          inscription
+            .into_body()
+            .map(|body| (headers, ContentBody::Compressed(compression, body))),
+        );
       } else {
         return Err(ServerError::NotAcceptable {
           accept_encoding,
@@ -1525,11 +2678,11 @@ impl Server {
       }
     }
 
-    let Some(body) = inscription.into_body() else {
-      return Ok(None);
-    };
-
-    Ok(Some((headers, body)))
+    Ok(
+      inscription
+        .into_body()
+        .map(|body| (headers, ContentBody::Bytes(body))),
+    )
   }
 
   async fn preview(
@@ -1538,6 +2691,7 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path(inscription_id): Path<InscriptionId>,
     accept_encoding: AcceptEncoding,
+    byte_ranges: ByteRanges,
   ) -> ServerResult {
     task::block_in_place(|| {
       if settings.is_hidden(inscription_id) {
@@ -1548,20 +2702,39 @@ impl Server {
         .get_inscription_by_id(inscription_id)?
         .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
 
+      if server_config
+        .moderation
+        .is_hidden(&index, inscription_id, &inscription)?
+      {
+        return Ok(PreviewUnknownHtml.into_response());
+      }
+
+      let mut content_id = inscription_id;
+
       if let Some(delegate) = inscription.delegate() {
         inscription = index
           .get_inscription_by_id(delegate)?
-          .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+          .ok_or_not_found(|| format!("delegate {inscription_id}"))?;
+
+        if server_config
+          .moderation
+          .is_hidden(&index, delegate, &inscription)?
+        {
+          return Ok(PreviewUnknownHtml.into_response());
+        }
+
+        content_id = delegate;
       }
 
       let media = inscription.media();
 
       if let Media::Iframe = media {
-        return Ok(
-          Self::content_response(inscription, accept_encoding, &server_config)?
-            .ok_or_not_found(|| format!("inscription {inscription_id} content"))?
-            .into_response(),
-        );
+        let (headers, body) = Self::content_response(inscription, accept_encoding, &server_config)?
+          .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+        let etag = body.etag(content_id);
+
+        return Ok(body.into_response(headers, &etag, &byte_ranges));
       }
 
       let content_security_policy = server_config.preview_content_security_policy(media)?;
@@ -1580,6 +2753,9 @@ impl Server {
           )
             .into_response(),
         ),
+        Media::Csv => {
+          Ok((content_security_policy, PreviewCsvHtml { inscription_id }).into_response())
+        }
         Media::Font => {
           Ok((content_security_policy, PreviewFontHtml { inscription_id }).into_response())
         }
@@ -1594,6 +2770,9 @@ impl Server {
           )
             .into_response(),
         ),
+        Media::Json => {
+          Ok((content_security_policy, PreviewJsonHtml { inscription_id }).into_response())
+        }
         Media::Markdown => Ok(
           (
             content_security_policy,
@@ -1601,12 +2780,18 @@ impl Server {
           )
             .into_response(),
         ),
+        Media::Midi => {
+          Ok((content_security_policy, PreviewMidiHtml { inscription_id }).into_response())
+        }
         Media::Model => {
           Ok((content_security_policy, PreviewModelHtml { inscription_id }).into_response())
         }
         Media::Pdf => {
           Ok((content_security_policy, PreviewPdfHtml { inscription_id }).into_response())
         }
+        Media::Stl => {
+          Ok((content_security_policy, PreviewStlHtml { inscription_id }).into_response())
+        }
         Media::Text => {
           Ok((content_security_policy, PreviewTextHtml { inscription_id }).into_response())
         }
@@ -1618,28 +2803,178 @@ impl Server {
     })
   }
 
+  async fn thumbnail(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Path(inscription_id): Path<InscriptionId>,
+    Query(query): Query<ThumbnailQuery>,
+    conditional: Conditional,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let no_thumbnail = || {
+        Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} has no thumbnail"
+        )))
+      };
+
+      if settings.is_hidden(inscription_id) {
+        return no_thumbnail();
+      }
+
+      let size = query.size.unwrap_or(thumbnail::DEFAULT_SIZE);
+
+      // sizes are rounded up to one of a few sizes, so that at most that many
+      // thumbnails are rendered and cached for each inscription
+      let Some(size) = (size > 0)
+        .then(|| {
+          thumbnail::SIZES
+            .into_iter()
+            .find(|&quantized| quantized >= size)
+        })
+        .flatten()
+      else {
+        return Err(ServerError::BadRequest(format!(
+          "thumbnail size must be between 1 and {}",
+          thumbnail::MAX_SIZE
+        )));
+      };
+
+      let mut inscription = index
+        .get_inscription_by_id(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+      if server_config
+        .moderation
+        .is_hidden(&index, inscription_id, &inscription)?
+      {
+        return no_thumbnail();
+      }
+
+      let mut content_id = inscription_id;
+
+      if let Some(delegate) = inscription.delegate() {
+        inscription = index
+          .get_inscription_by_id(delegate)?
+          .ok_or_not_found(|| format!("delegate {inscription_id}"))?;
+
+        if server_config
+          .moderation
+          .is_hidden(&index, delegate, &inscription)?
+        {
+          return no_thumbnail();
+        }
+
+        content_id = delegate;
+      }
+
+      let image_rendering = match inscription.media() {
+        Media::Image(image_rendering) => image_rendering,
+        Media::Iframe
+          if inscription
+            .content_type()
+            .is_some_and(|content_type| content_type.starts_with("image/svg+xml")) =>
+        {
+          ImageRendering::Auto
+        }
+        _ => return no_thumbnail(),
+      };
+
+      // without `--decompress`, compressed content is only served as is
+      if inscription.content_encoding().is_some() && !server_config.decompress {
+        return Ok(Redirect::to(&format!("/content/{inscription_id}")).into_response());
+      }
+
+      let entry = index
+        .get_inscription_entry(content_id)?
+        .ok_or_not_found(|| format!("inscription {content_id}"))?;
+
+      let render = || {
+        let mut body = inscription.body().unwrap_or_default().to_vec();
+
+        if let Some(content_encoding) = inscription.content_encoding() {
+          body = content_encoding
+            .to_str()
+            .ok()
+            .and_then(Compression::from_content_encoding)
+            .ok_or_else(|| anyhow!("unknown content encoding {content_encoding:?}"))?
+            .decompress_with_limit(&body, thumbnail::MAX_BODY_SIZE)?;
+        }
+
+        thumbnail::render(
+          inscription.content_type().unwrap_or_default(),
+          &body,
+          image_rendering,
+          size,
+        )
+      };
+
+      let thumbnail = match &server_config.thumbnail_cache {
+        Some(cache) => cache.get_or_render(content_id, size, render),
+        None => render(),
+      };
+
+      // content that cannot be rendered, such as image formats without a
+      // decoder, is served as is
+      let thumbnail = match thumbnail {
+        Ok(thumbnail) => thumbnail,
+        Err(err) => {
+          log::debug!("failed to render thumbnail for inscription {content_id}: {err}");
+          return Ok(Redirect::to(&format!("/content/{inscription_id}")).into_response());
+        }
+      };
+
+      conditional.respond(
+        Validator::immutable(
+          format!("\"{}\"", sha256::Hash::hash(&thumbnail)),
+          timestamp(entry.timestamp.into()),
+        ),
+        || {
+          Ok(
+            (
+              [(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))],
+              thumbnail,
+            )
+              .into_response(),
+          )
+        },
+      )
+    })
+  }
+
   async fn inscription(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
+    conditional: Conditional,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
   ) -> ServerResult {
-    Self::inscription_inner(server_config, &index, accept_json, query, None).await
+    Self::inscription_inner(server_config, &index, accept_json, conditional, query, None).await
   }
 
   async fn inscription_child(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
+    conditional: Conditional,
     Path((DeserializeFromStr(query), child)): Path<(DeserializeFromStr<query::Inscription>, usize)>,
   ) -> ServerResult {
-    Self::inscription_inner(server_config, &index, accept_json, query, Some(child)).await
+    Self::inscription_inner(
+      server_config,
+      &index,
+      accept_json,
+      conditional,
+      query,
+      Some(child),
+    )
+    .await
   }
 
   async fn inscription_inner(
     server_config: Arc<ServerConfig>,
     index: &Index,
     accept_json: bool,
+    conditional: Conditional,
     query: query::Inscription,
     child: Option<usize>,
   ) -> ServerResult {
@@ -1654,37 +2989,45 @@ impl Server {
         .inscription_info(query, child)?
         .ok_or_not_found(|| format!("inscription {query}"))?;
 
-      Ok(if accept_json {
-        Json(info).into_response()
-      } else {
-        InscriptionHtml {
-          chain: server_config.chain,
-          charms: Charm::Vindicated.unset(info.charms.iter().fold(0, |mut acc, charm| {
-            charm.set(&mut acc);
-            acc
-          })),
-          children: info.children,
-          fee: info.fee,
-          height: info.height,
-          inscription,
-          id: info.id,
-          number: info.number,
-          next: info.next,
-          output: txout,
-          parents: info.parents,
-          previous: info.previous,
-          rune: info.rune,
-          sat: info.sat,
-          satpoint: info.satpoint,
-          timestamp: Utc.timestamp_opt(info.timestamp, 0).unwrap(),
-        }
-        .page(server_config)
-        .into_response()
-      })
+      // the inscription info covers everything about an inscription that can
+      // change, like its location, children, and the inscription after it
+      conditional.respond(
+        Validator::new(accept_json, &server_config.theme, &info),
+        || {
+          Ok(if accept_json {
+            Json(info).into_response()
+          } else {
+            InscriptionHtml {
+              chain: server_config.chain,
+              charms: Charm::Vindicated.unset(info.charms.iter().fold(0, |mut acc, charm| {
+                charm.set(&mut acc);
+                acc
+              })),
+              children: info.children,
+              fee: info.fee,
+              height: info.height,
+              inscription,
+              id: info.id,
+              number: info.number,
+              next: info.next,
+              output: txout,
+              parents: info.parents,
+              previous: info.previous,
+              rune: info.rune,
+              sat: info.sat,
+              satpoint: info.satpoint,
+              timestamp: Utc.timestamp_opt(info.timestamp, 0).unwrap(),
+            }
+            .page(server_config)
+            .into_response()
+          })
+        },
+      )
     })
   }
 
   async fn inscriptions_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
     Json(inscriptions): Json<Vec<InscriptionId>>,
@@ -1694,6 +3037,16 @@ impl Server {
         let mut response = Vec::new();
         for inscription in inscriptions {
           let query = query::Inscription::Id(inscription);
+
+          if server_config
+            .moderation
+            .is_inscription_hidden(&index, inscription)?
+          {
+            return Err(ServerError::NotFound(format!(
+              "inscription {query} not found"
+            )));
+          }
+
           let (info, _, _) = index
             .inscription_info(query, None)?
             .ok_or_not_found(|| format!("inscription {query}"))?;
@@ -1708,34 +3061,209 @@ impl Server {
     })
   }
 
+  async fn inscriptions_by_number_json(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Json(numbers): Json<Vec<i32>>,
+  ) -> ServerResult {
+    Self::batch(&server_config, accept_json, numbers, |number| {
+      let query = query::Inscription::Number(number);
+
+      let (info, _, _) = index
+        .inscription_info(query, None)?
+        .ok_or_not_found(|| format!("inscription {query}"))?;
+
+      if server_config
+        .moderation
+        .is_inscription_hidden(&index, info.id)?
+      {
+        return Err(ServerError::NotFound(format!(
+          "inscription {query} not found"
+        )));
+      }
+
+      Ok(info)
+    })
+  }
+
+  // Looks up each item of a batch request, reporting items which are not
+  // found or invalid individually instead of failing the whole request.
+  fn batch<Q, T: Serialize>(
+    server_config: &ServerConfig,
+    accept_json: bool,
+    queries: Vec<Q>,
+    mut lookup: impl FnMut(Q) -> ServerResult<T>,
+  ) -> ServerResult {
+    if !accept_json {
+      return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    if queries.len() > server_config.max_batch_size {
+      return Err(ServerError::BadRequest(format!(
+        "batch requests may contain at most {} items",
+        server_config.max_batch_size
+      )));
+    }
+
+    task::block_in_place(|| {
+      let mut results = Vec::new();
+
+      for query in queries {
+        results.push(match lookup(query) {
+          Ok(item) => api::BatchResult::Ok(item),
+          Err(ServerError::BadRequest(message) | ServerError::NotFound(message)) => {
+            api::BatchResult::Error(message)
+          }
+          Err(err) => return Err(err),
+        });
+      }
+
+      Ok(Json(results).into_response())
+    })
+  }
+
   async fn collections(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    accept_json: AcceptJson,
+    query: Query<PaginationQuery>,
   ) -> ServerResult {
-    Self::collections_paginated(Extension(server_config), Extension(index), Path(0)).await
+    Self::collections_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      accept_json,
+      query,
+    )
+    .await
   }
 
   async fn collections_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<usize>,
+    AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<PaginationQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (collections, more_collections) = index.get_collections_paginated(100, page_index)?;
-
-      let prev = page_index.checked_sub(1);
+      let mut page = index.get_collections_page(query.pagination(
+        &server_config,
+        accept_json,
+        page_index,
+        100,
+        Order::Asc,
+      )?)?;
 
-      let next = more_collections.then_some(page_index + 1);
+      page.items = server_config.moderation.filter(&index, page.items)?;
 
-      Ok(
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: page.items,
+          more: page.more,
+          page_index: u32::try_from(page_index)
+            .map_err(|_| anyhow!("page index {} out of range", page_index))?,
+          next_cursor: page.next,
+          prev_cursor: page.prev,
+        })
+        .into_response()
+      } else {
         CollectionsHtml {
-          inscriptions: collections,
-          prev,
-          next,
+          inscriptions: page.items,
+          prev: page_index.checked_sub(1),
+          next: page.more.then_some(page_index + 1),
         }
         .page(server_config)
-        .into_response(),
-      )
+        .into_response()
+      })
+    })
+  }
+
+  async fn collection(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(parent): Path<InscriptionId>,
+    accept_json: AcceptJson,
+    query: Query<BTreeMap<String, String>>,
+  ) -> ServerResult {
+    Self::collection_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((parent, 0)),
+      accept_json,
+      query,
+    )
+    .await
+  }
+
+  async fn collection_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((parent, page_index)): Path<(InscriptionId, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<BTreeMap<String, String>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let entry = index
+        .get_inscription_entry(parent)?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      // only `trait.<NAME>` parameters are filters, so other parameters, like
+      // `lang`, may be used alongside them
+      let filters = query
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.strip_prefix("trait.")?.into(), value)))
+        .collect::<BTreeMap<String, String>>();
+
+      let table =
+        server_config
+          .collections
+          .get_or_calculate(&index, parent, entry.sequence_number)?;
+
+      let mut visible = Vec::with_capacity(table.children.len());
+      for child in &table.children {
+        if !server_config
+          .moderation
+          .is_inscription_hidden(&index, child.id)?
+        {
+          visible.push(child);
+        }
+      }
+
+      let matching = visible
+        .iter()
+        .copied()
+        .filter(|child| {
+          filters
+            .iter()
+            .all(|(name, value)| child.traits.get(name) == Some(value))
+        })
+        .collect::<Vec<&api::CollectionChild>>();
+
+      let start = page_index.saturating_mul(COLLECTION_PAGE_SIZE);
+
+      let collection = CollectionHtml {
+        children: matching
+          .iter()
+          .skip(start)
+          .take(COLLECTION_PAGE_SIZE)
+          .map(|&child| child.clone())
+          .collect(),
+        filters,
+        matches: matching.len(),
+        more: matching.len() > start.saturating_add(COLLECTION_PAGE_SIZE),
+        page_index,
+        parent,
+        parent_number: entry.inscription_number,
+        total: visible.len(),
+        traits: table.traits.clone(),
+      };
+
+      Ok(if accept_json {
+        Json(collection).into_response()
+      } else {
+        collection.page(server_config).into_response()
+      })
     })
   }
 
@@ -1743,11 +3271,15 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(inscription_id): Path<InscriptionId>,
+    accept_json: AcceptJson,
+    query: Query<PaginationQuery>,
   ) -> ServerResult {
     Self::children_paginated(
       Extension(server_config),
       Extension(index),
       Path((inscription_id, 0)),
+      accept_json,
+      query,
     )
     .await
   }
@@ -1755,7 +3287,9 @@ impl Server {
   async fn children_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path((parent, page)): Path<(InscriptionId, usize)>,
+    Path((parent, page_index)): Path<(InscriptionId, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<PaginationQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
       let entry = index
@@ -1764,24 +3298,34 @@ impl Server {
 
       let parent_number = entry.inscription_number;
 
-      let (children, more_children) =
-        index.get_children_by_sequence_number_paginated(entry.sequence_number, 100, page)?;
-
-      let prev_page = page.checked_sub(1);
+      let mut page = index.get_children_page(
+        entry.sequence_number,
+        query.pagination(&server_config, accept_json, page_index, 100, Order::Asc)?,
+      )?;
 
-      let next_page = more_children.then_some(page + 1);
+      page.items = server_config.moderation.filter(&index, page.items)?;
 
-      Ok(
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: page.items,
+          more: page.more,
+          page_index: u32::try_from(page_index)
+            .map_err(|_| anyhow!("page index {} out of range", page_index))?,
+          next_cursor: page.next,
+          prev_cursor: page.prev,
+        })
+        .into_response()
+      } else {
         ChildrenHtml {
           parent,
           parent_number,
-          children,
-          prev_page,
-          next_page,
+          children: page.items,
+          prev_page: page_index.checked_sub(1),
+          next_page: page.more.then_some(page_index + 1),
         }
         .page(server_config)
-        .into_response(),
-      )
+        .into_response()
+      })
     })
   }
 
@@ -1820,18 +3364,27 @@ impl Server {
       let (ids, more) =
         index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
 
+      let ids = server_config.moderation.filter(&index, ids)?;
+
       Ok(Json(api::Children { ids, more, page }).into_response())
     })
   }
 
   async fn child_inscriptions_recursive(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult {
-    Self::child_inscriptions_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+    Self::child_inscriptions_recursive_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+    )
+    .await
   }
 
   async fn child_inscriptions_recursive_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path((parent, page)): Path<(InscriptionId, usize)>,
   ) -> ServerResult {
@@ -1844,7 +3397,9 @@ impl Server {
       let (ids, more) =
         index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
 
-      let children = ids
+      let children = server_config
+        .moderation
+        .filter(&index, ids)?
         .into_iter()
         .map(|inscription_id| {
           let entry = index
@@ -1887,12 +3442,14 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     accept_json: AcceptJson,
+    query: Query<PaginationQuery>,
   ) -> ServerResult {
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
       accept_json,
+      query,
     )
     .await
   }
@@ -1902,26 +3459,33 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<u32>,
     AcceptJson(accept_json): AcceptJson,
+    Query(query): Query<PaginationQuery>,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (inscriptions, more) = index.get_inscriptions_paginated(100, page_index)?;
+      let mut page = index.get_inscriptions_page(query.pagination(
+        &server_config,
+        accept_json,
+        page_index.into_usize(),
+        100,
+        Order::Desc,
+      )?)?;
 
-      let prev = page_index.checked_sub(1);
-
-      let next = more.then_some(page_index + 1);
+      page.items = server_config.moderation.filter(&index, page.items)?;
 
       Ok(if accept_json {
         Json(api::Inscriptions {
-          ids: inscriptions,
+          ids: page.items,
           page_index,
-          more,
+          more: page.more,
+          next_cursor: page.next,
+          prev_cursor: page.prev,
         })
         .into_response()
       } else {
         InscriptionsHtml {
-          inscriptions,
-          next,
-          prev,
+          inscriptions: page.items,
+          next: page.more.then_some(page_index + 1),
+          prev: page_index.checked_sub(1),
         }
         .page(server_config)
         .into_response()
@@ -1969,11 +3533,15 @@ impl Server {
         inscriptions.pop();
       }
 
+      let inscriptions = server_config.moderation.filter(&index, inscriptions)?;
+
       Ok(if accept_json {
         Json(api::Inscriptions {
           ids: inscriptions,
           page_index,
           more,
+          next_cursor: None,
+          prev_cursor: None,
         })
         .into_response()
       } else {
@@ -2015,6 +3583,8 @@ impl Server {
 
       let (parents, more) = index.get_parents_by_sequence_number_paginated(child.parents, page)?;
 
+      let parents = server_config.moderation.filter(&index, parents)?;
+
       let prev_page = page.checked_sub(1);
 
       let next_page = more.then_some(page + 1);
@@ -2034,13 +3604,20 @@ impl Server {
   }
 
   async fn parents_recursive(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult {
-    Self::parents_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+    Self::parents_recursive_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+    )
+    .await
   }
 
   async fn parents_recursive_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path((inscription_id, page)): Path<(InscriptionId, usize)>,
   ) -> ServerResult {
@@ -2051,6 +3628,8 @@ impl Server {
 
       let (ids, more) = index.get_parents_by_sequence_number_paginated(child.parents, page)?;
 
+      let ids = server_config.moderation.filter(&index, ids)?;
+
       let page_index =
         u32::try_from(page).map_err(|_| anyhow!("page index {} out of range", page))?;
 
@@ -2059,20 +3638,109 @@ impl Server {
           ids,
           more,
           page_index,
+          next_cursor: None,
+          prev_cursor: None,
         })
         .into_response(),
       )
     })
   }
 
+  async fn address_inscriptions_recursive(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+  ) -> ServerResult<Json<api::AddressInscriptions>> {
+    Self::address_inscriptions_recursive_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((address, 0)),
+    )
+    .await
+  }
+
+  async fn address_inscriptions_recursive_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((address, page)): Path<(Address<NetworkUnchecked>, usize)>,
+  ) -> ServerResult<Json<api::AddressInscriptions>> {
+    task::block_in_place(|| {
+      let (_address, outputs) = Self::address_outputs(&server_config, &index, address)?;
+
+      let inscriptions = index.get_inscriptions_for_outputs(&outputs)?;
+
+      let ids = server_config.moderation.filter(
+        &index,
+        inscriptions
+          .iter()
+          .skip(page.saturating_mul(100))
+          .take(100)
+          .copied()
+          .collect(),
+      )?;
+
+      let more = inscriptions.len() > page.saturating_add(1).saturating_mul(100);
+
+      Ok(Json(api::AddressInscriptions { ids, more, page }))
+    })
+  }
+
+  async fn rune_recursive(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(rune): Path<String>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let rune_query = rune
+        .parse::<query::Rune>()
+        .map_err(|err| ServerError::BadRequest(format!("invalid rune `{rune}`: {err}")))?;
+
+      match Self::rune_info(&index, rune_query) {
+        Ok(rune) => Ok(Json(api::RuneRecursive::from(rune)).into_response()),
+        Err(ServerError::NotFound(message)) => match server_config.proxy.as_ref() {
+          Some(proxy) => Self::proxy(proxy, &format!("r/rune/{rune}")),
+          None => Err(ServerError::NotFound(message)),
+        },
+        Err(err) => Err(err),
+      }
+    })
+  }
+
+  async fn utxo_recursive(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(outpoint): Path<OutPoint>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let Some((output, _txout)) = index.get_output_info(outpoint)? else {
+        return if let Some(proxy) = server_config.proxy.as_ref() {
+          Self::proxy(proxy, &format!("r/utxo/{outpoint}"))
+        } else {
+          Err(ServerError::NotFound(format!(
+            "output {outpoint} not found"
+          )))
+        };
+      };
+
+      let mut utxo = api::UtxoRecursive::from(output);
+
+      utxo.inscriptions = server_config.moderation.filter(&index, utxo.inscriptions)?;
+
+      Ok(Json(utxo).into_response())
+    })
+  }
+
   async fn sat_inscriptions(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(sat): Path<u64>,
   ) -> ServerResult<Json<api::SatInscriptions>> {
-    Self::sat_inscriptions_paginated(Extension(index), Path((sat, 0))).await
+    Self::sat_inscriptions_paginated(Extension(server_config), Extension(index), Path((sat, 0)))
+      .await
   }
 
   async fn sat_inscriptions_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path((sat, page)): Path<(u64, u64)>,
   ) -> ServerResult<Json<api::SatInscriptions>> {
@@ -2085,11 +3753,14 @@ impl Server {
 
       let (ids, more) = index.get_inscription_ids_by_sat_paginated(Sat(sat), 100, page)?;
 
+      let ids = server_config.moderation.filter(&index, ids)?;
+
       Ok(Json(api::SatInscriptions { ids, more, page }))
     })
   }
 
   async fn sat_inscription_at_index(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path((DeserializeFromStr(sat), inscription_index)): Path<(DeserializeFromStr<Sat>, isize)>,
   ) -> ServerResult<Json<api::SatInscription>> {
@@ -2100,7 +3771,10 @@ impl Server {
         ));
       }
 
-      let id = index.get_inscription_id_by_sat_indexed(sat, inscription_index)?;
+      let id = match index.get_inscription_id_by_sat_indexed(sat, inscription_index)? {
+        Some(id) if server_config.moderation.is_inscription_hidden(&index, id)? => None,
+        id => id,
+      };
 
       Ok(Json(api::SatInscription { id }))
     })
@@ -2121,7 +3795,8 @@ impl Server {
 #[cfg(test)]
 mod tests {
   use {
-    super::*, reqwest::Url, serde::de::DeserializeOwned, std::net::TcpListener, tempfile::TempDir,
+    super::*, image::GenericImageView, reqwest::Url, serde::de::DeserializeOwned,
+    std::net::TcpListener, tempfile::TempDir,
   };
 
   const RUNE: u128 = 99246114928149462;
@@ -2412,6 +4087,47 @@ mod tests {
       response.json().unwrap()
     }
 
+    #[track_caller]
+    fn graphql(&self, query: &str) -> serde_json::Value {
+      if let Err(error) = self.index.update() {
+        log::error!("{error}");
+      }
+
+      let response = reqwest::blocking::Client::new()
+        .post(self.join_url("/graphql"))
+        .json(&serde_json::json!({ "query": query }))
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::OK);
+
+      response.json().unwrap()
+    }
+
+    #[track_caller]
+    fn post_json<T: DeserializeOwned>(
+      &self,
+      path: impl AsRef<str>,
+      body: &serde_json::Value,
+    ) -> Result<T, (StatusCode, String)> {
+      if let Err(error) = self.index.update() {
+        log::error!("{error}");
+      }
+
+      let response = reqwest::blocking::Client::new()
+        .post(self.join_url(path.as_ref()))
+        .header(header::ACCEPT, "application/json")
+        .json(body)
+        .send()
+        .unwrap();
+
+      if response.status() == StatusCode::OK {
+        Ok(response.json().unwrap())
+      } else {
+        Err((response.status(), response.text().unwrap()))
+      }
+    }
+
     fn join_url(&self, url: &str) -> Url {
       self.url.join(url).unwrap()
     }
@@ -2710,6 +4426,41 @@ mod tests {
     TestServer::new().assert_redirect("/search?query=AB•CD", "/rune/AB•CD");
   }
 
+  #[test]
+  fn search_for_unknown_rune_redirects_to_rune_search() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          spacers: Some(1),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    server.assert_redirect("/search?query=AAAAAAAA•AAAAA", "/rune/AAAAAAAA•AAAAA");
+
+    server.assert_redirect(
+      "/search?query=AAAAAAAA•AAA",
+      "/runes/search?q=AAAAAAAA%E2%80%A2AAA",
+    );
+
+    server.assert_redirect(
+      "/search?query=AAAA••",
+      "/runes/search?q=AAAA%E2%80%A2%E2%80%A2",
+    );
+  }
+
   #[test]
   fn search_by_query_returns_inscription() {
     TestServer::new().assert_redirect(
@@ -3099,7 +4850,7 @@ mod tests {
   <dt>number</dt>
   <dd>0</dd>
   <dt>timestamp</dt>
-  <dd><time>1970-01-01 00:00:08 UTC</time></dd>
+  <dd><time datetime=1970-01-01T00:00:08Z>1970-01-01 00:00:08 UTC</time></dd>
   <dt>id</dt>
   <dd>8:1</dd>
   <dt>etching block</dt>
@@ -4028,7 +5779,7 @@ mod tests {
     TestServer::new().assert_response_regex(
       "/sat/0",
       StatusCode::OK,
-      ".*<dt>timestamp</dt><dd><time>2009-01-03 18:15:05 UTC</time></dd>.*",
+      ".*<dt>timestamp</dt><dd><time datetime=2009-01-03T18:15:05Z>2009-01-03 18:15:05 UTC</time></dd>.*",
     );
   }
 
@@ -4037,7 +5788,7 @@ mod tests {
     TestServer::new().assert_response_regex(
       "/sat/5000000000",
       StatusCode::OK,
-      ".*<dt>timestamp</dt><dd><time>.*</time> \\(expected\\)</dd>.*",
+      ".*<dt>timestamp</dt><dd><time datetime=.*>.*</time> \\(expected\\)</dd>.*",
     );
   }
 
@@ -4052,6 +5803,161 @@ mod tests {
     );
   }
 
+  #[test]
+  fn theme_directory_overrides_layout_and_static_assets() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::create_dir(tempdir.path().join("static")).unwrap();
+    fs::write(
+      tempdir.path().join("static/index.css"),
+      "html { color: red; }",
+    )
+    .unwrap();
+    fs::write(tempdir.path().join("static/logo.svg"), "<svg></svg>").unwrap();
+
+    fs::create_dir(tempdir.path().join("templates")).unwrap();
+    fs::write(
+      tempdir.path().join("templates/page.html"),
+      "<title>{{ title }} | {{ site_title }}</title><main>{{ content }}</main>",
+    )
+    .unwrap();
+    fs::write(
+      tempdir.path().join("templates/blocks.html"),
+      "<section>{{ content }}</section>",
+    )
+    .unwrap();
+
+    let server = TestServer::builder()
+      .server_option("--theme-dir", tempdir.path().to_str().unwrap())
+      .server_option("--site-title", "Example")
+      .build();
+
+    server.assert_response("/static/index.css", StatusCode::OK, "html { color: red; }");
+    server.assert_response("/static/logo.svg", StatusCode::OK, "<svg></svg>");
+
+    server.assert_response_regex("/static/index.js", StatusCode::OK, ".*addEventListener.*");
+
+    server.assert_response_regex(
+      "/blocks",
+      StatusCode::OK,
+      "<title>Blocks \\| Example</title><main><section><h1>Blocks</h1>.*</section></main>\n",
+    );
+
+    server.assert_response_regex(
+      "/",
+      StatusCode::OK,
+      "<title>Ordinals \\| Example</title><main><h1>Latest Inscriptions</h1>.*</main>\n",
+    );
+  }
+
+  #[test]
+  fn site_title_logo_and_color_scheme() {
+    TestServer::builder()
+      .server_option("--color-scheme", "auto")
+      .server_option("--site-logo", "/static/favicon.svg")
+      .server_option("--site-title", "Example")
+      .build()
+      .assert_response_regex(
+        "/",
+        StatusCode::OK,
+        ".*<html lang=en data-color-scheme=auto>.*<a href=/ title=home><img class=logo src='/static/favicon.svg'>Example<sup>.*",
+      );
+  }
+
+  #[test]
+  fn pages_are_localized() {
+    let server = TestServer::new();
+
+    server.assert_response_regex(
+      "/block/0?lang=zh",
+      StatusCode::OK,
+      ".*<html lang=zh .*<a href=/\\?lang=zh title=首页>.*<h1>区块 0</h1>.*<dt>时间戳</dt><dd><time datetime=2009-01-03T18:15:05Z>2009年1月3日 18:15:05 UTC</time></dd>.*",
+    );
+
+    let response = get_with_headers(
+      &server,
+      "/block/0",
+      &[(header::ACCEPT_LANGUAGE, "fr;q=0.9,ja;q=0.8")],
+    );
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response
+      .headers()
+      .get_all(header::VARY)
+      .iter()
+      .any(|vary| vary == "accept-language"));
+    assert_regex_match!(response.text().unwrap(), ".*<h1>ブロック 0</h1>.*");
+
+    let english = get_with_headers(&server, "/block/0", &[]);
+    let japanese = get_with_headers(&server, "/block/0", &[(header::ACCEPT_LANGUAGE, "ja")]);
+
+    assert_ne!(
+      header_value(&english, header::ETAG),
+      header_value(&japanese, header::ETAG),
+    );
+    assert_regex_match!(english.text().unwrap(), ".*<h1>Block 0</h1>.*");
+  }
+
+  #[test]
+  fn only_localized_responses_vary_by_language() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/html", "<p>hello</p>"));
+
+    let varies = |path: String, headers: &[(header::HeaderName, &str)]| {
+      get_with_headers(&server, &path, headers)
+        .headers()
+        .get_all(header::VARY)
+        .iter()
+        .any(|vary| vary == "accept-language")
+    };
+
+    assert!(varies("/block/0".into(), &[]));
+    assert!(!varies(
+      "/block/0".into(),
+      &[(header::ACCEPT, "application/json")]
+    ));
+    assert!(!varies(format!("/content/{inscription_id}"), &[]));
+    assert!(!varies(format!("/r/inscription/{inscription_id}"), &[]));
+    assert!(!varies("/static/index.css".into(), &[]));
+
+    let json = |accept_language| {
+      header_value(
+        &get_with_headers(
+          &server,
+          "/block/0",
+          &[
+            (header::ACCEPT, "application/json"),
+            (header::ACCEPT_LANGUAGE, accept_language),
+          ],
+        ),
+        header::ETAG,
+      )
+    };
+
+    assert_eq!(json("en"), json("ja"));
+  }
+
+  #[test]
+  fn language_chosen_with_query_is_carried_across_links() {
+    let server = TestServer::new();
+
+    server.assert_response_regex(
+      "/block/0?lang=ja",
+      StatusCode::OK,
+      ".*<link rel=stylesheet href=/static/index.css>.*\
+<a href=/inscriptions\\?lang=ja title=インスクリプション>.*\
+<form action=/search method=get><input type=hidden name=lang value=ja>.*",
+    );
+
+    assert!(
+      !get_with_headers(&server, "/block/0", &[(header::ACCEPT_LANGUAGE, "ja")])
+        .text()
+        .unwrap()
+        .contains("?lang=")
+    );
+  }
+
   #[test]
   fn favicon() {
     TestServer::new().assert_response_regex("/favicon.ico", StatusCode::OK, r".*");
@@ -4127,42 +6033,194 @@ mod tests {
   }
 
   #[test]
-  fn detect_unrecoverable_reorg() {
-    let test_server = TestServer::new();
+  fn transaction_sat_flow() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_sats()
+      .build();
 
-    test_server.mine_blocks(21);
+    server.mine_blocks(1);
 
-    test_server.assert_response_regex(
-      "/status",
-      StatusCode::OK,
-      ".*<dt>unrecoverably reorged</dt>\n  <dd>false</dd>.*",
+    let (txid, _id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          premine: Some(1000),
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
 
-    for _ in 0..15 {
-      test_server.core.invalidate_tip();
-    }
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .sat_flow
+        .unwrap()
+        .runes,
+      Some(vec![api::SatFlowRune {
+        amount: Pile {
+          amount: 1000,
+          divisibility: 0,
+          symbol: None,
+        },
+        output: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+      }]),
+    );
 
-    test_server.core.mine_blocks(21);
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      fee: 1000,
+      ..default()
+    });
 
-    test_server.assert_response_regex(
-      "/status",
-      StatusCode::OK,
-      ".*<dt>unrecoverably reorged</dt>\n  <dd>true</dd>.*",
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let satpoint = SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 0,
+    };
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .sat_flow,
+      Some(api::SatFlow {
+        fee: 1000,
+        inputs: vec![50 * COIN_VALUE],
+        inscriptions: Some(vec![api::SatFlowInscription {
+          genesis: true,
+          id: inscription_id,
+          input: 0,
+          satpoint,
+        }]),
+        runes: Some(Vec::new()),
+        segments: vec![
+          api::SatFlowSegment {
+            input: 0,
+            input_offset: 0,
+            output: Some(0),
+            output_offset: 0,
+            sat_ranges: Some(vec![(50 * COIN_VALUE, 100 * COIN_VALUE - 1000)]),
+            size: 50 * COIN_VALUE - 1000,
+          },
+          api::SatFlowSegment {
+            input: 0,
+            input_offset: 50 * COIN_VALUE - 1000,
+            output: None,
+            output_offset: 0,
+            sat_ranges: None,
+            size: 1000,
+          },
+        ],
+      }),
     );
-  }
 
-  #[test]
-  fn rare_with_sat_index() {
-    TestServer::builder().index_sats().build().assert_response(
-      "/rare.txt",
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
       StatusCode::OK,
-      "sat\tsatpoint
-0\t4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0
-",
+      format!(
+        ".*<h2>Sat Flow</h2>.*<line class=inscription .*<title>{inscription_id}</title></line>.*<td class=fee>fee</td>.*<td><a class=monospace href=/inscription/{inscription_id}>.*<td class=monospace>{satpoint}</td>.*"
+      ),
     );
-  }
 
-  #[test]
+    let height = server.index.block_count().unwrap();
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        usize::try_from(height - 1).unwrap(),
+        1,
+        0,
+        Default::default(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .sat_flow,
+      Some(api::SatFlow {
+        fee: 1000,
+        inputs: vec![50 * COIN_VALUE],
+        inscriptions: None,
+        runes: None,
+        segments: vec![
+          api::SatFlowSegment {
+            input: 0,
+            input_offset: 0,
+            output: Some(0),
+            output_offset: 0,
+            sat_ranges: None,
+            size: 50 * COIN_VALUE - 1000,
+          },
+          api::SatFlowSegment {
+            input: 0,
+            input_offset: 50 * COIN_VALUE - 1000,
+            output: None,
+            output_offset: 0,
+            sat_ranges: None,
+            size: 1000,
+          },
+        ],
+      }),
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      ".*<h2>Sat Flow</h2>.*<td class=fee>fee</td>.*<p>Outputs have been spent, so sat ranges, inscriptions, and rune balances are unavailable.</p>.*",
+    );
+  }
+
+  #[test]
+  fn detect_unrecoverable_reorg() {
+    let test_server = TestServer::new();
+
+    test_server.mine_blocks(21);
+
+    test_server.assert_response_regex(
+      "/status",
+      StatusCode::OK,
+      ".*<dt>unrecoverably reorged</dt>\n  <dd>false</dd>.*",
+    );
+
+    for _ in 0..15 {
+      test_server.core.invalidate_tip();
+    }
+
+    test_server.core.mine_blocks(21);
+
+    test_server.assert_response_regex(
+      "/status",
+      StatusCode::OK,
+      ".*<dt>unrecoverably reorged</dt>\n  <dd>true</dd>.*",
+    );
+  }
+
+  #[test]
+  fn rare_with_sat_index() {
+    TestServer::builder().index_sats().build().assert_response(
+      "/rare.txt",
+      StatusCode::OK,
+      "sat\tsatpoint
+0\t4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0
+",
+    );
+  }
+
+  #[test]
   fn rare_without_sat_index() {
     TestServer::new().assert_response(
       "/rare.txt",
@@ -4398,7 +6456,7 @@ mod tests {
     .unwrap();
 
     assert_eq!(headers["content-type"], "text/plain");
-    assert_eq!(body, vec![1, 2, 3]);
+    assert_eq!(body, ContentBody::Bytes(vec![1, 2, 3]));
   }
 
   #[test]
@@ -4491,6 +6549,42 @@ mod tests {
     }
   }
 
+  #[test]
+  fn preview_content_security_policies_use_csp_origin() {
+    let server_config = ServerConfig {
+      csp_origin: Some("https://ordinals.com".into()),
+      ..default()
+    };
+
+    for media in [
+      Media::Audio,
+      Media::Code(media::Language::Json),
+      Media::Csv,
+      Media::Font,
+      Media::Image(ImageRendering::Auto),
+      Media::Json,
+      Media::Markdown,
+      Media::Midi,
+      Media::Model,
+      Media::Pdf,
+      Media::Stl,
+      Media::Text,
+      Media::Unknown,
+      Media::Video,
+    ] {
+      let [(_name, value)] = server_config
+        .preview_content_security_policy(media)
+        .unwrap();
+
+      let value = value.to_str().unwrap();
+
+      assert!(
+        value.contains("https://ordinals.com") && !value.contains("'self'"),
+        "{media:?}: {value}"
+      );
+    }
+  }
+
   #[test]
   fn code_preview() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
@@ -4516,6 +6610,64 @@ mod tests {
     );
   }
 
+  #[test]
+  fn previews_of_additional_media_types() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    for (i, (content_type, content_security_policy, body)) in [
+      (
+        "text/x-rust",
+        "script-src-elem 'self' https://cdn.jsdelivr.net",
+        "<html lang=en data-inscription={} data-language=rust>",
+      ),
+      (
+        "text/csv",
+        "default-src 'self'",
+        "<script src=/static/preview-csv.js defer type=module></script>",
+      ),
+      (
+        "text/tab-separated-values",
+        "default-src 'self'",
+        "<script src=/static/preview-csv.js defer type=module></script>",
+      ),
+      (
+        "application/json",
+        "default-src 'self'",
+        "<script src=/static/preview-json.js defer type=module></script>",
+      ),
+      (
+        "audio/midi",
+        "default-src 'self'",
+        "<script src=/static/preview-midi.js defer type=module></script>",
+      ),
+      (
+        "model/stl",
+        "script-src-elem 'self' https://cdn.jsdelivr.net",
+        "<canvas data-inscription={}></canvas>",
+      ),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription(content_type, "hello").to_witness())],
+        ..default()
+      });
+
+      server.mine_blocks(1);
+
+      let inscription_id = InscriptionId { txid, index: 0 };
+
+      server.assert_response_csp(
+        format!("/preview/{inscription_id}"),
+        StatusCode::OK,
+        content_security_policy,
+        format!(".*{}.*", body.replace("{}", &inscription_id.to_string())),
+      );
+    }
+  }
+
   #[test]
   fn content_response_no_content_type() {
     let (headers, body) = Server::content_response(
@@ -4531,7 +6683,7 @@ mod tests {
     .unwrap();
 
     assert_eq!(headers["content-type"], "application/octet-stream");
-    assert!(body.is_empty());
+    assert_eq!(body, ContentBody::Bytes(Vec::new()));
   }
 
   #[test]
@@ -4549,7 +6701,7 @@ mod tests {
     .unwrap();
 
     assert_eq!(headers["content-type"], "application/octet-stream");
-    assert!(body.is_empty());
+    assert_eq!(body, ContentBody::Bytes(Vec::new()));
   }
 
   #[test]
@@ -4681,168 +6833,389 @@ mod tests {
       format!("/preview/{inscription_id}"),
       StatusCode::OK,
       "default-src 'self' 'unsafe-inline'",
-      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+      format!(r".*<img src=/content/{inscription_id} srcset='/thumbnail/{inscription_id}\?size=256 256w, .*"),
     );
   }
 
   #[test]
-  fn iframe_preview() {
+  fn thumbnail() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
+    let mut png = Vec::new();
+    image::RgbaImage::new(1000, 500)
+      .write_to(&mut io::Cursor::new(&mut png), image::ImageFormat::Png)
+      .unwrap();
+
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("text/html;charset=utf-8", "hello").to_witness(),
-      )],
+      inputs: &[(1, 0, 0, inscription("image/png", png).to_witness())],
       ..default()
     });
+    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
-      "hello",
+    let response = server.get(format!("/thumbnail/{inscription_id}?size=100"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "image/png"
+    );
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "public, max-age=1209600, immutable"
+    );
+
+    let etag = response.headers().get(header::ETAG).unwrap().clone();
+
+    assert_eq!(
+      image::load_from_memory(&response.bytes().unwrap())
+        .unwrap()
+        .dimensions(),
+      (128, 64)
+    );
+
+    assert!(server
+      .tempdir
+      .path()
+      .join(format!("regtest/thumbnails/{inscription_id}-128.png"))
+      .is_file());
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/thumbnail/{inscription_id}?size=100")))
+      .header(header::IF_NONE_MATCH, etag)
+      .send()
+      .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = server.get(format!("/thumbnail/{inscription_id}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      image::load_from_memory(&response.bytes().unwrap())
+        .unwrap()
+        .dimensions(),
+      (256, 128)
     );
   }
 
   #[test]
-  fn unknown_preview() {
+  fn thumbnail_of_svg() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "image/svg+xml",
+          r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 2 1"><rect width="2" height="1"/></svg>"#,
+        )
+        .to_witness(),
+      )],
       ..default()
     });
+    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self'",
-      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    let response = server.get(format!("/thumbnail/{inscription_id}?size=64"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      image::load_from_memory(&response.bytes().unwrap())
+        .unwrap()
+        .dimensions(),
+      (64, 32)
     );
   }
 
   #[test]
-  fn video_preview() {
+  fn thumbnail_of_undecodable_image_redirects_to_content() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
       ..default()
     });
     let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    server.assert_redirect(
+      &format!("/thumbnail/{inscription_id}"),
+      &format!("/content/{inscription_id}"),
     );
   }
 
   #[test]
-  fn inscription_page_title() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .build();
-    server.mine_blocks(1);
+  fn thumbnail_of_compressed_image_requires_decompress() {
+    let mut png = Vec::new();
+    image::RgbaImage::new(10, 10)
+      .write_to(&mut io::Cursor::new(&mut png), image::ImageFormat::Png)
+      .unwrap();
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    let mut body = Vec::new();
+
+    io::Write::write_all(
+      &mut brotli::CompressorWriter::new(&mut body, 4096, 11, 22),
+      &png,
+    )
+    .unwrap();
+
+    let inscription = Inscription {
+      content_type: Some("image/png".into()),
+      content_encoding: Some("br".into()),
+      body: Some(body),
       ..default()
-    });
+    };
 
-    server.mine_blocks(1);
+    let server = TestServer::builder().chain(Chain::Regtest).build();
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
+    let inscription_id = inscribe_content(&server, inscription.clone());
+
+    server.assert_redirect(
+      &format!("/thumbnail/{inscription_id}"),
+      &format!("/content/{inscription_id}"),
     );
-  }
 
-  #[test]
-  fn inscription_page_has_sat_when_sats_are_tracked() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
-      .index_sats()
+      .server_flag("--decompress")
       .build();
-    server.mine_blocks(1);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..default()
-    });
+    let inscription_id = inscribe_content(&server, inscription);
 
-    server.mine_blocks(1);
-
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>sat name</dt>\s*<dd><a href=/sat/nvtcsezkbth>nvtcsezkbth</a></dd>\s*<dt>preview</dt>.*",
+    let response = server.get(format!("/thumbnail/{inscription_id}"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      image::load_from_memory(&response.bytes().unwrap())
+        .unwrap()
+        .dimensions(),
+      (10, 10)
     );
   }
 
   #[test]
-  fn inscriptions_can_be_looked_up_by_sat_name() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
+  fn thumbnail_of_hidden_inscription_is_not_found() {
+    let core = mockcore::builder()
+      .network(Chain::Regtest.network())
       .build();
-    server.mine_blocks(1);
 
-    server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    core.mine_blocks(1);
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
       ..default()
     });
 
-    server.mine_blocks(1);
+    core.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", Sat(5000000000).name()),
-      StatusCode::OK,
-      ".*<title>Inscription 0</title.*",
-    );
-  }
+    let inscription_id = InscriptionId { txid, index: 0 };
 
-  #[test]
-  fn inscriptions_can_be_looked_up_by_sat_name_with_letter_i() {
     let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
+      .core(core)
+      .config(&format!("hidden: [{inscription_id}]"))
       .build();
-    server.assert_response_regex("/inscription/i", StatusCode::NOT_FOUND, ".*");
+
+    server.assert_response(
+      format!("/thumbnail/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} has no thumbnail"),
+    );
   }
 
   #[test]
-  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
+  fn thumbnail_errors() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..default()
     });
+    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+    server.assert_response(
+      format!("/thumbnail/{inscription_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {inscription_id} has no thumbnail"),
     );
-  }
+
+    server.assert_response(
+      format!("/thumbnail/{inscription_id}?size=0"),
+      StatusCode::BAD_REQUEST,
+      "thumbnail size must be between 1 and 1024",
+    );
+
+    server.assert_response(
+      format!("/thumbnail/{inscription_id}?size=1025"),
+      StatusCode::BAD_REQUEST,
+      "thumbnail size must be between 1 and 1024",
+    );
+  }
+
+  #[test]
+  fn iframe_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/html;charset=utf-8", "hello").to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+      "hello",
+    );
+  }
+
+  #[test]
+  fn unknown_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self'",
+      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn video_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+      ..default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn inscription_page_title() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      ".*<title>Inscription 0</title>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_page_has_sat_when_sats_are_tracked() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>sat name</dt>\s*<dd><a href=/sat/nvtcsezkbth>nvtcsezkbth</a></dd>\s*<dt>preview</dt>.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_looked_up_by_sat_name() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", Sat(5000000000).name()),
+      StatusCode::OK,
+      ".*<title>Inscription 0</title.*",
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_looked_up_by_sat_name_with_letter_i() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+    server.assert_response_regex("/inscription/i", StatusCode::NOT_FOUND, ".*");
+  }
+
+  #[test]
+  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      r".*<dt>value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+    );
+  }
 
   #[test]
   fn strict_transport_security_header_is_set() {
@@ -5037,6 +7410,142 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscriptions_json_cursor_pagination() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let mut ids = Vec::new();
+
+    for i in 0..5 {
+      server.mine_blocks(1);
+      ids.push(InscriptionId {
+        txid: server.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+          ..default()
+        }),
+        index: 0,
+      });
+    }
+
+    server.mine_blocks(1);
+
+    let first = server.get_json::<api::Inscriptions>("/inscriptions?limit=2");
+    assert_eq!(first.ids, [ids[4], ids[3]]);
+    assert!(first.more);
+    assert_eq!(first.prev_cursor, None);
+
+    let second = server.get_json::<api::Inscriptions>(format!(
+      "/inscriptions?limit=2&cursor={}",
+      first.next_cursor.unwrap()
+    ));
+    assert_eq!(second.ids, [ids[2], ids[1]]);
+
+    let previous = server.get_json::<api::Inscriptions>(format!(
+      "/inscriptions?limit=2&cursor={}",
+      second.prev_cursor.unwrap()
+    ));
+    assert_eq!(previous.ids, first.ids);
+
+    let ascending = server.get_json::<api::Inscriptions>("/inscriptions?limit=3&order=asc");
+    assert_eq!(ascending.ids, [ids[0], ids[1], ids[2]]);
+
+    let page = server.get_json::<api::Inscriptions>("/inscriptions/1?limit=2");
+    assert_eq!(page.ids, [ids[2], ids[1]]);
+    assert_eq!(page.page_index, 1);
+  }
+
+  #[test]
+  fn pagination_limit_and_cursor_are_validated() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    for path in ["/inscriptions?limit=0", "/runes?limit=1001"] {
+      let response = reqwest::blocking::Client::new()
+        .get(server.join_url(path))
+        .header(header::ACCEPT, "application/json")
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+      assert_eq!(response.text().unwrap(), "limit must be between 1 and 1000");
+    }
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url("/collections?cursor=foo"))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn children_and_collections_json_cursor_pagination() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let mut children = Vec::new();
+
+    for i in 0..3 {
+      children.push(InscriptionId {
+        txid: server.core.broadcast_tx(TransactionTemplate {
+          inputs: &[
+            (i + 2, 1, 0, Default::default()),
+            (
+              i + 2,
+              0,
+              0,
+              Inscription {
+                content_type: Some("text/plain".into()),
+                body: Some("hello".into()),
+                parents: vec![parent.value()],
+                ..default()
+              }
+              .to_witness(),
+            ),
+          ],
+          outputs: 2,
+          output_values: &[50 * COIN_VALUE, 50 * COIN_VALUE],
+          ..default()
+        }),
+        index: 0,
+      });
+
+      server.mine_blocks(1);
+    }
+
+    let first = server.get_json::<api::Inscriptions>(format!("/children/{parent}?limit=2"));
+    assert_eq!(first.ids, &children[..2]);
+    assert!(first.more);
+
+    let second = server.get_json::<api::Inscriptions>(format!(
+      "/children/{parent}?limit=2&cursor={}",
+      first.next_cursor.unwrap()
+    ));
+    assert_eq!(second.ids, &children[2..]);
+    assert!(!second.more);
+    assert_eq!(second.next_cursor, None);
+
+    let descending = server.get_json::<api::Inscriptions>(format!("/children/{parent}?order=desc"));
+    assert_eq!(descending.ids, [children[2], children[1], children[0]]);
+
+    let collections = server.get_json::<api::Inscriptions>("/collections");
+    assert_eq!(collections.ids, [parent]);
+    assert!(!collections.more);
+    assert_eq!(collections.next_cursor, None);
+    assert_eq!(collections.prev_cursor, None);
+  }
+
   #[test]
   fn collections_page_prev_and_next() {
     let server = TestServer::builder()
@@ -5163,29 +7672,342 @@ next
     );
   }
 
-  #[test]
-  fn inscription_links_to_parent() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+  fn inscribe_content(server: &TestServer, inscription: Inscription) -> InscriptionId {
     server.mine_blocks(1);
 
-    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
-    };
+    InscriptionId { txid, index: 0 }
+  }
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[
-        (
-          2,
-          0,
-          0,
+  fn get_content(
+    server: &TestServer,
+    inscription_id: InscriptionId,
+    headers: &[(header::HeaderName, &str)],
+  ) -> reqwest::blocking::Response {
+    get_with_headers(server, &format!("/content/{inscription_id}"), headers)
+  }
+
+  fn get_with_headers(
+    server: &TestServer,
+    path: &str,
+    headers: &[(header::HeaderName, &str)],
+  ) -> reqwest::blocking::Response {
+    let mut request = reqwest::blocking::Client::builder()
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(server.join_url(path));
+
+    for (name, value) in headers {
+      request = request.header(name, *value);
+    }
+
+    request.send().unwrap()
+  }
+
+  fn header_value(response: &reqwest::blocking::Response, name: header::HeaderName) -> String {
+    response.headers()[name].to_str().unwrap().to_owned()
+  }
+
+  #[test]
+  fn content_without_range_is_served_in_full() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", "hello world"));
+
+    let response = get_content(&server, inscription_id, &[]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+    assert_eq!(response.headers()[header::CONTENT_LENGTH], "11");
+    assert_eq!(
+      response.headers()[header::ETAG],
+      format!("\"{inscription_id}\"")
+    );
+    assert_eq!(response.text().unwrap(), "hello world");
+  }
+
+  #[test]
+  fn content_range_is_served_as_partial_content() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let content = format!("hello {}", "world".repeat(20));
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", &content));
+
+    let response = get_content(
+      &server,
+      inscription_id,
+      &[
+        (header::RANGE, "bytes=6-"),
+        (header::ACCEPT_ENCODING, "gzip"),
+      ],
+    );
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 6-105/106");
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+    assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+    assert_eq!(response.text().unwrap(), content[6..]);
+  }
+
+  #[test]
+  fn content_multiple_ranges_are_served_as_multipart() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", "hello world"));
+
+    let response = get_content(&server, inscription_id, &[(header::RANGE, "bytes=0-1,-2")]);
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+
+    let content_type = response.headers()[header::CONTENT_TYPE]
+      .to_str()
+      .unwrap()
+      .to_owned();
+
+    let boundary = content_type
+      .strip_prefix("multipart/byteranges; boundary=")
+      .unwrap();
+
+    pretty_assert_eq!(
+      response.text().unwrap(),
+      format!(
+        "\r\n--{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-1/11\r\n\r\nhe\
+        \r\n--{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 9-10/11\r\n\r\nld\
+        \r\n--{boundary}--\r\n"
+      ),
+    );
+  }
+
+  #[test]
+  fn unsatisfiable_content_range() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", "hello world"));
+
+    let response = get_content(&server, inscription_id, &[(header::RANGE, "bytes=11-")]);
+
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */11");
+  }
+
+  #[test]
+  fn content_range_is_only_served_if_range_matches() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", "hello world"));
+
+    let etag = get_content(&server, inscription_id, &[]).headers()[header::ETAG]
+      .to_str()
+      .unwrap()
+      .to_owned();
+
+    let response = get_content(
+      &server,
+      inscription_id,
+      &[(header::RANGE, "bytes=0-4"), (header::IF_RANGE, &etag)],
+    );
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.text().unwrap(), "hello");
+
+    let response = get_content(
+      &server,
+      inscription_id,
+      &[
+        (header::RANGE, "bytes=0-4"),
+        (header::IF_RANGE, "\"stale\""),
+      ],
+    );
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "hello world");
+  }
+
+  #[test]
+  fn decompressed_content_ignores_range() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--decompress")
+      .build();
+
+    let mut body = Vec::new();
+
+    io::Write::write_all(
+      &mut brotli::CompressorWriter::new(&mut body, 4096, 11, 22),
+      b"hello world",
+    )
+    .unwrap();
+
+    let inscription_id = inscribe_content(
+      &server,
+      Inscription {
+        content_type: Some("text/plain".into()),
+        content_encoding: Some("br".into()),
+        body: Some(body),
+        ..default()
+      },
+    );
+
+    let response = get_content(&server, inscription_id, &[(header::RANGE, "bytes=0-4")]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ACCEPT_RANGES], "none");
+    assert_eq!(response.text().unwrap(), "hello world");
+  }
+
+  #[test]
+  fn unchanged_content_is_not_modified() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", "hello world"));
+
+    let response = get_content(&server, inscription_id, &[]);
+
+    let etag = header_value(&response, header::ETAG);
+    let last_modified = header_value(&response, header::LAST_MODIFIED);
+
+    let response = get_content(&server, inscription_id, &[(header::IF_NONE_MATCH, &etag)]);
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(header_value(&response, header::ETAG), etag);
+    assert_eq!(
+      header_value(&response, header::CACHE_CONTROL),
+      "public, max-age=1209600, immutable"
+    );
+    assert_eq!(response.text().unwrap(), "");
+
+    let response = get_content(
+      &server,
+      inscription_id,
+      &[(header::IF_MODIFIED_SINCE, &last_modified)],
+    );
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = get_content(
+      &server,
+      inscription_id,
+      &[(header::IF_NONE_MATCH, "\"stale\"")],
+    );
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().unwrap(), "hello world");
+  }
+
+  #[test]
+  fn inscription_is_not_modified_until_it_moves() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let inscription_id = inscribe_content(&server, inscription("text/plain", "hello world"));
+
+    let path = format!("/inscription/{inscription_id}");
+
+    let response = get_with_headers(&server, &path, &[]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_value(&response, header::CACHE_CONTROL), "no-cache");
+    assert!(response
+      .headers()
+      .get_all(header::VARY)
+      .iter()
+      .any(|value| value == "accept"));
+
+    let etag = header_value(&response, header::ETAG);
+
+    assert!(etag.starts_with("W/\""));
+
+    let response = get_with_headers(&server, &path, &[(header::IF_NONE_MATCH, &etag)]);
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(header_value(&response, header::ETAG), etag);
+
+    let response = get_with_headers(
+      &server,
+      &path,
+      &[
+        (header::IF_NONE_MATCH, &etag),
+        (header::ACCEPT, "application/json"),
+      ],
+    );
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 0,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = get_with_headers(&server, &path, &[(header::IF_NONE_MATCH, &etag)]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(header_value(&response, header::ETAG), etag);
+  }
+
+  #[test]
+  fn output_is_not_modified_until_it_is_spent() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let path = format!("/output/{}:0", server.core.tx(1, 0).txid());
+
+    let response = get_with_headers(&server, &path, &[]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_value(&response, header::CACHE_CONTROL), "no-cache");
+
+    let etag = header_value(&response, header::ETAG);
+
+    let response = get_with_headers(&server, &path, &[(header::IF_NONE_MATCH, &etag)]);
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = get_with_headers(&server, &path, &[(header::IF_NONE_MATCH, &etag)]);
+
+    assert_eq!(response.status(), StatusCode::OK);
+  }
+
+  #[test]
+  fn inscription_links_to_parent() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
@@ -5283,7 +8105,7 @@ next
   }
 
   #[test]
-  fn inscriptions_page_shows_max_four_children() {
+  fn collection_gallery_aggregates_child_traits() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
@@ -5292,40 +8114,203 @@ next
       ..default()
     });
 
-    server.mine_blocks(6);
+    server.mine_blocks(4);
 
-    let parent_inscription_id = InscriptionId {
+    let parent = InscriptionId {
       txid: parent_txid,
       index: 0,
     };
 
-    let _txid = server.core.broadcast_tx(TransactionTemplate {
+    let child = |eyes: &str| {
+      let mut metadata = Vec::new();
+      ciborium::into_writer(
+        &Value::Map(vec![(Value::Text("eyes".into()), Value::Text(eyes.into()))]),
+        &mut metadata,
+      )
+      .unwrap();
+
+      Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        metadata: Some(metadata),
+        parents: vec![parent.value()],
+        ..default()
+      }
+      .to_witness()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 0, 0, child("blue")),
+        (3, 0, 0, child("blue")),
+        (4, 0, 0, child("laser")),
+        (2, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let child = |index, rank, rarity_score, eyes: &str| api::CollectionChild {
+      id: InscriptionId { txid, index },
+      rank,
+      rarity_score,
+      traits: [("eyes".into(), eyes.into())].into(),
+    };
+
+    let traits = BTreeMap::from([(
+      "eyes".to_string(),
+      BTreeMap::from([("blue".to_string(), 2), ("laser".to_string(), 1)]),
+    )]);
+
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{parent}")),
+      api::Collection {
+        children: vec![
+          child(0, 2, 1.5, "blue"),
+          child(1, 2, 1.5, "blue"),
+          child(2, 1, 3.0, "laser"),
+        ],
+        filters: BTreeMap::new(),
+        matches: 3,
+        more: false,
+        page_index: 0,
+        parent,
+        parent_number: 0,
+        total: 3,
+        traits: traits.clone(),
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Collection>(format!("/collection/{parent}?trait.eyes=blue&lang=ja")),
+      api::Collection {
+        children: vec![child(0, 2, 1.5, "blue"), child(1, 2, 1.5, "blue")],
+        filters: [("eyes".into(), "blue".into())].into(),
+        matches: 2,
+        more: false,
+        page_index: 0,
+        parent,
+        parent_number: 0,
+        total: 3,
+        traits,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/collection/{parent}?trait.eyes=laser"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Inscription 0 Collection</title>.*
+<p>Showing 1 of 3 children. <a href=/collection/{parent}>Clear filters</a></p>.*
+    <td><a class=selected href=/collection/{parent}>laser</a></td>.*
+<h2>1 Child</h2>
+<div class=thumbnails>
+  <a href=/inscription/{txid}i2>.*"
+      ),
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Collection>(format!("/collection/{parent}/1"))
+        .children,
+      Vec::new(),
+    );
+
+    server.assert_response_regex(
+      format!("/collection/{txid}i0"),
+      StatusCode::OK,
+      ".*<h2>0 Children</h2>\n<p>No children.</p>.*",
+    );
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer(
+      &Value::Map(vec![(
+        Value::Text("eyes".into()),
+        Value::Text("laser".into()),
+      )]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    server.core.broadcast_tx(TransactionTemplate {
       inputs: &[
         (
-          2,
-          0,
-          0,
-          Inscription {
-            content_type: Some("text/plain".into()),
-            body: Some("hello".into()),
-            parents: vec![parent_inscription_id.value()],
-            ..default()
-          }
-          .to_witness(),
-        ),
-        (
-          3,
+          5,
           0,
           0,
           Inscription {
             content_type: Some("text/plain".into()),
             body: Some("hello".into()),
-            parents: vec![parent_inscription_id.value()],
+            metadata: Some(metadata),
+            parents: vec![parent.value()],
             ..default()
           }
           .to_witness(),
         ),
-        (
+        (6, 1, 0, Default::default()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let collection = server.get_json::<api::Collection>(format!("/collection/{parent}"));
+
+    assert_eq!(collection.total, 4);
+    assert_eq!(
+      collection.traits,
+      BTreeMap::from([(
+        "eyes".to_string(),
+        BTreeMap::from([("blue".to_string(), 2), ("laser".to_string(), 2)]),
+      )]),
+    );
+  }
+
+  #[test]
+  fn inscriptions_page_shows_max_four_children() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(6);
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let _txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (
+          2,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (
+          3,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            parents: vec![parent_inscription_id.value()],
+            ..default()
+          }
+          .to_witness(),
+        ),
+        (
           4,
           0,
           0,
@@ -5379,6 +8364,7 @@ next
 .*<a href=/inscription/.*><iframe .* src=/preview/.*></iframe></a>.*
     <div class=center>
       <a href=/children/{parent_inscription_id}>all</a>
+      <a href=/collection/{parent_inscription_id}>gallery</a>
     </div>.*"
       ),
     );
@@ -6111,562 +9097,673 @@ next
   }
 
   #[test]
-  fn sat_recursive_endpoints() {
+  fn rune_mint_dashboard() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
-      .index_sats()
+      .index_runes()
       .build();
 
-    assert_eq!(
-      server.get_json::<api::SatInscriptions>("/r/sat/5000000000"),
-      api::SatInscriptions {
-        ids: Vec::new(),
-        page: 0,
-        more: false
-      }
-    );
+    server.mine_blocks(1);
 
-    assert_eq!(
-      server.get_json::<api::SatInscription>("/r/sat/5000000000/at/0"),
-      api::SatInscription { id: None }
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          symbol: Some('%'),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(10),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
 
     server.mine_blocks(1);
 
+    let height = server.index.block_count().unwrap();
+
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      inputs: &[(usize::try_from(height - 1).unwrap(), 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let mut ids = Vec::new();
-    ids.push(InscriptionId { txid, index: 0 });
-
-    for i in 1..111 {
-      let txid = server.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 1, 0, inscription("text/plain", "foo").to_witness())],
-        ..default()
-      });
-
-      server.mine_blocks(1);
-
-      ids.push(InscriptionId { txid, index: 0 });
-    }
+    let mints = server.get_json::<api::RuneMints>(format!("/rune/{id}/mints"));
 
-    let paginated_response = server.get_json::<api::SatInscriptions>("/r/sat/5000000000");
+    pretty_assert_eq!(
+      mints.activity,
+      vec![api::RuneBlockActivity {
+        burned: 0,
+        height,
+        mints: 1,
+      }],
+    );
+    pretty_assert_eq!(mints.recent_mints, vec![api::RuneMint { height, txid }]);
+    assert_eq!(mints.entry.mints, 1);
+    assert_eq!(mints.remaining, 9);
+    assert!(mints.mintable);
+    assert_eq!(mints.blocks_until_start, None);
+    assert_eq!(mints.blocks_until_end, None);
+    assert_eq!(
+      mints.projected_mint_out,
+      Some(u64::from(height) + 9 * u64::from(RECENT_MINT_BLOCKS)),
+    );
 
-    let equivalent_paginated_response =
-      server.get_json::<api::SatInscriptions>("/r/sat/5000000000/0");
+    pretty_assert_eq!(
+      server.get_json::<api::RunesMinting>("/runes/minting"),
+      api::RunesMinting {
+        entries: vec![api::MintingRune {
+          end: None,
+          id,
+          mints: 1,
+          recent_mints: 1,
+          remaining: 9,
+          spaced_rune: SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          symbol: Some('%'),
+        }],
+        more: false,
+        next: None,
+        prev: None,
+      }
+    );
 
-    assert_eq!(paginated_response.ids.len(), 100);
-    assert!(paginated_response.more);
-    assert_eq!(paginated_response.page, 0);
+    server.assert_response_regex(
+      format!("/rune/{id}"),
+      StatusCode::OK,
+      ".*<dt>mints</dt>\\s*<dd><a href=/rune/AAAAAAAAAAAAA/mints>1</a></dd>.*",
+    );
 
-    assert_eq!(
-      paginated_response.ids.len(),
-      equivalent_paginated_response.ids.len()
+    server.assert_response_regex(
+      "/runes/minting",
+      StatusCode::OK,
+      ".*<a href=/rune/AAAAAAAAAAAAA/mints>% AAAAAAAAAAAAA</a>.*",
     );
-    assert_eq!(paginated_response.more, equivalent_paginated_response.more);
-    assert_eq!(paginated_response.page, equivalent_paginated_response.page);
+  }
 
-    let paginated_response = server.get_json::<api::SatInscriptions>("/r/sat/5000000000/1");
+  #[test]
+  fn runes_minting_only_lists_open_mints() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
 
-    assert_eq!(paginated_response.ids.len(), 11);
-    assert!(!paginated_response.more);
-    assert_eq!(paginated_response.page, 1);
+    server.mine_blocks(1);
 
-    assert_eq!(
-      server
-        .get_json::<api::SatInscription>("/r/sat/5000000000/at/0")
-        .id,
-      Some(ids[0])
+    let (_txid, capped) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(1),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
 
-    assert_eq!(
-      server
-        .get_json::<api::SatInscription>("/r/sat/5000000000/at/-111")
-        .id,
-      Some(ids[0])
+    server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE + 1)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
 
-    assert_eq!(
-      server
-        .get_json::<api::SatInscription>("/r/sat/5000000000/at/110")
-        .id,
-      Some(ids[110])
+    let (_txid, ending) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE + 2)),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(10),
+            offset: (None, Some(3)),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
 
-    assert_eq!(
+    let minting = || {
       server
-        .get_json::<api::SatInscription>("/r/sat/5000000000/at/-1")
-        .id,
-      Some(ids[110])
-    );
-
-    assert!(server
-      .get_json::<api::SatInscription>("/r/sat/5000000000/at/111")
-      .id
-      .is_none());
-  }
+        .get_json::<api::RunesMinting>("/runes/minting")
+        .entries
+        .into_iter()
+        .map(|entry| entry.id)
+        .collect::<Vec<RuneId>>()
+    };
 
-  #[test]
-  fn children_recursive_endpoint() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
+    assert_eq!(minting(), [capped, ending]);
 
-    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        usize::try_from(server.index.block_count().unwrap() - 1).unwrap(),
+        0,
+        0,
+        Witness::new(),
+      )],
+      op_return: Some(
+        Runestone {
+          mint: Some(capped),
+          ..default()
+        }
+        .encipher(),
+      ),
       ..default()
     });
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
-    };
-
-    server.assert_response(
-      format!("/r/children/{parent_inscription_id}"),
-      StatusCode::NOT_FOUND,
-      &format!("inscription {parent_inscription_id} not found"),
-    );
-
     server.mine_blocks(1);
 
-    let children_json =
-      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
-    assert_eq!(children_json.ids.len(), 0);
+    assert_eq!(minting(), [ending]);
 
-    let mut builder = script::Builder::new();
-    for _ in 0..111 {
-      builder = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
-        parents: vec![parent_inscription_id.value()],
-        unrecognized_even_field: false,
-        ..default()
-      }
-      .append_reveal_script_to_builder(builder);
-    }
+    server.mine_blocks(1);
 
-    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+    assert_eq!(minting(), []);
+  }
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
-      ..default()
-    });
+  #[test]
+  fn rune_search() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
 
     server.mine_blocks(1);
 
-    let first_child_inscription_id = InscriptionId { txid, index: 0 };
-    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
-    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
-    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
-
-    let children_json =
-      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
-
-    assert_eq!(children_json.ids.len(), 100);
-    assert_eq!(children_json.ids[0], first_child_inscription_id);
-    assert_eq!(children_json.ids[99], hundredth_child_inscription_id);
-    assert!(children_json.more);
-    assert_eq!(children_json.page, 0);
-
-    let children_json =
-      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}/1"));
-
-    assert_eq!(children_json.ids.len(), 11);
-    assert_eq!(children_json.ids[0], hundred_first_child_inscription_id);
-    assert_eq!(children_json.ids[10], hundred_eleventh_child_inscription_id);
-    assert!(!children_json.more);
-    assert_eq!(children_json.page, 1);
-  }
-
-  #[test]
-  fn parents_recursive_endpoint() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
+    server.assert_response_regex(
+      "/runes/search?q=A",
+      StatusCode::OK,
+      ".*<h1>Rune Search</h1>.*<p>No runes match A.</p>.*",
+    );
 
-    let mut parent_ids = Vec::new();
-    let mut inputs = Vec::new();
-    for i in 0..111 {
-      parent_ids.push(InscriptionId {
-        txid: server.core.broadcast_tx(TransactionTemplate {
-          inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
+    let (_txid, id0) = server.etch(
+      Runestone {
+        edicts: vec![
+          Edict {
+            id: RuneId::default(),
+            amount: 500,
+            output: 0,
+          },
+          Edict {
+            id: RuneId::default(),
+            amount: 500,
+            output: 1,
+          },
+        ],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          spacers: Some(1),
           ..default()
         }),
-        index: 0,
-      });
-
-      inputs.push((i + 2, 1, 0, Witness::default()));
-
-      server.mine_blocks(1);
-    }
+        ..default()
+      },
+      2,
+      None,
+    );
 
-    inputs.insert(
-      0,
-      (
-        112,
-        0,
-        0,
-        Inscription {
-          content_type: Some("text/plain".into()),
-          body: Some("hello".into()),
-          parents: parent_ids.iter().map(|id| id.value()).collect(),
+    let (_txid, id1) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE + 1)),
+          premine: Some(1000),
+          symbol: Some('%'),
           ..default()
-        }
-        .to_witness(),
-      ),
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &inputs,
-      ..default()
-    });
-
-    server.mine_blocks(1);
-
-    let inscription_id = InscriptionId { txid, index: 0 };
-
-    let first_parent_inscription_id = parent_ids[0];
-    let hundredth_parent_inscription_id = parent_ids[99];
-    let hundred_first_parent_inscription_id = parent_ids[100];
-    let hundred_eleventh_parent_inscription_id = parent_ids[110];
-
-    let parents_json = server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}"));
-
-    assert_eq!(parents_json.ids.len(), 100);
-    assert_eq!(parents_json.ids[0], first_parent_inscription_id);
-    assert_eq!(parents_json.ids[99], hundredth_parent_inscription_id);
-    assert!(parents_json.more);
-    assert_eq!(parents_json.page_index, 0);
-
-    let parents_json =
-      server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}/1"));
-
-    assert_eq!(parents_json.ids.len(), 11);
-    assert_eq!(parents_json.ids[0], hundred_first_parent_inscription_id);
-    assert_eq!(parents_json.ids[10], hundred_eleventh_parent_inscription_id);
-    assert!(!parents_json.more);
-    assert_eq!(parents_json.page_index, 1);
-  }
-
-  #[test]
-  fn child_inscriptions_recursive_endpoint() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
-
-    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-      ..default()
-    });
+    let result0 = |outputs| api::RuneSearchResult {
+      id: id0,
+      mints: 0,
+      number: 0,
+      outputs,
+      spaced_rune: SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 1,
+      },
+      symbol: None,
+    };
 
-    let parent_inscription_id = InscriptionId {
-      txid: parent_txid,
-      index: 0,
+    let result1 = |outputs| api::RuneSearchResult {
+      id: id1,
+      mints: 0,
+      number: 1,
+      outputs,
+      spaced_rune: SpacedRune {
+        rune: Rune(RUNE + 1),
+        spacers: 0,
+      },
+      symbol: Some('%'),
     };
 
-    server.assert_response(
-      format!("/r/children/{parent_inscription_id}/inscriptions"),
-      StatusCode::NOT_FOUND,
-      &format!("inscription {parent_inscription_id} not found"),
+    pretty_assert_eq!(
+      server.get_json::<api::RuneSearch>("/runes/search?q=aaaa.aaaa"),
+      api::RuneSearch {
+        more: false,
+        query: "aaaa.aaaa".into(),
+        results: vec![result0(2), result1(1)],
+        sort: api::RuneSort::Outputs,
+      }
     );
 
-    server.mine_blocks(1);
+    pretty_assert_eq!(
+      server
+        .get_json::<api::RuneSearch>("/runes/search?q=AAAAAAAAAAAAB&sort=number")
+        .results,
+      vec![result1(1), result0(2)],
+    );
 
-    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
-      "/r/children/{parent_inscription_id}/inscriptions"
-    ));
-    assert_eq!(child_inscriptions_json.children.len(), 0);
+    pretty_assert_eq!(
+      server
+        .get_json::<api::RuneSearch>("/runes/search?q=AAAAAAAAAAABA")
+        .results,
+      vec![result0(2), result1(1)],
+    );
 
-    let mut builder = script::Builder::new();
-    for _ in 0..111 {
-      builder = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
-        parents: vec![parent_inscription_id.value()],
-        unrecognized_even_field: false,
-        ..default()
+    pretty_assert_eq!(
+      server.get_json::<api::RuneSearch>("/runes/search?q=AAAAAAAAAAAA&sort=number&limit=1"),
+      api::RuneSearch {
+        more: true,
+        query: "AAAAAAAAAAAA".into(),
+        results: vec![result0(2)],
+        sort: api::RuneSort::Number,
       }
-      .append_reveal_script_to_builder(builder);
-    }
+    );
 
-    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+    assert_eq!(
+      server
+        .get_json::<api::RuneSearch>("/runes/search?q=B")
+        .results,
+      Vec::new(),
+    );
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (id0.block.try_into().unwrap(), 1, 0, Witness::new()),
+        (id0.block.try_into().unwrap(), 1, 1, Witness::new()),
+      ],
+      outputs: 1,
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let first_child_inscription_id = InscriptionId { txid, index: 0 };
-    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
-    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
-    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
-
-    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
-      "/r/children/{parent_inscription_id}/inscriptions"
-    ));
-
-    assert_eq!(child_inscriptions_json.children.len(), 100);
-
     assert_eq!(
-      child_inscriptions_json.children[0].id,
-      first_child_inscription_id
+      server
+        .get_json::<api::RuneSearch>("/runes/search?q=A")
+        .results
+        .into_iter()
+        .map(|result| (result.id, result.outputs))
+        .collect::<Vec<(RuneId, u64)>>(),
+      [(id0, 1), (id1, 1)],
     );
-    assert_eq!(child_inscriptions_json.children[0].number, 1); // parent is #0, 1st child is #1
 
-    assert_eq!(
-      child_inscriptions_json.children[99].id,
-      hundredth_child_inscription_id
+    server.assert_response_regex(
+      "/runes/search?q=A%E2%80%A2A",
+      StatusCode::OK,
+      ".*<title>Rune Search: A•A</title>.*
+<table>.*
+    <td><a href=/rune/A•AAAAAAAAAAAA>¤ A•AAAAAAAAAAAA</a></td>
+    <td>0</td>
+    <td>1</td>
+    <td>0</td>.*
+    <td><a href=/rune/AAAAAAAAAAAAB>% AAAAAAAAAAAAB</a></td>.*",
     );
-    assert_eq!(child_inscriptions_json.children[99].number, -99); // all but 1st child are cursed
+  }
 
-    assert!(child_inscriptions_json.more);
-    assert_eq!(child_inscriptions_json.page, 0);
+  #[test]
+  fn rune_and_utxo_recursive_endpoints() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
 
-    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
-      "/r/children/{parent_inscription_id}/inscriptions/1"
-    ));
+    server.mine_blocks(1);
 
-    assert_eq!(child_inscriptions_json.children.len(), 11);
+    server.assert_response_regex(
+      format!("/r/rune/{}", Rune(RUNE)),
+      StatusCode::NOT_FOUND,
+      ".*",
+    );
 
-    assert_eq!(
-      child_inscriptions_json.children[0].id,
-      hundred_first_child_inscription_id
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          divisibility: Some(1),
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          terms: Some(Terms {
+            amount: Some(100),
+            cap: Some(10),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
     );
-    assert_eq!(child_inscriptions_json.children[0].number, -100);
 
-    assert_eq!(
-      child_inscriptions_json.children[10].id,
-      hundred_eleventh_child_inscription_id
+    pretty_assert_eq!(
+      server.get_json::<api::RuneRecursive>(format!("/r/rune/{id}")),
+      api::RuneRecursive {
+        block: id.block,
+        burned: 0,
+        divisibility: 1,
+        etching: txid,
+        id,
+        max_supply: 2000,
+        mintable: true,
+        mints: 0,
+        number: 0,
+        premine: 1000,
+        spaced_rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        supply: 1000,
+        symbol: None,
+        terms: Some(Terms {
+          amount: Some(100),
+          cap: Some(10),
+          ..default()
+        }),
+        timestamp: id.block,
+        turbo: false,
+      }
     );
-    assert_eq!(child_inscriptions_json.children[10].number, -110);
 
-    assert!(!child_inscriptions_json.more);
-    assert_eq!(child_inscriptions_json.page, 1);
+    pretty_assert_eq!(
+      server.get_json::<api::UtxoRecursive>(format!("/r/utxo/{txid}:0")),
+      api::UtxoRecursive {
+        inscriptions: Vec::new(),
+        runes: [(
+          SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          Pile {
+            amount: 1000,
+            divisibility: 1,
+            symbol: None,
+          },
+        )]
+        .into_iter()
+        .collect(),
+        sat_ranges: None,
+        value: 5000000000,
+      }
+    );
+
+    server.assert_response_regex(format!("/r/utxo/{txid}:100"), StatusCode::NOT_FOUND, ".*");
   }
 
   #[test]
-  fn inscriptions_in_block_page() {
+  fn address_page_shows_whole_address_and_page_of_outputs() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
+      .ord_flag("--index-addresses")
       .index_sats()
       .build();
 
-    for _ in 0..101 {
-      server.mine_blocks(1);
-    }
+    let address = default_address(Chain::Regtest);
 
-    for i in 0..101 {
-      server.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
-        ..default()
-      });
-    }
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/inscriptions/block/102",
-      StatusCode::OK,
-      r".*(<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*){100}.*",
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}/0")),
+      api::AddressInfo {
+        inscriptions: vec![InscriptionId { txid, index: 0 }],
+        more: false,
+        outputs: vec![OutPoint { txid, vout: 0 }],
+        page: 0,
+        rarities: Some(
+          [(Rarity::Common, 4999999999), (Rarity::Uncommon, 1)]
+            .into_iter()
+            .collect()
+        ),
+        runes_balances: None,
+        sat_balance: 50 * COIN_VALUE,
+        transactions: 1,
+      }
     );
 
-    server.assert_response_regex(
-      "/inscriptions/block/102/1",
-      StatusCode::OK,
-      r".*<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*",
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}/1")),
+      api::AddressInfo {
+        inscriptions: Vec::new(),
+        more: false,
+        outputs: Vec::new(),
+        page: 1,
+        rarities: Some(
+          [(Rarity::Common, 4999999999), (Rarity::Uncommon, 1)]
+            .into_iter()
+            .collect()
+        ),
+        runes_balances: None,
+        sat_balance: 50 * COIN_VALUE,
+        transactions: 1,
+      }
     );
-  }
 
-  #[test]
-  fn inscription_query_display() {
-    assert_eq!(
-      query::Inscription::Id(inscription_id(1)).to_string(),
-      "1111111111111111111111111111111111111111111111111111111111111111i1"
+    server.assert_response_regex(
+      format!("/address/{address}"),
+      StatusCode::OK,
+      ".*<dt>transactions</dt>
+  <dd>1</dd>
+  <dt>sat rarities</dt>
+  <dd><span class=common>common</span>: 4999999999</dd>
+  <dd><span class=uncommon>uncommon</span>: 1</dd>.*",
     );
-    assert_eq!(query::Inscription::Number(1).to_string(), "1")
-  }
-
-  #[test]
-  fn inscription_not_found() {
-    TestServer::builder()
-      .chain(Chain::Regtest)
-      .build()
-      .assert_response(
-        "/inscription/0",
-        StatusCode::NOT_FOUND,
-        "inscription 0 not found",
-      );
   }
 
   #[test]
-  fn looking_up_inscription_by_sat_requires_sat_index() {
-    TestServer::builder()
+  fn address_inscriptions_recursive_endpoint() {
+    let server = TestServer::builder()
       .chain(Chain::Regtest)
-      .build()
-      .assert_response(
-        "/inscription/abcd",
-        StatusCode::NOT_FOUND,
-        "sat index required",
-      );
-  }
+      .ord_flag("--index-addresses")
+      .build();
 
-  #[test]
-  fn delegate() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+    let address = default_address(Chain::Regtest);
 
     server.mine_blocks(1);
 
-    let delegate = Inscription {
-      content_type: Some("text/html".into()),
-      body: Some("foo".into()),
-      ..default()
-    };
+    let mut ids = Vec::new();
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, delegate.to_witness())],
-      ..default()
-    });
+    for i in 0..2 {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/plain", "foo").to_witness())],
+        ..default()
+      });
 
-    let delegate = InscriptionId { txid, index: 0 };
+      server.mine_blocks(1);
 
-    server.mine_blocks(1);
+      ids.push(InscriptionId { txid, index: 0 });
+    }
 
-    let inscription = Inscription {
-      delegate: Some(delegate.value()),
-      ..default()
-    };
+    let mut response =
+      server.get_json::<api::AddressInscriptions>(format!("/r/address/{address}/inscriptions"));
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(2, 0, 0, inscription.to_witness())],
-      ..default()
-    });
+    response.ids.sort();
+    ids.sort();
 
-    server.mine_blocks(1);
+    pretty_assert_eq!(
+      response,
+      api::AddressInscriptions {
+        ids,
+        more: false,
+        page: 0,
+      }
+    );
 
-    let id = InscriptionId { txid, index: 0 };
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInscriptions>(format!("/r/address/{address}/inscriptions/1")),
+      api::AddressInscriptions {
+        ids: Vec::new(),
+        more: false,
+        page: 1,
+      }
+    );
 
     server.assert_response_regex(
-      format!("/inscription/{id}"),
-      StatusCode::OK,
       format!(
-        ".*<h1>Inscription 1</h1>.*
-        <dl>
-          <dt>id</dt>
-          <dd class=monospace>{id}</dd>
-          .*
-          <dt>delegate</dt>
-          <dd><a href=/inscription/{delegate}>{delegate}</a></dd>
-          .*
-        </dl>.*"
-      )
-      .unindent(),
+        "/r/address/{}/inscriptions",
+        default_address(Chain::Mainnet)
+      ),
+      StatusCode::BAD_REQUEST,
+      ".*",
     );
+  }
 
-    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
-
-    server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
+  #[test]
+  fn sat_recursive_endpoints() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
 
     assert_eq!(
-      server
-        .get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}"))
-        .delegate,
-      Some(delegate)
+      server.get_json::<api::SatInscriptions>("/r/sat/5000000000"),
+      api::SatInscriptions {
+        ids: Vec::new(),
+        page: 0,
+        more: false
+      }
     );
-  }
 
-  #[test]
-  fn content_proxy() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+    assert_eq!(
+      server.get_json::<api::SatInscription>("/r/sat/5000000000/at/0"),
+      api::SatInscription { id: None }
+    );
 
     server.mine_blocks(1);
 
-    let inscription = Inscription {
-      content_type: Some("text/html".into()),
-      body: Some("foo".into()),
-      ..default()
-    };
-
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription.to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let id = InscriptionId { txid, index: 0 };
-
-    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
-
-    let server_with_proxy = TestServer::builder()
-      .chain(Chain::Regtest)
-      .server_option("--proxy", server.url.as_ref())
-      .build();
+    let mut ids = Vec::new();
+    ids.push(InscriptionId { txid, index: 0 });
 
-    server_with_proxy.mine_blocks(1);
+    for i in 1..111 {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 1, 0, inscription("text/plain", "foo").to_witness())],
+        ..default()
+      });
 
-    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
-    server_with_proxy.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
-  }
+      server.mine_blocks(1);
 
-  #[test]
-  fn metadata_proxy() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+      ids.push(InscriptionId { txid, index: 0 });
+    }
 
-    server.mine_blocks(1);
+    let paginated_response = server.get_json::<api::SatInscriptions>("/r/sat/5000000000");
 
-    let mut metadata = Vec::new();
-    ciborium::into_writer("bar", &mut metadata).unwrap();
+    let equivalent_paginated_response =
+      server.get_json::<api::SatInscriptions>("/r/sat/5000000000/0");
 
-    let inscription = Inscription {
-      content_type: Some("text/html".into()),
-      body: Some("foo".into()),
-      metadata: Some(metadata.clone()),
-      ..default()
-    };
+    assert_eq!(paginated_response.ids.len(), 100);
+    assert!(paginated_response.more);
+    assert_eq!(paginated_response.page, 0);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription.to_witness())],
-      ..default()
-    });
+    assert_eq!(
+      paginated_response.ids.len(),
+      equivalent_paginated_response.ids.len()
+    );
+    assert_eq!(paginated_response.more, equivalent_paginated_response.more);
+    assert_eq!(paginated_response.page, equivalent_paginated_response.page);
 
-    server.mine_blocks(1);
+    let paginated_response = server.get_json::<api::SatInscriptions>("/r/sat/5000000000/1");
 
-    let id = InscriptionId { txid, index: 0 };
+    assert_eq!(paginated_response.ids.len(), 11);
+    assert!(!paginated_response.more);
+    assert_eq!(paginated_response.page, 1);
 
-    server.assert_response(
-      format!("/r/metadata/{id}"),
-      StatusCode::OK,
-      &format!("\"{}\"", hex::encode(metadata.clone())),
+    assert_eq!(
+      server
+        .get_json::<api::SatInscription>("/r/sat/5000000000/at/0")
+        .id,
+      Some(ids[0])
     );
 
-    let server_with_proxy = TestServer::builder()
-      .chain(Chain::Regtest)
-      .server_option("--proxy", server.url.as_ref())
-      .build();
-
-    server_with_proxy.mine_blocks(1);
+    assert_eq!(
+      server
+        .get_json::<api::SatInscription>("/r/sat/5000000000/at/-111")
+        .id,
+      Some(ids[0])
+    );
 
-    server.assert_response(
-      format!("/r/metadata/{id}"),
-      StatusCode::OK,
-      &format!("\"{}\"", hex::encode(metadata.clone())),
+    assert_eq!(
+      server
+        .get_json::<api::SatInscription>("/r/sat/5000000000/at/110")
+        .id,
+      Some(ids[110])
     );
 
-    server_with_proxy.assert_response(
-      format!("/r/metadata/{id}"),
-      StatusCode::OK,
-      &format!("\"{}\"", hex::encode(metadata.clone())),
+    assert_eq!(
+      server
+        .get_json::<api::SatInscription>("/r/sat/5000000000/at/-1")
+        .id,
+      Some(ids[110])
     );
+
+    assert!(server
+      .get_json::<api::SatInscription>("/r/sat/5000000000/at/111")
+      .id
+      .is_none());
   }
 
   #[test]
-  fn children_proxy() {
+  fn children_recursive_endpoint() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
-
     server.mine_blocks(1);
 
     let parent_txid = server.core.broadcast_tx(TransactionTemplate {
@@ -6674,29 +9771,29 @@ next
       ..default()
     });
 
-    let parent_id = InscriptionId {
+    let parent_inscription_id = InscriptionId {
       txid: parent_txid,
       index: 0,
     };
 
     server.assert_response(
-      format!("/r/children/{parent_id}"),
+      format!("/r/children/{parent_inscription_id}"),
       StatusCode::NOT_FOUND,
-      &format!("inscription {parent_id} not found"),
+      &format!("inscription {parent_inscription_id} not found"),
     );
 
     server.mine_blocks(1);
 
-    let children = server.get_json::<api::Children>(format!("/r/children/{parent_id}"));
-
-    assert_eq!(children.ids.len(), 0);
+    let children_json =
+      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
+    assert_eq!(children_json.ids.len(), 0);
 
     let mut builder = script::Builder::new();
-    for _ in 0..11 {
+    for _ in 0..111 {
       builder = Inscription {
         content_type: Some("text/plain".into()),
         body: Some("hello".into()),
-        parents: vec![parent_id.value()],
+        parents: vec![parent_inscription_id.value()],
         unrecognized_even_field: false,
         ..default()
       }
@@ -6712,139 +9809,593 @@ next
 
     server.mine_blocks(1);
 
-    let first_child_id = InscriptionId { txid, index: 0 };
+    let first_child_inscription_id = InscriptionId { txid, index: 0 };
+    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
+    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
+    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
 
-    let children = server.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+    let children_json =
+      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}"));
 
-    assert_eq!(children.ids.len(), 11);
-    assert_eq!(first_child_id, children.ids[0]);
+    assert_eq!(children_json.ids.len(), 100);
+    assert_eq!(children_json.ids[0], first_child_inscription_id);
+    assert_eq!(children_json.ids[99], hundredth_child_inscription_id);
+    assert!(children_json.more);
+    assert_eq!(children_json.page, 0);
 
-    let server_with_proxy = TestServer::builder()
-      .chain(Chain::Regtest)
-      .server_option("--proxy", server.url.as_ref())
-      .build();
+    let children_json =
+      server.get_json::<api::Children>(format!("/r/children/{parent_inscription_id}/1"));
 
-    server_with_proxy.mine_blocks(1);
+    assert_eq!(children_json.ids.len(), 11);
+    assert_eq!(children_json.ids[0], hundred_first_child_inscription_id);
+    assert_eq!(children_json.ids[10], hundred_eleventh_child_inscription_id);
+    assert!(!children_json.more);
+    assert_eq!(children_json.page, 1);
+  }
 
-    let children = server.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+  #[test]
+  fn parents_recursive_endpoint() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
 
-    assert_eq!(children.ids.len(), 11);
-    assert_eq!(first_child_id, children.ids[0]);
+    let mut parent_ids = Vec::new();
+    let mut inputs = Vec::new();
+    for i in 0..111 {
+      parent_ids.push(InscriptionId {
+        txid: server.core.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
+          ..default()
+        }),
+        index: 0,
+      });
 
-    let children = server_with_proxy.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+      inputs.push((i + 2, 1, 0, Witness::default()));
 
-    assert_eq!(children.ids.len(), 11);
-    assert_eq!(first_child_id, children.ids[0]);
+      server.mine_blocks(1);
+    }
+
+    inputs.insert(
+      0,
+      (
+        112,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          parents: parent_ids.iter().map(|id| id.value()).collect(),
+          ..default()
+        }
+        .to_witness(),
+      ),
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &inputs,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let first_parent_inscription_id = parent_ids[0];
+    let hundredth_parent_inscription_id = parent_ids[99];
+    let hundred_first_parent_inscription_id = parent_ids[100];
+    let hundred_eleventh_parent_inscription_id = parent_ids[110];
+
+    let parents_json = server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}"));
+
+    assert_eq!(parents_json.ids.len(), 100);
+    assert_eq!(parents_json.ids[0], first_parent_inscription_id);
+    assert_eq!(parents_json.ids[99], hundredth_parent_inscription_id);
+    assert!(parents_json.more);
+    assert_eq!(parents_json.page_index, 0);
+
+    let parents_json =
+      server.get_json::<api::Inscriptions>(format!("/r/parents/{inscription_id}/1"));
+
+    assert_eq!(parents_json.ids.len(), 11);
+    assert_eq!(parents_json.ids[0], hundred_first_parent_inscription_id);
+    assert_eq!(parents_json.ids[10], hundred_eleventh_parent_inscription_id);
+    assert!(!parents_json.more);
+    assert_eq!(parents_json.page_index, 1);
   }
 
   #[test]
-  fn inscription_proxy() {
+  fn child_inscriptions_recursive_endpoint() {
     let server = TestServer::builder().chain(Chain::Regtest).build();
-
     server.mine_blocks(1);
 
-    let inscription = Inscription {
-      content_type: Some("text/html".into()),
-      body: Some("foo".into()),
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       ..default()
+    });
+
+    let parent_inscription_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
     };
 
+    server.assert_response(
+      format!("/r/children/{parent_inscription_id}/inscriptions"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {parent_inscription_id} not found"),
+    );
+
+    server.mine_blocks(1);
+
+    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions"
+    ));
+    assert_eq!(child_inscriptions_json.children.len(), 0);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..111 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent_inscription_id.value()],
+        unrecognized_even_field: false,
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
     let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription.to_witness())],
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let id = InscriptionId { txid, index: 0 };
-
-    pretty_assert_eq!(
-      server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
-      api::InscriptionRecursive {
-        charms: Vec::new(),
-        content_type: Some("text/html".into()),
-        content_length: Some(3),
-        delegate: None,
-        fee: 0,
-        height: 2,
-        id,
-        number: 0,
-        output: OutPoint { txid, vout: 0 },
-        sat: None,
-        satpoint: SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0
-        },
-        timestamp: 2,
-        value: Some(50 * COIN_VALUE),
-      }
-    );
+    let first_child_inscription_id = InscriptionId { txid, index: 0 };
+    let hundredth_child_inscription_id = InscriptionId { txid, index: 99 };
+    let hundred_first_child_inscription_id = InscriptionId { txid, index: 100 };
+    let hundred_eleventh_child_inscription_id = InscriptionId { txid, index: 110 };
 
-    let server_with_proxy = TestServer::builder()
-      .chain(Chain::Regtest)
-      .server_option("--proxy", server.url.as_ref())
-      .build();
+    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions"
+    ));
 
-    server_with_proxy.mine_blocks(1);
+    assert_eq!(child_inscriptions_json.children.len(), 100);
 
-    pretty_assert_eq!(
-      server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
-      api::InscriptionRecursive {
-        charms: Vec::new(),
-        content_type: Some("text/html".into()),
-        content_length: Some(3),
-        delegate: None,
-        fee: 0,
-        height: 2,
-        id,
-        number: 0,
-        output: OutPoint { txid, vout: 0 },
-        sat: None,
-        satpoint: SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0
-        },
-        timestamp: 2,
-        value: Some(50 * COIN_VALUE),
-      }
+    assert_eq!(
+      child_inscriptions_json.children[0].id,
+      first_child_inscription_id
     );
+    assert_eq!(child_inscriptions_json.children[0].number, 1); // parent is #0, 1st child is #1
 
     assert_eq!(
-      server_with_proxy.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
-      api::InscriptionRecursive {
-        charms: Vec::new(),
-        content_type: Some("text/html".into()),
-        content_length: Some(3),
-        delegate: None,
-        fee: 0,
-        height: 2,
-        id,
-        number: 0,
-        output: OutPoint { txid, vout: 0 },
-        sat: None,
-        satpoint: SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0
-        },
-        timestamp: 2,
-        value: Some(50 * COIN_VALUE),
-      }
+      child_inscriptions_json.children[99].id,
+      hundredth_child_inscription_id
     );
-  }
+    assert_eq!(child_inscriptions_json.children[99].number, -99); // all but 1st child are cursed
 
-  #[test]
-  fn block_info() {
-    let server = TestServer::new();
+    assert!(child_inscriptions_json.more);
+    assert_eq!(child_inscriptions_json.page, 0);
 
-    pretty_assert_eq!(
-      server.get_json::<api::BlockInfo>("/r/blockinfo/0"),
-      api::BlockInfo {
-        average_fee: 0,
-        average_fee_rate: 0,
-        bits: 486604799,
-        chainwork: [0; 32],
-        confirmations: 0,
-        difficulty: 0.0,
+    let child_inscriptions_json = server.get_json::<api::ChildInscriptions>(format!(
+      "/r/children/{parent_inscription_id}/inscriptions/1"
+    ));
+
+    assert_eq!(child_inscriptions_json.children.len(), 11);
+
+    assert_eq!(
+      child_inscriptions_json.children[0].id,
+      hundred_first_child_inscription_id
+    );
+    assert_eq!(child_inscriptions_json.children[0].number, -100);
+
+    assert_eq!(
+      child_inscriptions_json.children[10].id,
+      hundred_eleventh_child_inscription_id
+    );
+    assert_eq!(child_inscriptions_json.children[10].number, -110);
+
+    assert!(!child_inscriptions_json.more);
+    assert_eq!(child_inscriptions_json.page, 1);
+  }
+
+  #[test]
+  fn inscriptions_in_block_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    for _ in 0..101 {
+      server.mine_blocks(1);
+    }
+
+    for i in 0..101 {
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/foo", "hello").to_witness())],
+        ..default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/inscriptions/block/102",
+      StatusCode::OK,
+      r".*(<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*){100}.*",
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/block/102/1",
+      StatusCode::OK,
+      r".*<a href=/inscription/[[:xdigit:]]{64}i0>.*</a>.*",
+    );
+  }
+
+  #[test]
+  fn inscription_query_display() {
+    assert_eq!(
+      query::Inscription::Id(inscription_id(1)).to_string(),
+      "1111111111111111111111111111111111111111111111111111111111111111i1"
+    );
+    assert_eq!(query::Inscription::Number(1).to_string(), "1")
+  }
+
+  #[test]
+  fn inscription_not_found() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/inscription/0",
+        StatusCode::NOT_FOUND,
+        "inscription 0 not found",
+      );
+  }
+
+  #[test]
+  fn looking_up_inscription_by_sat_requires_sat_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/inscription/abcd",
+        StatusCode::NOT_FOUND,
+        "sat index required",
+      );
+  }
+
+  #[test]
+  fn delegate() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let delegate = Inscription {
+      content_type: Some("text/html".into()),
+      body: Some("foo".into()),
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, delegate.to_witness())],
+      ..default()
+    });
+
+    let delegate = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let inscription = Inscription {
+      delegate: Some(delegate.value()),
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription.to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Inscription 1</h1>.*
+        <dl>
+          <dt>id</dt>
+          <dd class=monospace>{id}</dd>
+          .*
+          <dt>delegate</dt>
+          <dd><a href=/inscription/{delegate}>{delegate}</a></dd>
+          .*
+        </dl>.*"
+      )
+      .unindent(),
+    );
+
+    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
+
+    server.assert_response(format!("/preview/{id}"), StatusCode::OK, "foo");
+
+    assert_eq!(
+      server
+        .get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}"))
+        .delegate,
+      Some(delegate)
+    );
+  }
+
+  #[test]
+  fn content_proxy() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let inscription = Inscription {
+      content_type: Some("text/html".into()),
+      body: Some("foo".into()),
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
+
+    let server_with_proxy = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--proxy", server.url.as_ref())
+      .build();
+
+    server_with_proxy.mine_blocks(1);
+
+    server.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
+    server_with_proxy.assert_response(format!("/content/{id}"), StatusCode::OK, "foo");
+  }
+
+  #[test]
+  fn metadata_proxy() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer("bar", &mut metadata).unwrap();
+
+    let inscription = Inscription {
+      content_type: Some("text/html".into()),
+      body: Some("foo".into()),
+      metadata: Some(metadata.clone()),
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response(
+      format!("/r/metadata/{id}"),
+      StatusCode::OK,
+      &format!("\"{}\"", hex::encode(metadata.clone())),
+    );
+
+    let server_with_proxy = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--proxy", server.url.as_ref())
+      .build();
+
+    server_with_proxy.mine_blocks(1);
+
+    server.assert_response(
+      format!("/r/metadata/{id}"),
+      StatusCode::OK,
+      &format!("\"{}\"", hex::encode(metadata.clone())),
+    );
+
+    server_with_proxy.assert_response(
+      format!("/r/metadata/{id}"),
+      StatusCode::OK,
+      &format!("\"{}\"", hex::encode(metadata.clone())),
+    );
+  }
+
+  #[test]
+  fn children_proxy() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let parent_txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let parent_id = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/r/children/{parent_id}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {parent_id} not found"),
+    );
+
+    server.mine_blocks(1);
+
+    let children = server.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+
+    assert_eq!(children.ids.len(), 0);
+
+    let mut builder = script::Builder::new();
+    for _ in 0..11 {
+      builder = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
+        parents: vec![parent_id.value()],
+        unrecognized_even_field: false,
+        ..default()
+      }
+      .append_reveal_script_to_builder(builder);
+    }
+
+    let witness = Witness::from_slice(&[builder.into_bytes(), Vec::new()]);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, witness), (2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let first_child_id = InscriptionId { txid, index: 0 };
+
+    let children = server.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+
+    assert_eq!(children.ids.len(), 11);
+    assert_eq!(first_child_id, children.ids[0]);
+
+    let server_with_proxy = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--proxy", server.url.as_ref())
+      .build();
+
+    server_with_proxy.mine_blocks(1);
+
+    let children = server.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+
+    assert_eq!(children.ids.len(), 11);
+    assert_eq!(first_child_id, children.ids[0]);
+
+    let children = server_with_proxy.get_json::<api::Children>(format!("/r/children/{parent_id}"));
+
+    assert_eq!(children.ids.len(), 11);
+    assert_eq!(first_child_id, children.ids[0]);
+  }
+
+  #[test]
+  fn inscription_proxy() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let inscription = Inscription {
+      content_type: Some("text/html".into()),
+      body: Some("foo".into()),
+      ..default()
+    };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription.to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
+      api::InscriptionRecursive {
+        charms: Vec::new(),
+        content_type: Some("text/html".into()),
+        content_length: Some(3),
+        delegate: None,
+        fee: 0,
+        height: 2,
+        id,
+        number: 0,
+        output: OutPoint { txid, vout: 0 },
+        sat: None,
+        satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0
+        },
+        timestamp: 2,
+        value: Some(50 * COIN_VALUE),
+      }
+    );
+
+    let server_with_proxy = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--proxy", server.url.as_ref())
+      .build();
+
+    server_with_proxy.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
+      api::InscriptionRecursive {
+        charms: Vec::new(),
+        content_type: Some("text/html".into()),
+        content_length: Some(3),
+        delegate: None,
+        fee: 0,
+        height: 2,
+        id,
+        number: 0,
+        output: OutPoint { txid, vout: 0 },
+        sat: None,
+        satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0
+        },
+        timestamp: 2,
+        value: Some(50 * COIN_VALUE),
+      }
+    );
+
+    assert_eq!(
+      server_with_proxy.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
+      api::InscriptionRecursive {
+        charms: Vec::new(),
+        content_type: Some("text/html".into()),
+        content_length: Some(3),
+        delegate: None,
+        fee: 0,
+        height: 2,
+        id,
+        number: 0,
+        output: OutPoint { txid, vout: 0 },
+        sat: None,
+        satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0
+        },
+        timestamp: 2,
+        value: Some(50 * COIN_VALUE),
+      }
+    );
+  }
+
+  #[test]
+  fn block_info() {
+    let server = TestServer::new();
+
+    pretty_assert_eq!(
+      server.get_json::<api::BlockInfo>("/r/blockinfo/0"),
+      api::BlockInfo {
+        average_fee: 0,
+        average_fee_rate: 0,
+        bits: 486604799,
+        chainwork: [0; 32],
+        confirmations: 0,
+        difficulty: 0.0,
         hash: "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
           .parse()
           .unwrap(),
@@ -6876,86 +10427,1073 @@ next
 
     server.mine_blocks(1);
 
-    pretty_assert_eq!(
-      server.get_json::<api::BlockInfo>("/r/blockinfo/1"),
-      api::BlockInfo {
-        average_fee: 0,
-        average_fee_rate: 0,
-        bits: 0,
-        chainwork: [0; 32],
-        confirmations: 0,
-        difficulty: 0.0,
-        hash: "56d05060a0280d0712d113f25321158747310ece87ea9e299bde06cf385b8d85"
-          .parse()
-          .unwrap(),
-        feerate_percentiles: [0, 0, 0, 0, 0],
-        height: 1,
-        max_fee: 0,
-        max_fee_rate: 0,
-        max_tx_size: 0,
-        median_fee: 0,
-        median_time: None,
-        merkle_root: TxMerkleNode::all_zeros(),
-        min_fee: 0,
-        min_fee_rate: 0,
-        next_block: None,
-        nonce: 0,
-        previous_block: None,
-        subsidy: 0,
-        target: BlockHash::all_zeros(),
-        timestamp: 0,
-        total_fee: 0,
-        total_size: 0,
-        total_weight: 0,
-        transaction_count: 0,
-        version: 1,
-      },
-    )
+    pretty_assert_eq!(
+      server.get_json::<api::BlockInfo>("/r/blockinfo/1"),
+      api::BlockInfo {
+        average_fee: 0,
+        average_fee_rate: 0,
+        bits: 0,
+        chainwork: [0; 32],
+        confirmations: 0,
+        difficulty: 0.0,
+        hash: "56d05060a0280d0712d113f25321158747310ece87ea9e299bde06cf385b8d85"
+          .parse()
+          .unwrap(),
+        feerate_percentiles: [0, 0, 0, 0, 0],
+        height: 1,
+        max_fee: 0,
+        max_fee_rate: 0,
+        max_tx_size: 0,
+        median_fee: 0,
+        median_time: None,
+        merkle_root: TxMerkleNode::all_zeros(),
+        min_fee: 0,
+        min_fee_rate: 0,
+        next_block: None,
+        nonce: 0,
+        previous_block: None,
+        subsidy: 0,
+        target: BlockHash::all_zeros(),
+        timestamp: 0,
+        total_fee: 0,
+        total_size: 0,
+        total_weight: 0,
+        transaction_count: 0,
+        version: 1,
+      },
+    )
+  }
+
+  #[test]
+  fn authentication_requires_username_and_password() {
+    assert!(Arguments::try_parse_from(["ord", "--server-username", "server", "foo"]).is_err());
+    assert!(Arguments::try_parse_from(["ord", "--server-password", "server", "bar"]).is_err());
+    assert!(Arguments::try_parse_from([
+      "ord",
+      "--server-username",
+      "foo",
+      "--server-password",
+      "bar",
+      "server"
+    ])
+    .is_ok());
+  }
+
+  #[test]
+  fn inscriptions_can_be_hidden_with_config() {
+    let core = mockcore::builder()
+      .network(Chain::Regtest.network())
+      .build();
+
+    core.mine_blocks(1);
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let inscription = InscriptionId { txid, index: 0 };
+
+    let server = TestServer::builder()
+      .core(core)
+      .config(&format!("hidden: [{inscription}]"))
+      .build();
+
+    server.assert_response_regex(format!("/inscription/{inscription}"), StatusCode::OK, ".*");
+
+    server.assert_response_regex(
+      format!("/content/{inscription}"),
+      StatusCode::OK,
+      PreviewUnknownHtml.to_string(),
+    );
+  }
+
+  #[test]
+  fn inscriptions_can_be_hidden_at_runtime() {
+    let tempdir = TempDir::new().unwrap();
+
+    let hidden_file = tempdir.path().join("hidden.json");
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .config(
+        "
+access:
+  api_keys:
+    moderator:
+      admin: true
+      key: foo
+",
+      )
+      .server_option("--hidden-file", hidden_file.to_str().unwrap())
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "parent").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (
+            2,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("child".into()),
+              parents: vec![parent.value()],
+              ..default()
+            }
+            .to_witness(),
+          ),
+          (2, 1, 0, Default::default()),
+        ],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let client = reqwest::blocking::Client::new();
+
+    let admin = |method: reqwest::Method, hidden: serde_json::Value| {
+      let response = client
+        .request(method, server.join_url("/admin/hidden"))
+        .header(access::API_KEY_HEADER, "foo")
+        .json(&hidden)
+        .send()
+        .unwrap();
+
+      assert_eq!(response.status(), StatusCode::OK);
+
+      response.json::<Hidden>().unwrap()
+    };
+
+    let assert_hidden = |id: InscriptionId, hidden: bool| {
+      let body = server.get(format!("/content/{id}")).text().unwrap();
+
+      assert_eq!(
+        body == PreviewUnknownHtml.to_string(),
+        hidden,
+        "inscription {id}"
+      );
+    };
+
+    assert_hidden(parent, false);
+    assert_hidden(child, false);
+
+    admin(
+      reqwest::Method::POST,
+      serde_json::json!({ "inscriptions": [parent] }),
+    );
+
+    assert_hidden(parent, true);
+    assert_hidden(child, false);
+
+    server.assert_response_regex(
+      format!("/preview/{parent}"),
+      StatusCode::OK,
+      PreviewUnknownHtml.to_string(),
+    );
+
+    admin(
+      reqwest::Method::POST,
+      serde_json::json!({ "parents": [parent] }),
+    );
+
+    assert_hidden(child, true);
+
+    pretty_assert_eq!(
+      admin(
+        reqwest::Method::DELETE,
+        serde_json::json!({ "inscriptions": [parent], "parents": [parent] }),
+      ),
+      Hidden::default(),
+    );
+
+    assert_hidden(parent, false);
+    assert_hidden(child, false);
+
+    let hash = sha256::Hash::hash(b"child");
+
+    let hidden = admin(
+      reqwest::Method::POST,
+      serde_json::json!({ "content_hashes": [hash] }),
+    );
+
+    assert_eq!(hidden.content_hashes, [hash].into());
+
+    assert_hidden(child, true);
+
+    assert_eq!(
+      serde_json::from_str::<Hidden>(&fs::read_to_string(&hidden_file).unwrap()).unwrap(),
+      hidden,
+    );
+
+    fs::write(
+      &hidden_file,
+      serde_json::to_string(&Hidden {
+        inscriptions: [parent].into(),
+        ..default()
+      })
+      .unwrap(),
+    )
+    .unwrap();
+
+    for attempt in 0.. {
+      if server.get(format!("/content/{parent}")).text().unwrap() == PreviewUnknownHtml.to_string()
+      {
+        break;
+      }
+
+      assert!(attempt < 100, "hidden file was not reloaded");
+
+      thread::sleep(Duration::from_millis(100));
+    }
+
+    assert_hidden(child, false);
+  }
+
+  #[test]
+  fn hidden_inscriptions_are_not_listed() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .config(
+        "
+access:
+  api_keys:
+    moderator:
+      admin: true
+      key: foo
+",
+      )
+      .build();
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "parent").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let child = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[
+          (
+            2,
+            0,
+            0,
+            Inscription {
+              content_type: Some("text/plain".into()),
+              body: Some("child".into()),
+              parents: vec![parent.value()],
+              ..default()
+            }
+            .to_witness(),
+          ),
+          (2, 1, 0, Default::default()),
+        ],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions").ids,
+      [child, parent],
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Children>(format!("/r/children/{parent}"))
+        .ids,
+      [child],
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/admin/hidden"))
+      .header(access::API_KEY_HEADER, "foo")
+      .json(&serde_json::json!({ "inscriptions": [child] }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert_eq!(
+      server.get_json::<api::Inscriptions>("/inscriptions").ids,
+      [parent],
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscriptions>(format!("/children/{parent}"))
+        .ids,
+      Vec::new(),
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Children>(format!("/r/children/{parent}"))
+        .ids,
+      Vec::new(),
+    );
+
+    server.assert_response(
+      format!("/r/inscription/{child}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {child} not found"),
+    );
+
+    server.assert_response_regex(format!("/r/inscription/{parent}"), StatusCode::OK, ".*");
+  }
+
+  #[test]
+  fn admin_api_requires_admin_api_key() {
+    let status = |server: &TestServer, key: Option<&str>| {
+      let mut request = reqwest::blocking::Client::new().get(server.join_url("/admin/hidden"));
+
+      if let Some(key) = key {
+        request = request.header(access::API_KEY_HEADER, key);
+      }
+
+      request.send().unwrap().status()
+    };
+
+    let server = TestServer::new();
+
+    assert_eq!(status(&server, None), StatusCode::UNAUTHORIZED);
+    assert_eq!(status(&server, Some("foo")), StatusCode::UNAUTHORIZED);
+
+    let server = TestServer::builder()
+      .config(
+        "
+access:
+  api_keys:
+    moderator:
+      admin: true
+      key: foo
+    partner:
+      key: bar
+",
+      )
+      .build();
+
+    assert_eq!(status(&server, None), StatusCode::UNAUTHORIZED);
+    assert_eq!(status(&server, Some("bar")), StatusCode::UNAUTHORIZED);
+    assert_eq!(status(&server, Some("baz")), StatusCode::UNAUTHORIZED);
+    assert_eq!(status(&server, Some("foo")), StatusCode::OK);
+  }
+
+  #[test]
+  fn protected_routes_require_api_key() {
+    let server = TestServer::builder()
+      .config(
+        "
+access:
+  api_keys:
+    foo:
+      key: bar
+  protected:
+  - json
+",
+      )
+      .build();
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .get(server.join_url("/status"))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+      .get(server.join_url("/status"))
+      .header(header::ACCEPT, "application/json")
+      .header(access::API_KEY_HEADER, "baz")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.text().unwrap(), "invalid API key");
+
+    let response = client
+      .get(server.join_url("/status"))
+      .header(header::ACCEPT, "application/json")
+      .header(access::API_KEY_HEADER, "bar")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    server.assert_response_regex("/status", StatusCode::OK, ".*<h1>Status</h1>.*");
+    server.assert_response("/r/blockheight", StatusCode::OK, "0");
+    server.assert_response(
+      format!("/content/{}", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn requests_over_rate_limit_are_rejected() {
+    let server = TestServer::builder()
+      .config(
+        "
+access:
+  api_keys:
+    foo:
+      key: bar
+      rate_limits:
+        recursive: 1/h
+  rate_limits:
+    ip:
+      recursive: 2/h
+",
+      )
+      .build();
+
+    server.assert_response("/r/blockheight", StatusCode::OK, "0");
+    server.assert_response("/r/blockheight", StatusCode::OK, "0");
+
+    let response = server.get("/r/blockheight");
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+      response.headers().get(header::CACHE_CONTROL).unwrap(),
+      "no-store"
+    );
+
+    let retry_after = response
+      .headers()
+      .get(header::RETRY_AFTER)
+      .unwrap()
+      .to_str()
+      .unwrap()
+      .parse::<u64>()
+      .unwrap();
+
+    assert!(retry_after > 1700 && retry_after <= 1800, "{retry_after}");
+
+    let client = reqwest::blocking::Client::new();
+
+    let request = || {
+      client
+        .get(server.join_url("/r/blockheight"))
+        .header(access::API_KEY_HEADER, "bar")
+        .send()
+        .unwrap()
+        .status()
+    };
+
+    assert_eq!(request(), StatusCode::OK);
+    assert_eq!(request(), StatusCode::TOO_MANY_REQUESTS);
+
+    server.assert_response_regex("/status", StatusCode::OK, ".*");
+  }
+
+  #[test]
+  fn graphql_is_disabled_by_default() {
+    let server = TestServer::builder().build();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/graphql"))
+      .json(&serde_json::json!({ "query": "{ sat(query: \"0\") { name } }" }))
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.text().unwrap(), "GraphQL API disabled");
+  }
+
+  #[test]
+  fn graphql_resolves_relationships() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .server_flag("--enable-graphql")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.graphql(&format!(
+        r#"{{
+          inscription(query: "{id}") {{
+            id
+            number
+            contentType
+            block {{ height }}
+            sat {{ name inscriptions {{ id }} }}
+            output {{ outpoint value inscriptions {{ number }} }}
+            transaction {{ txid inscriptionCount }}
+          }}
+        }}"#
+      )),
+      serde_json::json!({
+        "data": {
+          "inscription": {
+            "id": id.to_string(),
+            "number": 0,
+            "contentType": "text/foo",
+            "block": { "height": 2 },
+            "sat": {
+              "name": Sat(50 * COIN_VALUE).name(),
+              "inscriptions": [{ "id": id.to_string() }],
+            },
+            "output": {
+              "outpoint": OutPoint { txid, vout: 0 }.to_string(),
+              "value": 50 * COIN_VALUE,
+              "inscriptions": [{ "number": 0 }],
+            },
+            "transaction": { "txid": txid.to_string(), "inscriptionCount": 1 },
+          }
+        }
+      })
+    );
+
+    pretty_assert_eq!(
+      server.graphql(r#"{ block(query: "2") { transactions(limit: 1, page: 1) { txid } } }"#),
+      serde_json::json!({
+        "data": { "block": { "transactions": [{ "txid": txid.to_string() }] } }
+      })
+    );
+
+    pretty_assert_eq!(
+      server.graphql(r#"{ inscription(query: "1") { id } }"#),
+      serde_json::json!({ "data": { "inscription": null } })
+    );
+  }
+
+  #[test]
+  fn graphql_resolves_runes() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .server_flag("--enable-graphql")
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          symbol: Some('$'),
+          premine: Some(u128::MAX),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    pretty_assert_eq!(
+      server.graphql(&format!(
+        r#"{{
+          transaction(txid: "{txid}") {{
+            etching {{ id name supply symbol mintable }}
+            outputs(limit: 1) {{ runes {{ rune amount }} }}
+          }}
+        }}"#
+      )),
+      serde_json::json!({
+        "data": {
+          "transaction": {
+            "etching": {
+              "id": id.to_string(),
+              "name": "AAAAAAAAAAAAA",
+              "supply": u128::MAX.to_string(),
+              "symbol": "$",
+              "mintable": false,
+            },
+            "outputs": [{
+              "runes": [{ "rune": "AAAAAAAAAAAAA", "amount": u128::MAX.to_string() }]
+            }],
+          }
+        }
+      })
+    );
+  }
+
+  #[test]
+  fn graphql_queries_are_limited() {
+    let server = TestServer::builder()
+      .server_flag("--enable-graphql")
+      .server_option("--graphql-max-depth", "3")
+      .build();
+
+    let errors = |query: &str| {
+      server.graphql(query)["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["message"].as_str().unwrap().to_string())
+        .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+      server.graphql(r#"{ block(query: "0") { previous { height } } }"#),
+      serde_json::json!({ "data": { "block": { "previous": null } } })
+    );
+
+    assert_eq!(
+      errors(r#"{ block(query: "0") { previous { previous { height } } } }"#),
+      ["Query is nested too deep."]
+    );
+
+    assert_eq!(
+      errors(r#"{ block(query: "0") { transactions { outputs { txid: outpoint } } } }"#),
+      ["Query is too complex."]
+    );
+
+    assert_eq!(
+      errors(r#"{ block(query: "0") { transactions(limit: 101) { txid } } }"#),
+      ["limit must be between 1 and 100"]
+    );
+  }
+
+  #[test]
+  fn batch_lookups_report_failures_per_item() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let sats = server
+      .post_json::<Vec<api::BatchResult<api::Sat>>>(
+        "/sats",
+        &serde_json::json!([0, "nvtdijuwxlp", "0.0.0"]),
+      )
+      .unwrap();
+
+    assert_eq!(sats.len(), 3);
+    assert_matches!(&sats[0], api::BatchResult::Ok(api::Sat { number: 0, .. }));
+    assert_matches!(&sats[1], api::BatchResult::Ok(api::Sat { number: 0, .. }));
+    assert_matches!(&sats[2], api::BatchResult::Error(message) if message.starts_with("invalid sat `0.0.0`"));
+
+    let inscriptions = server
+      .post_json::<Vec<api::BatchResult<api::Inscription>>>(
+        "/inscriptions/numbers",
+        &serde_json::json!([0, 1]),
+      )
+      .unwrap();
+
+    assert_matches!(
+      &inscriptions[0],
+      api::BatchResult::Ok(api::Inscription { number: 0, id, .. }) if id.txid == txid
+    );
+    assert_eq!(
+      inscriptions[1],
+      api::BatchResult::Error("inscription 1 not found".into())
+    );
+
+    let transactions = server
+      .post_json::<Vec<api::BatchResult<api::Transaction>>>(
+        "/txs",
+        &serde_json::json!([txid, Txid::all_zeros()]),
+      )
+      .unwrap();
+
+    assert_matches!(
+      &transactions[0],
+      api::BatchResult::Ok(api::Transaction {
+        inscription_count: 1,
+        ..
+      })
+    );
+    assert_eq!(
+      transactions[1],
+      api::BatchResult::Error(format!("transaction {} not found", Txid::all_zeros()))
+    );
+
+    let blocks = server
+      .post_json::<Vec<api::BatchResult<api::Block>>>("/blocks", &serde_json::json!([2, 3]))
+      .unwrap();
+
+    assert_matches!(
+      &blocks[0],
+      api::BatchResult::Ok(api::Block { height: 2, .. })
+    );
+    assert_eq!(
+      blocks[1],
+      api::BatchResult::Error("block 3 not found".into())
+    );
   }
 
   #[test]
-  fn authentication_requires_username_and_password() {
-    assert!(Arguments::try_parse_from(["ord", "--server-username", "server", "foo"]).is_err());
-    assert!(Arguments::try_parse_from(["ord", "--server-password", "server", "bar"]).is_err());
-    assert!(Arguments::try_parse_from([
-      "ord",
-      "--server-username",
-      "foo",
-      "--server-password",
-      "bar",
-      "server"
-    ])
-    .is_ok());
+  fn decode_previews_transaction() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_sats()
+      .build();
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: 1000,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let [(start, end)] = server
+      .index
+      .list(OutPoint { txid, vout: 0 })
+      .unwrap()
+      .unwrap()[..]
+    else {
+      panic!("expected a single sat range");
+    };
+
+    assert_eq!(end - start, 5000000000);
+
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint { txid, vout: 0 },
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: inscription("text/plain", "foo").to_witness(),
+      }],
+      output: vec![
+        TxOut {
+          value: 1000,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 4999998000,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 0,
+          script_pubkey: Runestone {
+            edicts: vec![Edict {
+              id,
+              amount: 400,
+              output: 1,
+            }],
+            ..default()
+          }
+          .encipher(),
+        },
+      ],
+    };
+
+    let preview = server
+      .post_json::<api::DecodePreview>(
+        "/decode",
+        &serde_json::Value::String(consensus::encode::serialize_hex(&transaction)),
+      )
+      .unwrap();
+
+    let pile = |amount| {
+      [(
+        SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        Pile {
+          amount,
+          divisibility: 0,
+          symbol: None,
+        },
+      )]
+      .into_iter()
+      .collect()
+    };
+
+    assert_eq!(preview.inscriptions.len(), 1);
+
+    pretty_assert_eq!(
+      preview.simulation,
+      api::Simulation {
+        burned: BTreeMap::new(),
+        fee: 1000,
+        fee_inscriptions: Vec::new(),
+        fee_sat_ranges: Some(vec![(start + 4999999000, end)]),
+        outputs: vec![
+          api::SimulatedOutput {
+            inscriptions: vec![InscriptionId {
+              txid: transaction.txid(),
+              index: 0,
+            }],
+            runes: pile(600),
+            sat_ranges: Some(vec![(start, start + 1000)]),
+            value: 1000,
+          },
+          api::SimulatedOutput {
+            inscriptions: Vec::new(),
+            runes: pile(400),
+            sat_ranges: Some(vec![(start + 1000, start + 4999999000)]),
+            value: 4999998000,
+          },
+          api::SimulatedOutput {
+            inscriptions: Vec::new(),
+            runes: BTreeMap::new(),
+            sat_ranges: Some(Vec::new()),
+            value: 0,
+          },
+        ],
+      }
+    );
+
+    let cenotaph = Transaction {
+      input: vec![TxIn {
+        witness: Witness::new(),
+        ..transaction.input[0].clone()
+      }],
+      output: vec![
+        transaction.output[0].clone(),
+        TxOut {
+          value: 0,
+          script_pubkey: vec![0x6a, 0x5d, 0x01, 0xff].into(),
+        },
+      ],
+      ..transaction
+    };
+
+    let psbt = base64::engine::general_purpose::STANDARD
+      .encode(Psbt::from_unsigned_tx(cenotaph).unwrap().serialize());
+
+    let preview = server
+      .post_json::<api::DecodePreview>("/decode", &serde_json::Value::String(psbt))
+      .unwrap();
+
+    assert_matches!(preview.runestone, Some(Artifact::Cenotaph(_)));
+    assert_eq!(preview.simulation.burned, pile(1000));
+    assert_eq!(preview.simulation.fee, 4999999000);
+    assert_eq!(preview.simulation.outputs[0].runes, BTreeMap::new());
+
+    let mut psbt = Psbt::from_unsigned_tx(Transaction {
+      input: vec![TxIn {
+        witness: Witness::new(),
+        ..transaction.input[0].clone()
+      }],
+      output: vec![transaction.output[0].clone()],
+      ..transaction.clone()
+    })
+    .unwrap();
+
+    psbt.inputs[0].witness_utxo = Some(TxOut {
+      value: 10000000000,
+      script_pubkey: ScriptBuf::new(),
+    });
+
+    assert_eq!(
+      server.post_json::<api::DecodePreview>(
+        "/decode",
+        &serde_json::Value::String(
+          base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+        ),
+      ),
+      Err((
+        StatusCode::BAD_REQUEST,
+        format!("PSBT value of input {txid}:0 is 10000000000, but output has value 5000000000"),
+      )),
+    );
+
+    let overflow = Transaction {
+      output: vec![
+        TxOut {
+          value: u64::MAX,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 1,
+          script_pubkey: ScriptBuf::new(),
+        },
+      ],
+      ..transaction.clone()
+    };
+
+    assert_eq!(
+      server.post_json::<api::DecodePreview>(
+        "/decode",
+        &serde_json::Value::String(consensus::encode::serialize_hex(&overflow)),
+      ),
+      Err((
+        StatusCode::BAD_REQUEST,
+        "transaction output value overflows".into(),
+      )),
+    );
+
+    assert_eq!(
+      server.post_json::<api::DecodePreview>("/decode", &serde_json::Value::String("foo".into())),
+      Err((
+        StatusCode::BAD_REQUEST,
+        "expected hex-encoded transaction or PSBT, or base64-encoded PSBT: Invalid padding".into()
+      )),
+    );
   }
 
   #[test]
-  fn inscriptions_can_be_hidden_with_config() {
-    let core = mockcore::builder()
-      .network(Chain::Regtest.network())
+  fn decode_simulates_etchings_with_indexing_rules() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
       .build();
 
-    core.mine_blocks(1);
+    server.mine_blocks(1);
 
-    let txid = core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    let commit = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      p2tr: true,
       ..default()
     });
 
-    core.mine_blocks(1);
+    server.mine_blocks(Runestone::COMMIT_CONFIRMATIONS.into());
 
-    let inscription = InscriptionId { txid, index: 0 };
+    let simulate = |rune: Option<Rune>, committed: Rune| {
+      let tapscript = script::Builder::new()
+        .push_slice::<&PushBytes>(committed.commitment().as_slice().try_into().unwrap())
+        .into_script();
+
+      let mut witness = Witness::default();
+      witness.push(tapscript);
+      witness.push([]);
+
+      let transaction = Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+          previous_output: OutPoint {
+            txid: commit,
+            vout: 0,
+          },
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::MAX,
+          witness,
+        }],
+        output: vec![
+          TxOut {
+            value: 1000,
+            script_pubkey: ScriptBuf::new(),
+          },
+          TxOut {
+            value: 0,
+            script_pubkey: Runestone {
+              etching: Some(Etching {
+                rune,
+                premine: Some(1000),
+                ..default()
+              }),
+              ..default()
+            }
+            .encipher(),
+          },
+        ],
+      };
 
+      server
+        .post_json::<api::DecodePreview>(
+          "/decode",
+          &serde_json::Value::String(consensus::encode::serialize_hex(&transaction)),
+        )
+        .unwrap()
+        .simulation
+        .outputs
+        .remove(0)
+        .runes
+    };
+
+    let pile = |rune| {
+      BTreeMap::from([(
+        SpacedRune { rune, spacers: 0 },
+        Pile {
+          amount: 1000,
+          divisibility: 0,
+          symbol: None,
+        },
+      )])
+    };
+
+    assert_eq!(simulate(Some(Rune(RUNE)), Rune(RUNE)), pile(Rune(RUNE)));
+
+    assert_eq!(simulate(Some(Rune(RUNE)), Rune(RUNE + 1)), BTreeMap::new());
+
+    let height = server.index.block_count().unwrap();
+
+    assert_eq!(
+      simulate(None, Rune(RUNE + 1)),
+      pile(Rune::reserved(height.into(), u32::MAX))
+    );
+  }
+
+  #[test]
+  fn batch_lookups_are_limited() {
     let server = TestServer::builder()
-      .core(core)
-      .config(&format!("hidden: [{inscription}]"))
+      .server_option("--max-batch-size", "2")
       .build();
 
-    server.assert_response_regex(format!("/inscription/{inscription}"), StatusCode::OK, ".*");
+    assert_eq!(
+      server
+        .post_json::<serde_json::Value>("/sats", &serde_json::json!([0, 1, 2]))
+        .unwrap_err(),
+      (
+        StatusCode::BAD_REQUEST,
+        "batch requests may contain at most 2 items".into()
+      )
+    );
 
-    server.assert_response_regex(
-      format!("/content/{inscription}"),
-      StatusCode::OK,
-      PreviewUnknownHtml.to_string(),
+    assert_eq!(
+      server
+        .post_json::<Vec<api::BatchResult<api::Sat>>>("/sats", &serde_json::json!([0, 1]))
+        .unwrap()
+        .len(),
+      2
     );
   }
 
@@ -0,0 +1,515 @@
+use {
+  super::*,
+  axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderName, Request},
+    middleware::Next,
+  },
+  std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroU32,
+    sync::Mutex,
+    time::Instant,
+  },
+};
+
+pub(crate) const API_KEY_HEADER: &str = "x-api-key";
+
+// limiter state is only pruned once it holds this many clients
+const PRUNE_THRESHOLD: usize = 100_000;
+
+// Access control settings for `ord server`, which are only configurable with
+// the configuration file.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Access {
+  pub api_keys: BTreeMap<String, ApiKey>,
+  pub client_ip_header: Option<String>,
+  pub protected: BTreeSet<RouteGroup>,
+  pub rate_limits: RateLimits,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+  #[serde(default)]
+  pub admin: bool,
+  pub key: String,
+  #[serde(default)]
+  pub rate_limits: BTreeMap<RouteGroup, Rate>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimits {
+  pub ip: BTreeMap<RouteGroup, Rate>,
+  pub key: BTreeMap<RouteGroup, Rate>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteGroup {
+  Content,
+  Html,
+  Json,
+  Recursive,
+}
+
+impl RouteGroup {
+  fn of<B>(request: &Request<B>) -> Self {
+    let path = request.uri().path();
+
+    if path.starts_with("/r/") {
+      Self::Recursive
+    } else if path.starts_with("/content/") {
+      Self::Content
+    } else if request.method() == http::Method::POST
+      || path == "/openapi.json"
+      || request
+        .headers()
+        .get(header::ACCEPT)
+        .is_some_and(|accept| accept == "application/json")
+    {
+      Self::Json
+    } else {
+      Self::Html
+    }
+  }
+}
+
+// A token bucket holding up to `requests` tokens, which is refilled at a
+// rate of `requests` tokens per `period`.
+#[derive(Debug, Copy, Clone, PartialEq, DeserializeFromStr, SerializeDisplay)]
+pub struct Rate {
+  requests: NonZeroU32,
+  period: Duration,
+}
+
+impl Rate {
+  fn interval(self) -> Duration {
+    self.period / self.requests.get()
+  }
+}
+
+impl Display for Rate {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let unit = match self.period.as_secs() {
+      1 => "s",
+      60 => "m",
+      3600 => "h",
+      _ => "d",
+    };
+
+    write!(f, "{}/{unit}", self.requests)
+  }
+}
+
+impl FromStr for Rate {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (requests, unit) = s
+      .split_once('/')
+      .with_context(|| format!("invalid rate `{s}`, expected `<REQUESTS>/<s|m|h|d>`"))?;
+
+    let period = match unit.trim() {
+      "s" => Duration::from_secs(1),
+      "m" => Duration::from_secs(60),
+      "h" => Duration::from_secs(60 * 60),
+      "d" => Duration::from_secs(24 * 60 * 60),
+      unit => bail!("invalid rate period `{unit}`, expected `s`, `m`, `h`, or `d`"),
+    };
+
+    Ok(Self {
+      requests: requests
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid rate request count `{requests}`"))?,
+      period,
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Client {
+  Ip(IpAddr),
+  Key(String),
+}
+
+struct Key {
+  admin: bool,
+  name: String,
+  rate_limits: BTreeMap<RouteGroup, Rate>,
+}
+
+pub(crate) struct AccessControl {
+  client_ip_header: Option<HeaderName>,
+  ip_limits: BTreeMap<RouteGroup, Rate>,
+  keys: HashMap<String, Key>,
+  limiter: Limiter,
+  protected: BTreeSet<RouteGroup>,
+}
+
+impl AccessControl {
+  pub(crate) fn new(access: &Access) -> Result<Self> {
+    let mut keys = HashMap::new();
+
+    for (name, api_key) in &access.api_keys {
+      ensure!(!api_key.key.is_empty(), "API key `{name}` is empty");
+
+      let mut rate_limits = access.rate_limits.key.clone();
+      rate_limits.extend(api_key.rate_limits.clone());
+
+      ensure!(
+        keys
+          .insert(
+            api_key.key.clone(),
+            Key {
+              admin: api_key.admin,
+              name: name.clone(),
+              rate_limits,
+            }
+          )
+          .is_none(),
+        "API key `{name}` is not unique"
+      );
+    }
+
+    ensure!(
+      access.protected.is_empty() || !keys.is_empty(),
+      "route groups are protected but no API keys are configured"
+    );
+
+    Ok(Self {
+      client_ip_header: access
+        .client_ip_header
+        .as_deref()
+        .map(HeaderName::from_str)
+        .transpose()
+        .context("invalid client IP header")?,
+      ip_limits: access.rate_limits.ip.clone(),
+      keys,
+      limiter: Limiter::default(),
+      protected: access.protected.clone(),
+    })
+  }
+
+  fn client_ip<B>(&self, request: &Request<B>) -> IpAddr {
+    // with a single trusted proxy, the last address it appends is the only
+    // one which cannot be forged by the client
+    let forwarded = self.client_ip_header.as_ref().and_then(|header| {
+      request
+        .headers()
+        .get(header)?
+        .to_str()
+        .ok()?
+        .rsplit(',')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+    });
+
+    forwarded
+      .or_else(|| {
+        request
+          .extensions()
+          .get::<ConnectInfo<SocketAddr>>()
+          .map(|ConnectInfo(address)| address.ip())
+      })
+      .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+  }
+
+  pub(crate) async fn middleware<B>(
+    State(access_control): State<Option<Arc<Self>>>,
+    request: Request<B>,
+    next: Next<B>,
+  ) -> Response {
+    let admin = request.uri().path().starts_with("/admin/");
+
+    let admin_key_required = || {
+      (
+        StatusCode::UNAUTHORIZED,
+        format!("admin API key required, set the `{API_KEY_HEADER}` header"),
+      )
+        .into_response()
+    };
+
+    let Some(access_control) = access_control else {
+      if admin {
+        return admin_key_required();
+      }
+
+      return next.run(request).await;
+    };
+
+    let group = RouteGroup::of(&request);
+
+    let (client, limits) = match request.headers().get(API_KEY_HEADER) {
+      Some(key) => {
+        let Some(key) = key
+          .to_str()
+          .ok()
+          .and_then(|key| access_control.keys.get(key))
+        else {
+          return (StatusCode::UNAUTHORIZED, "invalid API key").into_response();
+        };
+
+        if admin && !key.admin {
+          return admin_key_required();
+        }
+
+        (Client::Key(key.name.clone()), &key.rate_limits)
+      }
+      None => {
+        if admin {
+          return admin_key_required();
+        }
+
+        if access_control.protected.contains(&group) {
+          return (
+            StatusCode::UNAUTHORIZED,
+            format!("API key required, set the `{API_KEY_HEADER}` header"),
+          )
+            .into_response();
+        }
+
+        (
+          Client::Ip(access_control.client_ip(&request)),
+          &access_control.ip_limits,
+        )
+      }
+    };
+
+    if let Some(rate) = limits.get(&group) {
+      if let Err(retry_after) = access_control
+        .limiter
+        .acquire(group, client, *rate, Instant::now())
+      {
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+        return (
+          StatusCode::TOO_MANY_REQUESTS,
+          [
+            (header::RETRY_AFTER, HeaderValue::from(seconds)),
+            (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+          ],
+          "rate limit exceeded",
+        )
+          .into_response();
+      }
+    }
+
+    next.run(request).await
+  }
+}
+
+// Generic cell rate algorithm, which behaves like a token bucket but only
+// stores the time at which each bucket will be full again.
+#[derive(Default)]
+struct Limiter(Mutex<HashMap<(RouteGroup, Client), Instant>>);
+
+impl Limiter {
+  fn acquire(
+    &self,
+    group: RouteGroup,
+    client: Client,
+    rate: Rate,
+    now: Instant,
+  ) -> Result<(), Duration> {
+    let mut buckets = self.0.lock().unwrap();
+
+    if buckets.len() >= PRUNE_THRESHOLD {
+      // buckets which are full are indistinguishable from new ones
+      buckets.retain(|_, full| *full > now);
+    }
+
+    let full = buckets
+      .get(&(group, client.clone()))
+      .copied()
+      .unwrap_or(now)
+      .max(now);
+
+    let next = full + rate.interval();
+
+    let wait = next.duration_since(now);
+
+    if wait > rate.period {
+      return Err(wait - rate.period);
+    }
+
+    buckets.insert((group, client), next);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rate(s: &str) -> Rate {
+    s.parse().unwrap()
+  }
+
+  #[test]
+  fn rate_from_str() {
+    assert_eq!(
+      rate("10/s"),
+      Rate {
+        requests: 10.try_into().unwrap(),
+        period: Duration::from_secs(1),
+      }
+    );
+
+    assert_eq!(rate("60 / m").period, Duration::from_secs(60));
+    assert_eq!(rate("1/h").period, Duration::from_secs(3600));
+    assert_eq!(rate("1/d").period, Duration::from_secs(86400));
+
+    assert!("10".parse::<Rate>().is_err());
+    assert!("0/s".parse::<Rate>().is_err());
+    assert!("-1/s".parse::<Rate>().is_err());
+    assert!("10/y".parse::<Rate>().is_err());
+  }
+
+  #[test]
+  fn rate_round_trips() {
+    for s in ["10/s", "60/m", "1/h", "5/d"] {
+      assert_eq!(rate(s).to_string(), s);
+    }
+  }
+
+  #[test]
+  fn limiter_allows_bursts_and_refills() {
+    let limiter = Limiter::default();
+    let client = Client::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    let start = Instant::now();
+
+    for _ in 0..3 {
+      limiter
+        .acquire(RouteGroup::Json, client.clone(), rate("3/s"), start)
+        .unwrap();
+    }
+
+    let retry_after = limiter
+      .acquire(RouteGroup::Json, client.clone(), rate("3/s"), start)
+      .unwrap_err();
+
+    assert!(retry_after > Duration::from_millis(333) && retry_after < Duration::from_millis(334));
+
+    limiter
+      .acquire(
+        RouteGroup::Json,
+        client.clone(),
+        rate("3/s"),
+        start + retry_after,
+      )
+      .unwrap();
+
+    assert!(limiter
+      .acquire(
+        RouteGroup::Json,
+        client.clone(),
+        rate("3/s"),
+        start + retry_after,
+      )
+      .is_err());
+
+    limiter
+      .acquire(
+        RouteGroup::Json,
+        client,
+        rate("3/s"),
+        start + Duration::from_secs(2),
+      )
+      .unwrap();
+  }
+
+  #[test]
+  fn limiter_buckets_are_per_client_and_group() {
+    let limiter = Limiter::default();
+    let now = Instant::now();
+
+    let a = Client::Ip(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)));
+    let b = Client::Key("b".into());
+
+    limiter
+      .acquire(RouteGroup::Json, a.clone(), rate("1/m"), now)
+      .unwrap();
+    assert!(limiter
+      .acquire(RouteGroup::Json, a.clone(), rate("1/m"), now)
+      .is_err());
+
+    limiter
+      .acquire(RouteGroup::Html, a, rate("1/m"), now)
+      .unwrap();
+    limiter
+      .acquire(RouteGroup::Json, b, rate("1/m"), now)
+      .unwrap();
+  }
+
+  #[test]
+  fn route_groups() {
+    let group = |method: &str, path: &str, accept: Option<&str>| {
+      let mut request = Request::builder().method(method).uri(path);
+      if let Some(accept) = accept {
+        request = request.header(header::ACCEPT, accept);
+      }
+      RouteGroup::of(&request.body(()).unwrap())
+    };
+
+    assert_eq!(group("GET", "/", None), RouteGroup::Html);
+    assert_eq!(group("GET", "/inscriptions", None), RouteGroup::Html);
+    assert_eq!(
+      group("GET", "/inscriptions", Some("application/json")),
+      RouteGroup::Json
+    );
+    assert_eq!(group("POST", "/outputs", None), RouteGroup::Json);
+    assert_eq!(group("GET", "/openapi.json", None), RouteGroup::Json);
+    assert_eq!(group("GET", "/content/foo", None), RouteGroup::Content);
+    assert_eq!(
+      group("GET", "/r/blockheight", Some("application/json")),
+      RouteGroup::Recursive
+    );
+  }
+
+  #[test]
+  fn access_control_validation() {
+    let key = |key: &str| ApiKey {
+      admin: false,
+      key: key.into(),
+      rate_limits: BTreeMap::new(),
+    };
+
+    assert!(AccessControl::new(&Access {
+      api_keys: [("a".into(), key(""))].into(),
+      ..default()
+    })
+    .is_err());
+
+    assert!(AccessControl::new(&Access {
+      api_keys: [("a".into(), key("foo")), ("b".into(), key("foo"))].into(),
+      ..default()
+    })
+    .is_err());
+
+    assert!(AccessControl::new(&Access {
+      protected: [RouteGroup::Json].into(),
+      ..default()
+    })
+    .is_err());
+
+    assert!(AccessControl::new(&Access {
+      client_ip_header: Some("invalid header".into()),
+      ..default()
+    })
+    .is_err());
+
+    assert!(AccessControl::new(&Access {
+      api_keys: [("a".into(), key("foo"))].into(),
+      protected: [RouteGroup::Json].into(),
+      ..default()
+    })
+    .is_ok());
+  }
+}
@@ -0,0 +1,182 @@
+use {super::*, std::ops::Range};
+
+// more ranges than this are probably abusive, so the whole body is served
+const MAX_RANGES: usize = 16;
+
+#[derive(Default, Debug)]
+pub(crate) struct ByteRanges {
+  pub(crate) if_range: Option<String>,
+  pub(crate) range: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for ByteRanges
+where
+  S: Send + Sync,
+{
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    let header = |name| {
+      parts
+        .headers
+        .get(name)
+        .map(|value| value.to_str().unwrap_or_default().to_owned())
+    };
+
+    Ok(Self {
+      if_range: header(header::IF_RANGE),
+      range: header(header::RANGE),
+    })
+  }
+}
+
+impl ByteRanges {
+  // Returns `None` if the whole body should be served, either because no
+  // ranges were requested, the `If-Range` validator does not match, or the
+  // `Range` header cannot be parsed, and `Some(Err(()))` if none of the
+  // requested ranges are satisfiable.
+  pub(crate) fn resolve(
+    &self,
+    headers: &HeaderMap,
+    len: u64,
+  ) -> Option<Result<Vec<Range<u64>>, ()>> {
+    let range = self.range.as_ref()?;
+
+    if let Some(if_range) = &self.if_range {
+      // weak entity tags and dates never match, since content responses only
+      // carry strong entity tags
+      let matches = if_range.starts_with('"')
+        && headers
+          .get(header::ETAG)
+          .map(|etag| etag == if_range.as_str())
+          .unwrap_or_default();
+
+      if !matches {
+        return None;
+      }
+    }
+
+    let specs = range.trim().strip_prefix("bytes=")?.split(',');
+
+    let mut ranges = Vec::new();
+
+    for spec in specs {
+      let (start, end) = spec.trim().split_once('-')?;
+
+      let range = if start.is_empty() {
+        let suffix = end.parse::<u64>().ok()?;
+        (suffix > 0 && len > 0).then(|| len.saturating_sub(suffix)..len)
+      } else {
+        let start = start.parse::<u64>().ok()?;
+
+        let end = if end.is_empty() {
+          len
+        } else {
+          let end = end.parse::<u64>().ok()?;
+
+          if end < start {
+            return None;
+          }
+
+          end.saturating_add(1).min(len)
+        };
+
+        (start < len).then_some(start..end)
+      };
+
+      ranges.extend(range);
+    }
+
+    if ranges.len() > MAX_RANGES {
+      return None;
+    }
+
+    if ranges.is_empty() {
+      Some(Err(()))
+    } else {
+      Some(Ok(ranges))
+    }
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+  use super::*;
+
+  fn resolve(range: &str, len: u64) -> Option<Result<Vec<Range<u64>>, ()>> {
+    ByteRanges {
+      if_range: None,
+      range: Some(range.into()),
+    }
+    .resolve(&HeaderMap::new(), len)
+  }
+
+  #[test]
+  fn no_range() {
+    assert_eq!(ByteRanges::default().resolve(&HeaderMap::new(), 10), None);
+  }
+
+  #[test]
+  fn single_ranges() {
+    assert_eq!(resolve("bytes=0-4", 10), Some(Ok(vec![0..5])));
+    assert_eq!(resolve("bytes=5-", 10), Some(Ok(vec![5..10])));
+    assert_eq!(resolve("bytes=-3", 10), Some(Ok(vec![7..10])));
+    assert_eq!(resolve("bytes=-30", 10), Some(Ok(vec![0..10])));
+    assert_eq!(resolve("bytes=8-100", 10), Some(Ok(vec![8..10])));
+  }
+
+  #[test]
+  fn multiple_ranges() {
+    assert_eq!(
+      resolve("bytes=0-1, 4-5,-1", 10),
+      Some(Ok(vec![0..2, 4..6, 9..10]))
+    );
+    assert_eq!(resolve("bytes=0-1,20-30", 10), Some(Ok(vec![0..2])));
+  }
+
+  #[test]
+  fn unsatisfiable_ranges() {
+    assert_eq!(resolve("bytes=10-", 10), Some(Err(())));
+    assert_eq!(resolve("bytes=-0", 10), Some(Err(())));
+    assert_eq!(resolve("bytes=0-", 0), Some(Err(())));
+  }
+
+  #[test]
+  fn malformed_ranges_are_ignored() {
+    assert_eq!(resolve("items=0-1", 10), None);
+    assert_eq!(resolve("bytes=1", 10), None);
+    assert_eq!(resolve("bytes=a-b", 10), None);
+    assert_eq!(resolve("bytes=5-4", 10), None);
+    assert_eq!(
+      resolve(&format!("bytes={}", ["0-0"; 17].join(",")), 10),
+      None
+    );
+  }
+
+  #[test]
+  fn if_range() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ETAG, HeaderValue::from_static("\"foo\""));
+
+    let byte_ranges = |if_range: &str| ByteRanges {
+      if_range: Some(if_range.into()),
+      range: Some("bytes=0-0".into()),
+    };
+
+    assert_eq!(
+      byte_ranges("\"foo\"").resolve(&headers, 10),
+      Some(Ok(vec![0..1]))
+    );
+    assert_eq!(byte_ranges("\"bar\"").resolve(&headers, 10), None);
+    assert_eq!(byte_ranges("W/\"foo\"").resolve(&headers, 10), None);
+    assert_eq!(
+      byte_ranges("Wed, 21 Oct 2015 07:28:00 GMT").resolve(&headers, 10),
+      None
+    );
+  }
+}
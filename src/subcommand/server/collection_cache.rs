@@ -0,0 +1,143 @@
+use super::*;
+
+const MAX_COLLECTIONS: usize = 64;
+
+// Trait tables are calculated from the metadata of every child of a
+// collection, which never changes, so they are cached by parent and number of
+// children, and the least recently used table is evicted when the cache is
+// full. Concurrent requests for a table which is not cached wait for a single
+// calculation.
+#[derive(Default)]
+pub(crate) struct CollectionCache {
+  state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+  clock: u64,
+  // slot holding each table, once calculated, and when it was last used
+  slots: HashMap<(InscriptionId, u64), (Arc<Mutex<Option<Arc<TraitTable>>>>, u64)>,
+}
+
+pub(crate) struct TraitTable {
+  pub(crate) children: Vec<api::CollectionChild>,
+  pub(crate) traits: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl CollectionCache {
+  pub(crate) fn get_or_calculate(
+    &self,
+    index: &Index,
+    parent: InscriptionId,
+    sequence_number: u32,
+  ) -> Result<Arc<TraitTable>> {
+    let children = index.get_child_count(sequence_number)?;
+
+    let key = (parent, children);
+
+    let slot = {
+      let mut state = self.state.lock().unwrap();
+
+      state.clock += 1;
+      let clock = state.clock;
+
+      if !state.slots.contains_key(&key) && state.slots.len() >= MAX_COLLECTIONS {
+        if let Some(lru) = state
+          .slots
+          .iter()
+          .min_by_key(|(_key, (_slot, used))| *used)
+          .map(|(key, _slot)| *key)
+        {
+          state.slots.remove(&lru);
+        }
+      }
+
+      let (slot, used) = state.slots.entry(key).or_default();
+
+      *used = clock;
+
+      slot.clone()
+    };
+
+    let mut slot = slot.lock().unwrap();
+
+    if let Some(table) = slot.as_ref() {
+      return Ok(table.clone());
+    }
+
+    let table = Arc::new(TraitTable::calculate(
+      index,
+      sequence_number,
+      children.try_into().unwrap_or(usize::MAX),
+    )?);
+
+    *slot = Some(table.clone());
+
+    Ok(table)
+  }
+}
+
+impl TraitTable {
+  // Calculate the table of the first `children` children, so that children
+  // inscribed after the cache key was read are not included
+  fn calculate(index: &Index, sequence_number: u32, children: usize) -> Result<Self> {
+    let (children, _more) =
+      index.get_children_by_sequence_number_paginated(sequence_number, children, 0)?;
+
+    let children = children
+      .into_iter()
+      .map(|id| {
+        Ok((
+          id,
+          index
+            .get_inscription_by_id(id)?
+            .map(|inscription| inscription.traits())
+            .unwrap_or_default(),
+        ))
+      })
+      .collect::<Result<Vec<(InscriptionId, BTreeMap<String, String>)>>>()?;
+
+    let mut traits = BTreeMap::<String, BTreeMap<String, u64>>::new();
+
+    for (_id, child_traits) in &children {
+      for (name, value) in child_traits {
+        *traits
+          .entry(name.clone())
+          .or_default()
+          .entry(value.clone())
+          .or_default() += 1;
+      }
+    }
+
+    let total = children.len();
+
+    // each trait adds the inverse of the fraction of children which share
+    // its value, so children with rarer traits score higher
+    let scores = children
+      .iter()
+      .map(|(_id, child_traits)| {
+        child_traits
+          .iter()
+          .map(|(name, value)| total as f64 / traits[name][value] as f64)
+          .sum::<f64>()
+      })
+      .collect::<Vec<f64>>();
+
+    let mut descending = scores.clone();
+    descending.sort_by(|a, b| b.total_cmp(a));
+
+    Ok(Self {
+      children: children
+        .into_iter()
+        .zip(scores)
+        .map(|((id, child_traits), rarity_score)| api::CollectionChild {
+          id,
+          rank: descending.partition_point(|&score| score > rarity_score) + 1,
+          rarity_score,
+          traits: child_traits,
+        })
+        .collect(),
+      traits,
+    })
+  }
+}
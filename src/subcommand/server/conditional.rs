@@ -0,0 +1,249 @@
+use {
+  super::*,
+  axum::extract::FromRef,
+  bitcoin::hashes::{sha256, Hash},
+};
+
+const IMMUTABLE: &str = "public, max-age=1209600, immutable";
+
+// responses that can still change must be revalidated before each use, which
+// is cheap since unchanged responses are answered with `304 Not Modified`
+const REVALIDATE: &str = "no-cache";
+
+#[derive(Default, Debug)]
+pub(crate) struct Conditional {
+  pub(crate) if_modified_since: Option<String>,
+  pub(crate) if_none_match: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for Conditional
+where
+  Arc<ServerConfig>: FromRef<S>,
+  S: Send + Sync,
+{
+  type Rejection = (StatusCode, &'static str);
+
+  async fn from_request_parts(
+    parts: &mut http::request::Parts,
+    _state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    let header = |name| {
+      parts
+        .headers
+        .get(name)
+        .map(|value| value.to_str().unwrap_or_default().to_owned())
+    };
+
+    Ok(Self {
+      if_modified_since: header(header::IF_MODIFIED_SINCE),
+      if_none_match: header(header::IF_NONE_MATCH),
+    })
+  }
+}
+
+impl Conditional {
+  pub(crate) fn respond(
+    &self,
+    validator: Validator,
+    response: impl FnOnce() -> ServerResult,
+  ) -> ServerResult {
+    if self.is_fresh(&validator) {
+      let mut response = StatusCode::NOT_MODIFIED.into_response();
+      validator.insert_headers(response.headers_mut());
+      return Ok(response);
+    }
+
+    let mut response = response()?;
+
+    if response.status().is_success() {
+      validator.insert_headers(response.headers_mut());
+    }
+
+    Ok(response)
+  }
+
+  fn is_fresh(&self, validator: &Validator) -> bool {
+    // `If-Modified-Since` is ignored if `If-None-Match` is present
+    if let Some(if_none_match) = &self.if_none_match {
+      return if_none_match.split(',').map(str::trim).any(|etag| {
+        etag == "*" || etag.trim_start_matches("W/") == validator.etag.trim_start_matches("W/")
+      });
+    }
+
+    let (Some(if_modified_since), Some(last_modified)) =
+      (&self.if_modified_since, validator.last_modified)
+    else {
+      return false;
+    };
+
+    DateTime::parse_from_rfc2822(if_modified_since)
+      .map(|if_modified_since| if_modified_since >= last_modified)
+      .unwrap_or_default()
+  }
+}
+
+#[derive(Debug)]
+pub(crate) struct Validator {
+  cache_control: &'static str,
+  etag: String,
+  last_modified: Option<DateTime<Utc>>,
+  vary_accept: bool,
+}
+
+impl Validator {
+  // Validator for a JSON or HTML representation of index state that can still
+  // change. The entity tag is weak, since it is derived from the state the
+  // representation is rendered from and not from the representation itself,
+  // and for HTML representations, which are localized and rendered with the
+  // theme, includes the locale and theme.
+  pub(crate) fn new(accept_json: bool, theme: &Theme, state: impl Serialize) -> Self {
+    let mut engine = sha256::Hash::engine();
+
+    let html = (!accept_json).then(|| {
+      (
+        Locale::current(),
+        theme.color_scheme.to_string(),
+        &theme.templates,
+        &theme.logo,
+        &theme.title,
+      )
+    });
+
+    serde_json::to_writer(
+      &mut engine,
+      &(env!("CARGO_PKG_VERSION"), accept_json, html, state),
+    )
+    .unwrap();
+
+    Self {
+      cache_control: REVALIDATE,
+      etag: format!("W/\"{}\"", sha256::Hash::from_engine(engine)),
+      last_modified: None,
+      vary_accept: true,
+    }
+  }
+
+  // Validator for a representation that never changes once it exists.
+  pub(crate) fn immutable(etag: String, last_modified: DateTime<Utc>) -> Self {
+    Self {
+      cache_control: IMMUTABLE,
+      etag,
+      last_modified: Some(last_modified),
+      vary_accept: false,
+    }
+  }
+
+  fn insert_headers(&self, headers: &mut HeaderMap) {
+    headers.insert(
+      header::CACHE_CONTROL,
+      HeaderValue::from_static(self.cache_control),
+    );
+
+    headers.insert(header::ETAG, HeaderValue::from_str(&self.etag).unwrap());
+
+    if let Some(last_modified) = self.last_modified {
+      headers.insert(
+        header::LAST_MODIFIED,
+        HeaderValue::from_str(
+          &last_modified
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string(),
+        )
+        .unwrap(),
+      );
+    }
+
+    if self.vary_accept {
+      headers.append(header::VARY, HeaderValue::from_static("accept"));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn conditional(if_none_match: Option<&str>, if_modified_since: Option<&str>) -> Conditional {
+    Conditional {
+      if_modified_since: if_modified_since.map(str::to_owned),
+      if_none_match: if_none_match.map(str::to_owned),
+    }
+  }
+
+  fn last_modified() -> DateTime<Utc> {
+    Utc.timestamp_opt(1445412480, 0).unwrap()
+  }
+
+  #[test]
+  fn entity_tags_depend_on_state_and_representation() {
+    assert_eq!(
+      Validator::new(true, &Theme::default(), 1).etag,
+      Validator::new(true, &Theme::default(), 1).etag
+    );
+    assert_ne!(
+      Validator::new(true, &Theme::default(), 1).etag,
+      Validator::new(true, &Theme::default(), 2).etag
+    );
+    assert_ne!(
+      Validator::new(true, &Theme::default(), 1).etag,
+      Validator::new(false, &Theme::default(), 1).etag
+    );
+    assert!(Validator::new(true, &Theme::default(), 1)
+      .etag
+      .starts_with("W/\""));
+  }
+
+  #[test]
+  fn html_entity_tags_depend_on_theme() {
+    let theme = Theme {
+      title: Some("foo".into()),
+      ..default()
+    };
+
+    assert_ne!(
+      Validator::new(false, &Theme::default(), 1).etag,
+      Validator::new(false, &theme, 1).etag
+    );
+    assert_eq!(
+      Validator::new(true, &Theme::default(), 1).etag,
+      Validator::new(true, &theme, 1).etag
+    );
+  }
+
+  #[test]
+  fn if_none_match() {
+    let validator = Validator::immutable("\"foo\"".into(), last_modified());
+
+    assert!(conditional(Some("\"foo\""), None).is_fresh(&validator));
+    assert!(conditional(Some("W/\"foo\""), None).is_fresh(&validator));
+    assert!(conditional(Some("\"bar\", \"foo\""), None).is_fresh(&validator));
+    assert!(conditional(Some("*"), None).is_fresh(&validator));
+    assert!(!conditional(Some("\"bar\""), None).is_fresh(&validator));
+    assert!(!conditional(None, None).is_fresh(&validator));
+  }
+
+  #[test]
+  fn if_modified_since() {
+    let validator = Validator::immutable("\"foo\"".into(), last_modified());
+
+    assert!(conditional(None, Some("Wed, 21 Oct 2015 07:28:00 GMT")).is_fresh(&validator));
+    assert!(conditional(None, Some("Thu, 22 Oct 2015 07:28:00 GMT")).is_fresh(&validator));
+    assert!(!conditional(None, Some("Tue, 20 Oct 2015 07:28:00 GMT")).is_fresh(&validator));
+    assert!(!conditional(None, Some("yesterday")).is_fresh(&validator));
+    assert!(
+      !conditional(Some("\"bar\""), Some("Wed, 21 Oct 2015 07:28:00 GMT")).is_fresh(&validator)
+    );
+  }
+
+  #[test]
+  fn if_modified_since_is_ignored_without_last_modified() {
+    assert!(
+      !conditional(None, Some("Wed, 21 Oct 2015 07:28:00 GMT")).is_fresh(&Validator::new(
+        true,
+        &Theme::default(),
+        1
+      ))
+    );
+  }
+}
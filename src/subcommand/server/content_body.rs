@@ -0,0 +1,150 @@
+use {
+  super::*,
+  axum::body::{Bytes, StreamBody},
+  bitcoin::secp256k1::rand::{self, RngCore},
+  byte_ranges::ByteRanges,
+  futures::stream,
+  std::{iter, ops::Range},
+};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ContentBody {
+  // compressed body, decompressed as it is streamed
+  Compressed(Compression, Vec<u8>),
+  Bytes(Vec<u8>),
+}
+
+impl ContentBody {
+  // Strong entity tag of the representation. Inscription content never
+  // changes, so the ID of the inscription whose content is served identifies
+  // it, and the decompressed representation of a compressed body is
+  // distinguished from the compressed one.
+  pub(crate) fn etag(&self, content_id: InscriptionId) -> String {
+    match self {
+      Self::Compressed(..) => format!("\"{content_id}-decompressed\""),
+      Self::Bytes(_) => format!("\"{content_id}\""),
+    }
+  }
+
+  pub(crate) fn into_response(
+    self,
+    mut headers: HeaderMap,
+    etag: &str,
+    byte_ranges: &ByteRanges,
+  ) -> Response {
+    headers.insert(header::ETAG, HeaderValue::from_str(etag).unwrap());
+
+    match self {
+      Self::Compressed(compression, body) => {
+        // the decompressed length is not known until the body has been
+        // streamed, so ranges cannot be served
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("none"));
+
+        let mut decompressor = match compression.decoder(io::Cursor::new(body)) {
+          Ok(decompressor) => Some(decompressor),
+          Err(err) => return ServerError::Internal(err.into()).into_response(),
+        };
+
+        let chunks = iter::from_fn(move || {
+          let mut chunk = vec![0; CHUNK_SIZE];
+
+          match decompressor.as_mut()?.read(&mut chunk) {
+            Ok(0) => None,
+            Ok(n) => {
+              chunk.truncate(n);
+              Some(Ok(Bytes::from(chunk)))
+            }
+            Err(err) => {
+              decompressor = None;
+              Some(Err(err))
+            }
+          }
+        });
+
+        (headers, StreamBody::new(stream::iter(chunks))).into_response()
+      }
+      Self::Bytes(body) => {
+        let body = Bytes::from(body);
+        let len = u64::try_from(body.len()).unwrap();
+
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+        match byte_ranges.resolve(&headers, len) {
+          None => Self::stream(StatusCode::OK, headers, vec![body]),
+          Some(Err(())) => {
+            headers.insert(
+              header::CONTENT_RANGE,
+              HeaderValue::from_str(&format!("bytes */{len}")).unwrap(),
+            );
+
+            (StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response()
+          }
+          Some(Ok(ranges)) => {
+            if let [range] = ranges.as_slice() {
+              headers.insert(
+                header::CONTENT_RANGE,
+                HeaderValue::from_str(&Self::content_range(range, len)).unwrap(),
+              );
+
+              return Self::stream(
+                StatusCode::PARTIAL_CONTENT,
+                headers,
+                vec![Self::slice(&body, range)],
+              );
+            }
+
+            let boundary = format!("{:016x}", rand::thread_rng().next_u64());
+
+            let content_type = headers
+              .insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_str(&format!("multipart/byteranges; boundary={boundary}"))
+                  .unwrap(),
+              )
+              .map(|content_type| content_type.to_str().unwrap_or_default().to_owned())
+              .unwrap_or_default();
+
+            let mut parts = Vec::new();
+
+            for range in &ranges {
+              parts.push(Bytes::from(format!(
+                "\r\n--{boundary}\r\nContent-Type: {content_type}\r\nContent-Range: {}\r\n\r\n",
+                Self::content_range(range, len),
+              )));
+              parts.push(Self::slice(&body, range));
+            }
+
+            parts.push(Bytes::from(format!("\r\n--{boundary}--\r\n")));
+
+            Self::stream(StatusCode::PARTIAL_CONTENT, headers, parts)
+          }
+        }
+      }
+    }
+  }
+
+  // Streams `parts` in chunks, sliced from the body without copying it
+  fn stream(status: StatusCode, mut headers: HeaderMap, parts: Vec<Bytes>) -> Response {
+    let len = parts.iter().map(Bytes::len).sum::<usize>();
+
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+
+    let chunks = parts.into_iter().flat_map(|part| {
+      (0..part.len()).step_by(CHUNK_SIZE).map(move |start| {
+        Ok::<Bytes, io::Error>(part.slice(start..part.len().min(start + CHUNK_SIZE)))
+      })
+    });
+
+    (status, headers, StreamBody::new(stream::iter(chunks))).into_response()
+  }
+
+  fn content_range(range: &Range<u64>, len: u64) -> String {
+    format!("bytes {}-{}/{len}", range.start, range.end - 1)
+  }
+
+  fn slice(body: &Bytes, range: &Range<u64>) -> Bytes {
+    body.slice(usize::try_from(range.start).unwrap()..usize::try_from(range.end).unwrap())
+  }
+}
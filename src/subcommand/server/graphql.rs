@@ -0,0 +1,884 @@
+use {
+  super::*,
+  async_graphql::{Context, EmptyMutation, EmptySubscription, Object, SimpleObject},
+};
+
+type FieldResult<T> = async_graphql::Result<T>;
+
+pub(crate) type Schema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+
+// the default and maximum number of items returned by list fields
+const MAX_LIMIT: usize = 100;
+
+pub(crate) fn schema(max_depth: usize, max_complexity: usize) -> Schema {
+  Schema::build(Query, EmptyMutation, EmptySubscription)
+    .limit_depth(max_depth)
+    .limit_complexity(max_complexity)
+    .finish()
+}
+
+fn index<'a>(ctx: &Context<'a>) -> &'a Index {
+  ctx.data_unchecked::<Arc<Index>>()
+}
+
+fn chain(ctx: &Context) -> Chain {
+  ctx.data_unchecked::<Arc<ServerConfig>>().chain
+}
+
+// Returns the items on page `page` of `limit` items, which is checked so the
+// cost of list fields is bounded by their complexity.
+fn paginate<T>(
+  items: impl IntoIterator<Item = T>,
+  limit: Option<usize>,
+  page: Option<usize>,
+) -> FieldResult<Vec<T>> {
+  let (limit, page) = check_limit(limit, page)?;
+  Ok(
+    items
+      .into_iter()
+      .skip(page.saturating_mul(limit))
+      .take(limit)
+      .collect(),
+  )
+}
+
+fn check_limit(limit: Option<usize>, page: Option<usize>) -> FieldResult<(usize, usize)> {
+  let limit = limit.unwrap_or(MAX_LIMIT);
+
+  if limit == 0 || limit > MAX_LIMIT {
+    return Err(format!("limit must be between 1 and {MAX_LIMIT}").into());
+  }
+
+  Ok((limit, page.unwrap_or_default()))
+}
+
+fn parse<T: FromStr>(s: &str, what: &str) -> FieldResult<T> {
+  s.parse()
+    .map_err(|_| format!("invalid {what} `{s}`").into())
+}
+
+// Hidden inscriptions are loaded as if they did not exist
+fn load_inscription(ctx: &Context, query: query::Inscription) -> FieldResult<Option<Inscription>> {
+  let index = index(ctx);
+
+  let Some((info, _, _)) = index.inscription_info(query, None)? else {
+    return Ok(None);
+  };
+
+  if ctx
+    .data_unchecked::<Arc<ServerConfig>>()
+    .moderation
+    .is_inscription_hidden(index, info.id)?
+  {
+    return Ok(None);
+  }
+
+  Ok(Some(Inscription(info)))
+}
+
+fn load_inscriptions(
+  ctx: &Context,
+  ids: impl IntoIterator<Item = InscriptionId>,
+) -> FieldResult<Vec<Inscription>> {
+  let mut inscriptions = Vec::new();
+
+  for id in ids {
+    inscriptions.extend(load_inscription(ctx, query::Inscription::Id(id))?);
+  }
+
+  Ok(inscriptions)
+}
+
+fn load_output(index: &Index, outpoint: OutPoint) -> FieldResult<Option<Output>> {
+  Ok(
+    index
+      .get_output_info(outpoint)?
+      .map(|(info, _)| Output { outpoint, info }),
+  )
+}
+
+fn load_transaction(index: &Index, txid: Txid) -> FieldResult<Option<Transaction>> {
+  Ok(
+    index
+      .get_transaction(txid)?
+      .map(|transaction| Transaction { txid, transaction }),
+  )
+}
+
+fn load_block(index: &Index, height: u32) -> FieldResult<Option<Block>> {
+  Ok(
+    index
+      .get_block_by_height(height)?
+      .map(|block| Block { block, height }),
+  )
+}
+
+fn load_rune(index: &Index, rune: ordinals::Rune) -> FieldResult<Option<Rune>> {
+  if !index.has_rune_index() {
+    return Err("this server has no rune index".into());
+  }
+
+  Ok(
+    index
+      .rune(rune)?
+      .map(|(id, entry, parent)| Rune { entry, id, parent }),
+  )
+}
+
+pub(crate) struct Query;
+
+#[Object]
+impl Query {
+  /// Look up an inscription by ID, number, or the name of its sat.
+  async fn inscription(
+    &self,
+    ctx: &Context<'_>,
+    query: String,
+  ) -> FieldResult<Option<Inscription>> {
+    load_inscription(ctx, parse(&query, "inscription query")?)
+  }
+
+  /// Look up a sat by number, decimal, degree, name, or percentile.
+  async fn sat(&self, query: String) -> FieldResult<Sat> {
+    Ok(Sat(parse(&query, "sat")?))
+  }
+
+  /// Look up an output by outpoint.
+  async fn output(&self, ctx: &Context<'_>, outpoint: String) -> FieldResult<Option<Output>> {
+    load_output(index(ctx), parse(&outpoint, "outpoint")?)
+  }
+
+  /// Look up a block by height or hash.
+  async fn block(&self, ctx: &Context<'_>, query: String) -> FieldResult<Option<Block>> {
+    let index = index(ctx);
+
+    match parse(&query, "block query")? {
+      query::Block::Height(height) => load_block(index, height),
+      query::Block::Hash(hash) => {
+        let Some(info) = index.block_header_info(hash)? else {
+          return Ok(None);
+        };
+
+        load_block(index, u32::try_from(info.height)?)
+      }
+    }
+  }
+
+  /// Look up a transaction by ID.
+  async fn transaction(&self, ctx: &Context<'_>, txid: String) -> FieldResult<Option<Transaction>> {
+    load_transaction(index(ctx), parse(&txid, "transaction ID")?)
+  }
+
+  /// Look up a rune by name, ID, or number.
+  async fn rune(&self, ctx: &Context<'_>, query: String) -> FieldResult<Option<Rune>> {
+    let index = index(ctx);
+
+    let rune = match parse(&query, "rune query")? {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => match index.get_rune_by_id(rune_id)? {
+        Some(rune) => rune,
+        None => return Ok(None),
+      },
+      query::Rune::Number(number) => match index.get_rune_by_number(usize::try_from(number)?)? {
+        Some(rune) => rune,
+        None => return Ok(None),
+      },
+    };
+
+    load_rune(index, rune)
+  }
+
+  /// Look up an address. Requires an address index.
+  async fn address(&self, ctx: &Context<'_>, address: String) -> FieldResult<Address> {
+    if !index(ctx).has_address_index() {
+      return Err("this server has no address index".into());
+    }
+
+    Ok(Address(
+      parse::<bitcoin::Address<NetworkUnchecked>>(&address, "address")?
+        .require_network(chain(ctx).network())?,
+    ))
+  }
+}
+
+pub(crate) struct Inscription(api::Inscription);
+
+#[Object]
+impl Inscription {
+  async fn id(&self) -> String {
+    self.0.id.to_string()
+  }
+
+  async fn number(&self) -> i32 {
+    self.0.number
+  }
+
+  async fn address(&self) -> Option<&str> {
+    self.0.address.as_deref()
+  }
+
+  async fn charms(&self) -> Vec<String> {
+    self.0.charms.iter().map(Charm::to_string).collect()
+  }
+
+  async fn content_length(&self) -> Option<usize> {
+    self.0.content_length
+  }
+
+  async fn content_type(&self) -> Option<&str> {
+    self.0.content_type.as_deref()
+  }
+
+  async fn effective_content_type(&self) -> Option<&str> {
+    self.0.effective_content_type.as_deref()
+  }
+
+  async fn fee(&self) -> u64 {
+    self.0.fee
+  }
+
+  async fn height(&self) -> u32 {
+    self.0.height
+  }
+
+  async fn satpoint(&self) -> String {
+    self.0.satpoint.to_string()
+  }
+
+  async fn timestamp(&self) -> i64 {
+    self.0.timestamp
+  }
+
+  async fn value(&self) -> Option<u64> {
+    self.0.value
+  }
+
+  /// The block the inscription was revealed in.
+  async fn block(&self, ctx: &Context<'_>) -> FieldResult<Option<Block>> {
+    load_block(index(ctx), self.0.height)
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn children(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    let index = index(ctx);
+
+    let (limit, page) = check_limit(limit, page)?;
+
+    let Some(entry) = index.get_inscription_entry(self.0.id)? else {
+      return Ok(Vec::new());
+    };
+
+    let (children, _more) =
+      index.get_children_by_sequence_number_paginated(entry.sequence_number, limit, page)?;
+
+    load_inscriptions(ctx, children)
+  }
+
+  /// The next inscription by number.
+  async fn next(&self, ctx: &Context<'_>) -> FieldResult<Option<Inscription>> {
+    match self.0.next {
+      Some(id) => load_inscription(ctx, query::Inscription::Id(id)),
+      None => Ok(None),
+    }
+  }
+
+  /// The output the inscription is currently in.
+  async fn output(&self, ctx: &Context<'_>) -> FieldResult<Option<Output>> {
+    load_output(index(ctx), self.0.satpoint.outpoint)
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn parents(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    let index = index(ctx);
+
+    let Some(entry) = index.get_inscription_entry(self.0.id)? else {
+      return Ok(Vec::new());
+    };
+
+    let (parents, _more) =
+      index.get_parents_by_sequence_number_paginated(paginate(entry.parents, limit, page)?, 0)?;
+
+    load_inscriptions(ctx, parents)
+  }
+
+  /// The previous inscription by number.
+  async fn previous(&self, ctx: &Context<'_>) -> FieldResult<Option<Inscription>> {
+    match self.0.previous {
+      Some(id) => load_inscription(ctx, query::Inscription::Id(id)),
+      None => Ok(None),
+    }
+  }
+
+  /// The rune etched in the inscription's reveal transaction.
+  async fn rune(&self, ctx: &Context<'_>) -> FieldResult<Option<Rune>> {
+    match self.0.rune {
+      Some(spaced_rune) => load_rune(index(ctx), spaced_rune.rune),
+      None => Ok(None),
+    }
+  }
+
+  /// The sat the inscription is on. Requires a sat index.
+  async fn sat(&self) -> Option<Sat> {
+    self.0.sat.map(Sat)
+  }
+
+  /// The inscription's reveal transaction.
+  async fn transaction(&self, ctx: &Context<'_>) -> FieldResult<Option<Transaction>> {
+    load_transaction(index(ctx), self.0.id.txid)
+  }
+}
+
+pub(crate) struct Sat(ordinals::Sat);
+
+#[Object]
+impl Sat {
+  async fn number(&self) -> u64 {
+    self.0.n()
+  }
+
+  async fn charms(&self) -> Vec<String> {
+    Charm::charms(self.0.charms())
+      .iter()
+      .map(Charm::to_string)
+      .collect()
+  }
+
+  async fn cycle(&self) -> u32 {
+    self.0.cycle()
+  }
+
+  async fn decimal(&self) -> String {
+    self.0.decimal().to_string()
+  }
+
+  async fn degree(&self) -> String {
+    self.0.degree().to_string()
+  }
+
+  async fn epoch(&self) -> u32 {
+    self.0.epoch().0
+  }
+
+  async fn height(&self) -> u32 {
+    self.0.height().0
+  }
+
+  async fn name(&self) -> String {
+    self.0.name()
+  }
+
+  async fn offset(&self) -> u64 {
+    self.0.third()
+  }
+
+  async fn percentile(&self) -> String {
+    self.0.percentile()
+  }
+
+  async fn period(&self) -> u32 {
+    self.0.period()
+  }
+
+  async fn rarity(&self) -> String {
+    self.0.rarity().to_string()
+  }
+
+  async fn timestamp(&self, ctx: &Context<'_>) -> FieldResult<i64> {
+    Ok(
+      index(ctx)
+        .block_time(self.0.height())?
+        .timestamp()
+        .timestamp(),
+    )
+  }
+
+  /// The block the sat was mined in, if it has been mined.
+  async fn block(&self, ctx: &Context<'_>) -> FieldResult<Option<Block>> {
+    load_block(index(ctx), self.0.height().0)
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    let index = index(ctx);
+    load_inscriptions(
+      ctx,
+      paginate(index.get_inscription_ids_by_sat(self.0)?, limit, page)?,
+    )
+  }
+
+  /// The location of the sat, if it is rare or inscribed.
+  async fn satpoint(&self, ctx: &Context<'_>) -> FieldResult<Option<String>> {
+    let index = index(ctx);
+
+    let satpoint = match index.rare_sat_satpoint(self.0)? {
+      Some(satpoint) => Some(satpoint),
+      None => match index.get_inscription_ids_by_sat(self.0)?.first() {
+        Some(&inscription_id) => index.get_inscription_satpoint_by_id(inscription_id)?,
+        None => None,
+      },
+    };
+
+    Ok(satpoint.map(|satpoint| satpoint.to_string()))
+  }
+}
+
+pub(crate) struct Output {
+  info: api::Output,
+  outpoint: OutPoint,
+}
+
+#[Object]
+impl Output {
+  async fn outpoint(&self) -> String {
+    self.outpoint.to_string()
+  }
+
+  async fn address(&self) -> Option<String> {
+    self
+      .info
+      .address
+      .as_ref()
+      .map(|address| address.clone().assume_checked().to_string())
+  }
+
+  async fn indexed(&self) -> bool {
+    self.info.indexed
+  }
+
+  async fn script_pubkey(&self) -> &str {
+    &self.info.script_pubkey
+  }
+
+  async fn spent(&self) -> bool {
+    self.info.spent
+  }
+
+  async fn value(&self) -> u64 {
+    self.info.value
+  }
+
+  /// The sat ranges in the output. Requires a sat index.
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn sat_ranges(
+    &self,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Option<Vec<SatRange>>> {
+    self
+      .info
+      .sat_ranges
+      .as_ref()
+      .map(|ranges| {
+        paginate(
+          ranges.iter().map(|&(start, end)| SatRange {
+            end: Sat(ordinals::Sat(end)),
+            start: Sat(ordinals::Sat(start)),
+          }),
+          limit,
+          page,
+        )
+      })
+      .transpose()
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    load_inscriptions(
+      ctx,
+      paginate(self.info.inscriptions.iter().copied(), limit, page)?,
+    )
+  }
+
+  async fn runes(&self) -> Vec<RuneBalance> {
+    self
+      .info
+      .runes
+      .iter()
+      .map(|(spaced_rune, pile)| RuneBalance::new(*spaced_rune, *pile))
+      .collect()
+  }
+
+  /// The transaction which created the output.
+  async fn transaction(&self, ctx: &Context<'_>) -> FieldResult<Option<Transaction>> {
+    load_transaction(index(ctx), self.outpoint.txid)
+  }
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct SatRange {
+  /// The first sat after the range.
+  end: Sat,
+  start: Sat,
+}
+
+pub(crate) struct Block {
+  block: bitcoin::Block,
+  height: u32,
+}
+
+#[Object]
+impl Block {
+  async fn hash(&self) -> String {
+    self.block.block_hash().to_string()
+  }
+
+  async fn height(&self) -> u32 {
+    self.height
+  }
+
+  async fn timestamp(&self) -> u32 {
+    self.block.header.time
+  }
+
+  async fn transaction_count(&self) -> usize {
+    self.block.txdata.len()
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    let index = index(ctx);
+    load_inscriptions(
+      ctx,
+      paginate(index.get_inscriptions_in_block(self.height)?, limit, page)?,
+    )
+  }
+
+  async fn previous(&self, ctx: &Context<'_>) -> FieldResult<Option<Block>> {
+    match self.height.checked_sub(1) {
+      Some(height) => load_block(index(ctx), height),
+      None => Ok(None),
+    }
+  }
+
+  /// Runes etched in the block.
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn runes(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Rune>> {
+    let index = index(ctx);
+
+    let mut runes = Vec::new();
+
+    for spaced_rune in paginate(index.get_runes_in_block(self.height.into())?, limit, page)? {
+      runes.extend(load_rune(index, spaced_rune.rune)?);
+    }
+
+    Ok(runes)
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn transactions(
+    &self,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Transaction>> {
+    paginate(
+      self.block.txdata.iter().map(|transaction| Transaction {
+        txid: transaction.txid(),
+        transaction: transaction.clone(),
+      }),
+      limit,
+      page,
+    )
+  }
+}
+
+pub(crate) struct Transaction {
+  transaction: bitcoin::Transaction,
+  txid: Txid,
+}
+
+#[Object]
+impl Transaction {
+  async fn txid(&self) -> String {
+    self.txid.to_string()
+  }
+
+  async fn inscription_count(&self, ctx: &Context<'_>) -> FieldResult<u32> {
+    Ok(index(ctx).inscription_count(self.txid)?)
+  }
+
+  /// The rune etched in the transaction.
+  async fn etching(&self, ctx: &Context<'_>) -> FieldResult<Option<Rune>> {
+    let index = index(ctx);
+    match index.get_etching(self.txid)? {
+      Some(spaced_rune) => load_rune(index, spaced_rune.rune),
+      None => Ok(None),
+    }
+  }
+
+  /// Inscriptions revealed in the transaction.
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    let index = index(ctx);
+    load_inscriptions(
+      ctx,
+      paginate(
+        (0..index.inscription_count(self.txid)?).map(|index| InscriptionId {
+          txid: self.txid,
+          index,
+        }),
+        limit,
+        page,
+      )?,
+    )
+  }
+
+  /// The outputs spent by the transaction.
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn inputs(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Output>> {
+    let index = index(ctx);
+
+    let mut outputs = Vec::new();
+
+    for input in paginate(&self.transaction.input, limit, page)? {
+      if !input.previous_output.is_null() {
+        outputs.extend(load_output(index, input.previous_output)?);
+      }
+    }
+
+    Ok(outputs)
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn outputs(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Output>> {
+    let index = index(ctx);
+
+    let mut outputs = Vec::new();
+
+    for vout in paginate(0..self.transaction.output.len(), limit, page)? {
+      outputs.extend(load_output(
+        index,
+        OutPoint {
+          txid: self.txid,
+          vout: u32::try_from(vout)?,
+        },
+      )?);
+    }
+
+    Ok(outputs)
+  }
+}
+
+pub(crate) struct Rune {
+  entry: RuneEntry,
+  id: RuneId,
+  parent: Option<InscriptionId>,
+}
+
+#[Object]
+impl Rune {
+  async fn id(&self) -> String {
+    self.id.to_string()
+  }
+
+  async fn name(&self) -> String {
+    self.entry.spaced_rune.to_string()
+  }
+
+  async fn block(&self) -> u64 {
+    self.entry.block
+  }
+
+  async fn burned(&self) -> String {
+    self.entry.burned.to_string()
+  }
+
+  async fn divisibility(&self) -> u8 {
+    self.entry.divisibility
+  }
+
+  async fn mints(&self) -> String {
+    self.entry.mints.to_string()
+  }
+
+  async fn number(&self) -> u64 {
+    self.entry.number
+  }
+
+  async fn premine(&self) -> String {
+    self.entry.premine.to_string()
+  }
+
+  async fn supply(&self) -> String {
+    self.entry.supply().to_string()
+  }
+
+  async fn symbol(&self) -> Option<String> {
+    self.entry.symbol.map(String::from)
+  }
+
+  async fn timestamp(&self) -> u64 {
+    self.entry.timestamp
+  }
+
+  async fn turbo(&self) -> bool {
+    self.entry.turbo
+  }
+
+  /// Whether the rune can be minted in the next block.
+  async fn mintable(&self, ctx: &Context<'_>) -> FieldResult<bool> {
+    let height = index(ctx).block_height()?.unwrap_or(Height(0));
+    Ok(self.entry.mintable((height.n() + 1).into()).is_ok())
+  }
+
+  async fn etching(&self, ctx: &Context<'_>) -> FieldResult<Option<Transaction>> {
+    load_transaction(index(ctx), self.entry.etching)
+  }
+
+  async fn parent(&self, ctx: &Context<'_>) -> FieldResult<Option<Inscription>> {
+    match self.parent {
+      Some(id) => load_inscription(ctx, query::Inscription::Id(id)),
+      None => Ok(None),
+    }
+  }
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct RuneBalance {
+  /// The amount in the rune's smallest unit.
+  amount: String,
+  /// The amount with the rune's divisibility and symbol.
+  decimal: String,
+  divisibility: u8,
+  rune: String,
+  symbol: Option<String>,
+}
+
+impl RuneBalance {
+  fn new(spaced_rune: SpacedRune, pile: Pile) -> Self {
+    Self {
+      amount: pile.amount.to_string(),
+      decimal: pile.to_string(),
+      divisibility: pile.divisibility,
+      rune: spaced_rune.to_string(),
+      symbol: pile.symbol.map(String::from),
+    }
+  }
+}
+
+pub(crate) struct Address(bitcoin::Address);
+
+impl Address {
+  fn outpoints(&self, index: &Index) -> FieldResult<Vec<OutPoint>> {
+    let mut outputs = index.get_address_info(&self.0)?;
+    outputs.sort();
+    Ok(outputs)
+  }
+}
+
+#[Object]
+impl Address {
+  async fn address(&self) -> String {
+    self.0.to_string()
+  }
+
+  async fn sat_balance(&self, ctx: &Context<'_>) -> FieldResult<u64> {
+    let index = index(ctx);
+    Ok(index.get_sat_balances_for_outputs(&self.outpoints(index)?)?)
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn inscriptions(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Inscription>> {
+    let index = index(ctx);
+    load_inscriptions(
+      ctx,
+      paginate(
+        index.get_inscriptions_for_outputs(&self.outpoints(index)?)?,
+        limit,
+        page,
+      )?,
+    )
+  }
+
+  #[graphql(complexity = "limit.unwrap_or(MAX_LIMIT) * child_complexity")]
+  async fn outputs(
+    &self,
+    ctx: &Context<'_>,
+    limit: Option<usize>,
+    page: Option<usize>,
+  ) -> FieldResult<Vec<Output>> {
+    let index = index(ctx);
+
+    let mut outputs = Vec::new();
+
+    for outpoint in paginate(self.outpoints(index)?, limit, page)? {
+      outputs.extend(load_output(index, outpoint)?);
+    }
+
+    Ok(outputs)
+  }
+
+  async fn rune_balances(&self, ctx: &Context<'_>) -> FieldResult<Vec<RuneBalance>> {
+    let index = index(ctx);
+
+    if !index.has_rune_index() {
+      return Ok(Vec::new());
+    }
+
+    Ok(
+      index
+        .get_aggregated_rune_balances_for_outputs(&self.outpoints(index)?)?
+        .into_iter()
+        .map(|(spaced_rune, decimal, symbol)| {
+          RuneBalance::new(
+            spaced_rune,
+            Pile {
+              amount: decimal.value,
+              divisibility: decimal.scale,
+              symbol,
+            },
+          )
+        })
+        .collect(),
+    )
+  }
+}
@@ -0,0 +1,204 @@
+use {
+  super::*,
+  std::{sync::RwLock, time::SystemTime},
+};
+
+// Inscriptions hidden at runtime, in addition to those hidden in the
+// configuration file. An inscription is hidden if its ID is listed, if the
+// SHA-256 hash of its content is listed, or if any of its parents is listed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Hidden {
+  pub content_hashes: BTreeSet<sha256::Hash>,
+  pub inscriptions: BTreeSet<InscriptionId>,
+  pub parents: BTreeSet<InscriptionId>,
+}
+
+impl Hidden {
+  fn insert(&mut self, other: Hidden) {
+    self.content_hashes.extend(other.content_hashes);
+    self.inscriptions.extend(other.inscriptions);
+    self.parents.extend(other.parents);
+  }
+
+  fn remove(&mut self, other: &Hidden) {
+    self
+      .content_hashes
+      .retain(|hash| !other.content_hashes.contains(hash));
+    self
+      .inscriptions
+      .retain(|id| !other.inscriptions.contains(id));
+    self.parents.retain(|id| !other.parents.contains(id));
+  }
+}
+
+#[derive(Default)]
+pub(crate) struct Moderation {
+  path: Option<PathBuf>,
+  // hidden list and modification time of the file it was loaded from
+  state: RwLock<(Hidden, Option<SystemTime>)>,
+}
+
+impl Moderation {
+  pub(crate) fn load(path: PathBuf) -> Result<Self> {
+    let moderation = Self {
+      path: Some(path),
+      state: default(),
+    };
+
+    moderation.reload()?;
+
+    Ok(moderation)
+  }
+
+  /// Reload the hidden list if the file it is persisted in has been modified
+  /// since it was last loaded or saved.
+  pub(crate) fn reload(&self) -> Result {
+    let Some(path) = &self.path else {
+      return Ok(());
+    };
+
+    let modified = match fs::metadata(path) {
+      Ok(metadata) => Some(metadata.modified()?),
+      Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+      Err(err) => return Err(err.into()),
+    };
+
+    if self.state.read().unwrap().1 == modified {
+      return Ok(());
+    }
+
+    let hidden = match modified {
+      Some(_) => serde_json::from_slice(&fs::read(path)?)
+        .with_context(|| format!("failed to parse hidden list `{}`", path.display()))?,
+      None => Hidden::default(),
+    };
+
+    *self.state.write().unwrap() = (hidden, modified);
+
+    Ok(())
+  }
+
+  pub(crate) fn hidden(&self) -> Hidden {
+    self.state.read().unwrap().0.clone()
+  }
+
+  pub(crate) fn hide(&self, hidden: Hidden) -> Result<Hidden> {
+    self.update(|list| list.insert(hidden))
+  }
+
+  pub(crate) fn unhide(&self, hidden: &Hidden) -> Result<Hidden> {
+    self.update(|list| list.remove(hidden))
+  }
+
+  fn update(&self, f: impl FnOnce(&mut Hidden)) -> Result<Hidden> {
+    let mut state = self.state.write().unwrap();
+
+    let mut hidden = state.0.clone();
+
+    f(&mut hidden);
+
+    if let Some(path) = &self.path {
+      // write to a temporary file and rename it, so that a crash never
+      // leaves a truncated list behind
+      let tmp = path.with_extension("json.tmp");
+      fs::write(&tmp, serde_json::to_string_pretty(&hidden)?)?;
+      fs::rename(&tmp, path)?;
+      state.1 = Some(fs::metadata(path)?.modified()?);
+    }
+
+    state.0 = hidden.clone();
+
+    Ok(hidden)
+  }
+
+  pub(crate) fn is_hidden(
+    &self,
+    index: &Index,
+    inscription_id: InscriptionId,
+    inscription: &Inscription,
+  ) -> Result<bool> {
+    self.is_hidden_with(index, inscription_id, || {
+      Ok(inscription.body.as_deref().map(sha256::Hash::hash))
+    })
+  }
+
+  /// Whether the inscription with `inscription_id` is hidden, loading its
+  /// content from the index only if content hashes are hidden.
+  pub(crate) fn is_inscription_hidden(
+    &self,
+    index: &Index,
+    inscription_id: InscriptionId,
+  ) -> Result<bool> {
+    self.is_hidden_with(index, inscription_id, || {
+      Ok(
+        index
+          .get_inscription_by_id(inscription_id)?
+          .and_then(|inscription| inscription.body)
+          .map(|body| sha256::Hash::hash(&body)),
+      )
+    })
+  }
+
+  /// Remove hidden inscriptions from a list of inscriptions.
+  pub(crate) fn filter(
+    &self,
+    index: &Index,
+    inscription_ids: Vec<InscriptionId>,
+  ) -> Result<Vec<InscriptionId>> {
+    let mut visible = Vec::with_capacity(inscription_ids.len());
+
+    for inscription_id in inscription_ids {
+      if !self.is_inscription_hidden(index, inscription_id)? {
+        visible.push(inscription_id);
+      }
+    }
+
+    Ok(visible)
+  }
+
+  fn is_hidden_with(
+    &self,
+    index: &Index,
+    inscription_id: InscriptionId,
+    content_hash: impl FnOnce() -> Result<Option<sha256::Hash>>,
+  ) -> Result<bool> {
+    let state = self.state.read().unwrap();
+
+    let hidden = &state.0;
+
+    if hidden.inscriptions.contains(&inscription_id) {
+      return Ok(true);
+    }
+
+    if !hidden.content_hashes.is_empty() {
+      if let Some(hash) = content_hash()? {
+        if hidden.content_hashes.contains(&hash) {
+          return Ok(true);
+        }
+      }
+    }
+
+    if !hidden.parents.is_empty() {
+      if let Some(entry) = index.get_inscription_entry(inscription_id)? {
+        let mut page = 0;
+        loop {
+          let (parents, more) =
+            index.get_parents_by_sequence_number_paginated(entry.parents.clone(), page)?;
+
+          if parents.iter().any(|parent| hidden.parents.contains(parent)) {
+            return Ok(true);
+          }
+
+          if !more {
+            break;
+          }
+
+          page += 1;
+        }
+      }
+    }
+
+    Ok(false)
+  }
+}
@@ -0,0 +1,375 @@
+use {
+  super::*,
+  axum::{
+    handler::Handler,
+    routing::{self, MethodRouter},
+  },
+  schemars::{
+    generate::{Contract, SchemaSettings},
+    JsonSchema, Schema, SchemaGenerator,
+  },
+  serde_json::{json, Map, Value},
+  std::any,
+};
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+  generator.subschema_for::<T>()
+}
+
+struct Operation {
+  body: Option<SchemaFn>,
+  handler: &'static str,
+  method: &'static str,
+  path: &'static str,
+  query: Option<SchemaFn>,
+  response: Option<(&'static str, SchemaFn)>,
+}
+
+// Router which records the routes registered with it, and the types of the
+// requests and responses of those which are part of the JSON API, from which
+// the OpenAPI document is generated.
+pub(crate) struct OpenApiRouter<S> {
+  operations: Vec<Operation>,
+  router: Router<S>,
+}
+
+impl<S: Clone + Send + Sync + 'static> OpenApiRouter<S> {
+  pub(crate) fn new() -> Self {
+    Self {
+      operations: Vec::new(),
+      router: Router::new(),
+    }
+  }
+
+  pub(crate) fn delete<H: Handler<T, S>, T: 'static>(self, path: &'static str, handler: H) -> Self {
+    self.route::<H>("delete", path, routing::delete(handler))
+  }
+
+  pub(crate) fn get<H: Handler<T, S>, T: 'static>(self, path: &'static str, handler: H) -> Self {
+    self.route::<H>("get", path, routing::get(handler))
+  }
+
+  pub(crate) fn post<H: Handler<T, S>, T: 'static>(self, path: &'static str, handler: H) -> Self {
+    self.route::<H>("post", path, routing::post(handler))
+  }
+
+  fn route<H>(mut self, method: &'static str, path: &'static str, route: MethodRouter<S>) -> Self {
+    self.operations.push(Operation {
+      body: None,
+      handler: any::type_name::<H>().rsplit("::").next().unwrap(),
+      method,
+      path,
+      query: None,
+      response: None,
+    });
+
+    self.router = self.router.route(path, route);
+
+    self
+  }
+
+  fn last(&mut self) -> &mut Operation {
+    self.operations.last_mut().unwrap()
+  }
+
+  // The last route takes a JSON request body of type `T`
+  pub(crate) fn body<T: JsonSchema>(mut self) -> Self {
+    self.last().body = Some(schema::<T>);
+    self
+  }
+
+  // The last route returns JSON of type `T`
+  pub(crate) fn json<T: JsonSchema>(mut self) -> Self {
+    self.last().response = Some(("application/json", schema::<T>));
+    self
+  }
+
+  // The last route takes query parameters, described by the fields of `T`
+  pub(crate) fn query<T: JsonSchema>(mut self) -> Self {
+    self.last().query = Some(T::json_schema);
+    self
+  }
+
+  // The last route returns plain text, and is part of the JSON API
+  pub(crate) fn text(mut self) -> Self {
+    self.last().response = Some(("text/plain", schema::<String>));
+    self
+  }
+
+  pub(crate) fn into_router(self) -> Router<S> {
+    self.router
+  }
+
+  // Routes which are registered, but not part of the JSON API
+  #[cfg(test)]
+  fn undocumented(&self) -> BTreeSet<(&'static str, &'static str)> {
+    self
+      .operations
+      .iter()
+      .filter(|operation| operation.response.is_none())
+      .map(|operation| (operation.method, operation.path))
+      .collect()
+  }
+
+  pub(crate) fn document(&self) -> Value {
+    let mut generator = SchemaSettings::draft2020_12()
+      .with(|settings| {
+        settings.contract = Contract::Serialize;
+        settings.definitions_path = "/components/schemas".into();
+        settings.meta_schema = None;
+      })
+      .into_generator();
+
+    // query parameters are deserialized, so fields with defaults are optional
+    let mut query_generator = SchemaSettings::draft2020_12()
+      .with(|settings| {
+        settings.inline_subschemas = true;
+        settings.meta_schema = None;
+      })
+      .into_generator();
+
+    let mut paths = Map::new();
+
+    for operation in &self.operations {
+      let Some((content_type, response)) = operation.response else {
+        continue;
+      };
+
+      let mut parameters = Vec::new();
+
+      let path = operation
+        .path
+        .split('/')
+        .map(|segment| match segment.strip_prefix([':', '*']) {
+          Some(name) => {
+            let (description, schema) = path_parameter(name);
+
+            parameters.push(json!({
+              "name": name,
+              "in": "path",
+              "required": true,
+              "description": description,
+              "schema": schema(&mut generator),
+            }));
+
+            format!("{{{name}}}")
+          }
+          None => segment.into(),
+        })
+        .collect::<Vec<String>>()
+        .join("/");
+
+      if let Some(query) = operation.query {
+        let query = query(&mut query_generator);
+
+        let required = query
+          .get("required")
+          .and_then(Value::as_array)
+          .cloned()
+          .unwrap_or_default();
+
+        for (name, schema) in query
+          .get("properties")
+          .and_then(Value::as_object)
+          .into_iter()
+          .flatten()
+        {
+          let mut schema = schema.clone();
+
+          let description = schema
+            .as_object_mut()
+            .and_then(|schema| schema.remove("description"));
+
+          let required = required.contains(&name.as_str().into());
+
+          // optional parameters are omitted, rather than given as null
+          if !required {
+            if let Some(Value::Array(types)) = schema.get_mut("type") {
+              types.retain(|ty| ty != "null");
+              if let [ty] = types.as_slice() {
+                schema["type"] = ty.clone();
+              }
+            }
+
+            if let Some(Value::Array(values)) = schema.get_mut("enum") {
+              values.retain(|value| !value.is_null());
+            }
+          }
+
+          parameters.push(json!({
+            "name": name,
+            "in": "query",
+            "required": required,
+            "description": description,
+            "schema": schema,
+          }));
+        }
+      }
+
+      let mut item = json!({
+        "operationId": operation.handler,
+        "parameters": parameters,
+        "responses": {
+          "200": {
+            "description": "Success",
+            "content": {
+              content_type: {
+                "schema": response(&mut generator),
+              },
+            },
+          },
+          "default": {
+            "$ref": "#/components/responses/Error",
+          },
+        },
+      });
+
+      if let Some(body) = operation.body {
+        item["requestBody"] = json!({
+          "required": true,
+          "content": {
+            "application/json": {
+              "schema": body(&mut generator),
+            },
+          },
+        });
+      }
+
+      paths
+        .entry(path)
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .unwrap()
+        .insert(operation.method.into(), item);
+    }
+
+    json!({
+      "openapi": "3.1.0",
+      "info": {
+        "title": "ord",
+        "version": env!("CARGO_PKG_VERSION"),
+        "description": "JSON API of the `ord` explorer. Routes outside of `/r/` return JSON when requested with an `Accept: application/json` header.",
+        "license": {
+          "name": "CC0-1.0",
+          "identifier": "CC0-1.0",
+        },
+      },
+      "paths": paths,
+      "components": {
+        "schemas": generator.take_definitions(true),
+        "responses": {
+          "Error": {
+            "description": "Error",
+            "content": {
+              "text/plain": {
+                "schema": {
+                  "type": "string",
+                },
+              },
+            },
+          },
+        },
+        "securitySchemes": {
+          "ApiKey": {
+            "type": "apiKey",
+            "in": "header",
+            "name": access::API_KEY_HEADER,
+            "description": "Required for route groups protected by the server's access configuration, and used for per-key rate limits.",
+          },
+        },
+      },
+      "security": [{}, {"ApiKey": []}],
+    })
+  }
+}
+
+fn path_parameter(name: &str) -> (&'static str, SchemaFn) {
+  match name {
+    "address" => ("Address.", schema::<api::schema::Address>),
+    "child" => ("Index of the child.", schema::<usize>),
+    "height" => ("Block height.", schema::<u32>),
+    "index" => (
+      "Index of the inscription on the sat. Negative indices count back from the latest inscription.",
+      schema::<isize>,
+    ),
+    "inscription_id" => ("Inscription ID.", schema::<InscriptionId>),
+    "inscription_query" => ("Inscription ID or number.", schema::<String>),
+    "outpoint" | "output" => ("Outpoint.", schema::<api::schema::OutPoint>),
+    "page" => ("Page index.", schema::<usize>),
+    "query" => ("Block hash or height.", schema::<String>),
+    "rune" => (
+      "Rune name, spaced rune name, rune ID, or rune number.",
+      schema::<String>,
+    ),
+    "sat" => (
+      "Sat as integer, decimal, degree, name, or percentile.",
+      schema::<String>,
+    ),
+    "sat_number" => ("Sat number.", schema::<u64>),
+    "txid" => ("Transaction ID.", schema::<api::schema::Txid>),
+    _ => panic!("path parameter `{name}` of JSON route is not described"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn routes_which_are_not_documented_do_not_return_json() {
+    pretty_assert_eq!(
+      Server::router().undocumented(),
+      [
+        ("delete", "/admin/hidden"),
+        ("get", "/"),
+        ("get", "/admin/hidden"),
+        ("get", "/blockcount"),
+        ("get", "/blockhash"),
+        ("get", "/blockhash/:height"),
+        ("get", "/blockheight"),
+        ("get", "/blocktime"),
+        ("get", "/bounties"),
+        ("get", "/clock"),
+        ("get", "/content/:inscription_id"),
+        ("get", "/faq"),
+        ("get", "/favicon.ico"),
+        ("get", "/feed.xml"),
+        ("get", "/input/:block/:transaction/:input"),
+        ("get", "/install.sh"),
+        ("get", "/openapi.json"),
+        ("get", "/ordinal/:sat"),
+        ("get", "/parents/:inscription_id"),
+        ("get", "/parents/:inscription_id/:page"),
+        ("get", "/preview/:inscription_id"),
+        ("get", "/range/:start/:end"),
+        ("get", "/rare.txt"),
+        ("get", "/search"),
+        ("get", "/search/*query"),
+        ("get", "/static/*path"),
+        ("get", "/thumbnail/:inscription_id"),
+        ("get", "/update"),
+        ("post", "/admin/hidden"),
+      ]
+      .into_iter()
+      .collect(),
+    );
+  }
+
+  #[test]
+  fn operation_ids_are_unique() {
+    let document = Server::router().document();
+
+    let mut ids = BTreeSet::new();
+
+    for item in document["paths"].as_object().unwrap().values() {
+      for operation in item.as_object().unwrap().values() {
+        let id = operation["operationId"].as_str().unwrap();
+        assert!(ids.insert(id), "duplicate operation ID `{id}`");
+      }
+    }
+
+    assert!(ids.len() > 50);
+  }
+}
@@ -3,12 +3,19 @@ use {super::*, axum::http::HeaderName};
 #[derive(Default)]
 pub(crate) struct ServerConfig {
   pub(crate) chain: Chain,
+  pub(crate) collections: CollectionCache,
   pub(crate) proxy: Option<Url>,
   pub(crate) csp_origin: Option<String>,
   pub(crate) decompress: bool,
   pub(crate) domain: Option<String>,
+  pub(crate) graphql_schema: Option<graphql::Schema>,
   pub(crate) index_sats: bool,
   pub(crate) json_api_enabled: bool,
+  pub(crate) max_batch_size: usize,
+  pub(crate) max_page_size: usize,
+  pub(crate) moderation: Moderation,
+  pub(crate) theme: Theme,
+  pub(crate) thumbnail_cache: Option<ThumbnailCache>,
 }
 
 impl ServerConfig {
@@ -19,6 +26,7 @@ impl ServerConfig {
     let default = match media {
       Media::Audio => "default-src 'self'",
       Media::Code(_) => "script-src-elem 'self' https://cdn.jsdelivr.net",
+      Media::Csv => "default-src 'self'",
       Media::Font => "script-src-elem 'self'; style-src 'self' 'unsafe-inline'",
       Media::Iframe => {
         return Err(
@@ -26,9 +34,12 @@ impl ServerConfig {
         )
       }
       Media::Image(_) => "default-src 'self' 'unsafe-inline'",
+      Media::Json => "default-src 'self'",
       Media::Markdown => "script-src-elem 'self' https://cdn.jsdelivr.net",
+      Media::Midi => "default-src 'self'",
       Media::Model => "script-src-elem 'self' https://ajax.googleapis.com",
       Media::Pdf => "script-src-elem 'self' https://cdn.jsdelivr.net",
+      Media::Stl => "script-src-elem 'self' https://cdn.jsdelivr.net",
       Media::Text => "default-src 'self'",
       Media::Unknown => "default-src 'self'",
       Media::Video => "default-src 'self'",
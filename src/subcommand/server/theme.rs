@@ -0,0 +1,165 @@
+use {super::*, clap::ValueEnum, std::path::Component};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub(crate) enum ColorScheme {
+  Auto,
+  #[default]
+  Dark,
+  Light,
+}
+
+impl Display for ColorScheme {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Auto => "auto",
+        Self::Dark => "dark",
+        Self::Light => "light",
+      }
+    )
+  }
+}
+
+// Branding for white-labeled explorers. Templates in the theme directory's
+// `templates/` override the page layout in `templates/page.html` and the page
+// content of templates with the same name, and files in its `static/`
+// override the embedded static assets.
+#[derive(Debug, Default)]
+pub(crate) struct Theme {
+  pub(crate) color_scheme: ColorScheme,
+  pub(crate) dir: Option<PathBuf>,
+  pub(crate) logo: Option<String>,
+  pub(crate) templates: BTreeMap<String, String>,
+  pub(crate) title: Option<String>,
+}
+
+impl Theme {
+  pub(crate) fn load(
+    dir: Option<PathBuf>,
+    color_scheme: ColorScheme,
+    logo: Option<String>,
+    title: Option<String>,
+  ) -> Result<Self> {
+    let mut templates = BTreeMap::new();
+
+    if let Some(dir) = &dir {
+      let path = dir.join("templates");
+
+      let entries = match fs::read_dir(&path) {
+        Ok(entries) => Some(entries),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(anyhow!(err).context(format!("failed to read {}", path.display()))),
+      };
+
+      for entry in entries.into_iter().flatten() {
+        let path = entry?.path();
+
+        if path.extension() != Some("html".as_ref()) || !path.is_file() {
+          continue;
+        }
+
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+          continue;
+        };
+
+        templates.insert(
+          name.into(),
+          fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        );
+      }
+    }
+
+    Ok(Self {
+      color_scheme,
+      dir,
+      logo,
+      templates,
+      title,
+    })
+  }
+
+  /// Read a static asset from the theme directory, returning `None` if there
+  /// is no theme directory or the asset is not overridden.
+  pub(crate) fn static_asset(&self, path: &str) -> Result<Option<Vec<u8>>> {
+    let Some(dir) = &self.dir else {
+      return Ok(None);
+    };
+
+    let path = std::path::Path::new(path);
+
+    // only serve files inside of the theme's static directory
+    if !path
+      .components()
+      .all(|component| matches!(component, Component::Normal(_)))
+    {
+      return Ok(None);
+    }
+
+    match fs::read(dir.join("static").join(path)) {
+      Ok(content) => Ok(Some(content)),
+      Err(err)
+        if matches!(
+          err.kind(),
+          io::ErrorKind::NotFound | io::ErrorKind::IsADirectory
+        ) =>
+      {
+        Ok(None)
+      }
+      Err(err) => Err(err.into()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn static_assets_are_read_from_theme_directory() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::create_dir(tempdir.path().join("static")).unwrap();
+    fs::write(tempdir.path().join("static/index.css"), "foo").unwrap();
+    fs::write(tempdir.path().join("secret"), "bar").unwrap();
+
+    let theme = Theme::load(Some(tempdir.path().into()), ColorScheme::Dark, None, None).unwrap();
+
+    assert!(theme.templates.is_empty());
+    assert_eq!(
+      theme.static_asset("index.css").unwrap(),
+      Some(b"foo".to_vec())
+    );
+    assert_eq!(theme.static_asset("index.js").unwrap(), None);
+    assert_eq!(theme.static_asset("../secret").unwrap(), None);
+    assert_eq!(theme.static_asset("/index.css").unwrap(), None);
+  }
+
+  #[test]
+  fn templates_are_loaded_from_theme_directory() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::create_dir(tempdir.path().join("templates")).unwrap();
+    fs::write(tempdir.path().join("templates/page.html"), "{{ content }}").unwrap();
+    fs::write(tempdir.path().join("templates/home.html"), "foo").unwrap();
+    fs::write(tempdir.path().join("templates/notes.txt"), "bar").unwrap();
+
+    assert_eq!(
+      Theme::load(Some(tempdir.path().into()), ColorScheme::Dark, None, None)
+        .unwrap()
+        .templates,
+      [
+        ("home.html".into(), "foo".into()),
+        ("page.html".into(), "{{ content }}".into()),
+      ]
+      .into(),
+    );
+  }
+
+  #[test]
+  fn no_theme_directory() {
+    assert_eq!(Theme::default().static_asset("index.css").unwrap(), None);
+  }
+}
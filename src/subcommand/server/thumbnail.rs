@@ -0,0 +1,487 @@
+use {
+  super::*,
+  image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits},
+  io::Write,
+  resvg::{
+    tiny_skia::{IntSize, Pixmap, Transform},
+    usvg::{self, ImageHrefResolver, Tree},
+  },
+};
+
+mod avif;
+
+pub(crate) const DEFAULT_SIZE: u32 = 256;
+pub(crate) const MAX_SIZE: u32 = 1024;
+pub(crate) const SIZES: [u32; 5] = [64, 128, 256, 512, MAX_SIZE];
+
+// Bodies larger than this, after decompression, are not rendered
+pub(crate) const MAX_BODY_SIZE: u64 = 16 * 1024 * 1024;
+
+// Images wider or taller than this are not decoded
+const MAX_DIMENSION: u32 = 8192;
+
+// Images with more pixels than this are not decoded
+const MAX_PIXELS: u32 = 4096 * 4096;
+
+// Rendered thumbnails are stored in a directory of PNG files named after the
+// inscription whose content was rendered and the thumbnail size. Once the
+// thumbnails take up more than `max_size` bytes, the least recently used are
+// removed.
+pub(crate) struct ThumbnailCache {
+  dir: PathBuf,
+  max_size: u64,
+  // total size of cached thumbnails, or `None` if not yet known
+  size: Mutex<Option<u64>>,
+}
+
+impl ThumbnailCache {
+  pub(crate) fn new(dir: PathBuf, max_size: u64) -> Self {
+    Self {
+      dir,
+      max_size,
+      size: Mutex::new(None),
+    }
+  }
+
+  pub(crate) fn get_or_render(
+    &self,
+    inscription_id: InscriptionId,
+    size: u32,
+    render: impl FnOnce() -> Result<Vec<u8>>,
+  ) -> Result<Vec<u8>> {
+    let path = self.dir.join(format!("{inscription_id}-{size}.png"));
+
+    if let Ok(thumbnail) = fs::read(&path) {
+      // the modification time records when a thumbnail was last used
+      if let Err(err) = fs::File::options()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+      {
+        log::warn!("failed to touch thumbnail {}: {err}", path.display());
+      }
+
+      return Ok(thumbnail);
+    }
+
+    let thumbnail = render()?;
+
+    if let Err(err) = self.store(&path, &thumbnail) {
+      log::warn!("failed to cache thumbnail {}: {err}", path.display());
+    }
+
+    Ok(thumbnail)
+  }
+
+  fn store(&self, path: &std::path::Path, thumbnail: &[u8]) -> io::Result<()> {
+    fs::create_dir_all(&self.dir)?;
+
+    // write to a temporary file first, so concurrent requests never read a
+    // partially written thumbnail
+    let mut file = tempfile::NamedTempFile::new_in(&self.dir)?;
+    file.write_all(thumbnail)?;
+    file.persist(path)?;
+
+    let mut size = self.size.lock().unwrap();
+
+    let total = match *size {
+      Some(total) => total + u64::try_from(thumbnail.len()).unwrap(),
+      None => self.thumbnails()?.iter().map(|(_, _, len)| len).sum(),
+    };
+
+    *size = Some(if total > self.max_size {
+      self.evict()?
+    } else {
+      total
+    });
+
+    Ok(())
+  }
+
+  // Removes the least recently used thumbnails until they take up at most
+  // three quarters of the maximum size, so that eviction is not needed again
+  // after every render, and returns the size of those that remain
+  fn evict(&self) -> io::Result<u64> {
+    let mut thumbnails = self.thumbnails()?;
+
+    thumbnails.sort_by_key(|(_, modified, _)| *modified);
+
+    let mut total = thumbnails.iter().map(|(_, _, len)| len).sum::<u64>();
+
+    for (path, _, len) in thumbnails {
+      if total <= self.max_size / 4 * 3 {
+        break;
+      }
+
+      match fs::remove_file(&path) {
+        Ok(()) => total -= len,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => total -= len,
+        Err(err) => return Err(err),
+      }
+    }
+
+    Ok(total)
+  }
+
+  fn thumbnails(&self) -> io::Result<Vec<(PathBuf, SystemTime, u64)>> {
+    let mut thumbnails = Vec::new();
+
+    for entry in fs::read_dir(&self.dir)? {
+      let entry = entry?;
+
+      let path = entry.path();
+
+      if path.extension() != Some("png".as_ref()) {
+        continue;
+      }
+
+      let metadata = match entry.metadata() {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+        Err(err) => return Err(err),
+      };
+
+      thumbnails.push((path, metadata.modified()?, metadata.len()));
+    }
+
+    Ok(thumbnails)
+  }
+}
+
+// Renders an image or SVG document as a PNG that fits within a square of
+// `size` pixels. Raster images are never scaled up.
+pub(crate) fn render(
+  content_type: &str,
+  body: &[u8],
+  image_rendering: ImageRendering,
+  size: u32,
+) -> Result<Vec<u8>> {
+  ensure!(
+    u64::try_from(body.len()).unwrap() <= MAX_BODY_SIZE,
+    "image of {} bytes is too large to render",
+    body.len(),
+  );
+
+  let mime = content_type.split(';').next().unwrap_or_default().trim();
+
+  if mime == "image/svg+xml" {
+    return render_svg(body, size);
+  }
+
+  let decode = |format| -> Result<DynamicImage> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    // enough for every pixel with four 16-bit channels
+    limits.max_alloc = Some(u64::from(MAX_PIXELS) * 8);
+
+    let mut reader = ImageReader::with_format(io::Cursor::new(body), format);
+    reader.limits(limits);
+
+    Ok(reader.decode()?)
+  };
+
+  let mut image = match mime {
+    "image/apng" => decode(ImageFormat::Png)?,
+    "image/avif" => avif::decode(body)?,
+    mime => decode(
+      ImageFormat::from_mime_type(mime)
+        .ok_or_else(|| anyhow!("cannot render content type `{content_type}`"))?,
+    )?,
+  };
+
+  if image.width() > size || image.height() > size {
+    image = image.resize(
+      size,
+      size,
+      match image_rendering {
+        ImageRendering::Auto => FilterType::CatmullRom,
+        ImageRendering::Pixelated => FilterType::Nearest,
+      },
+    );
+  }
+
+  let mut png = Vec::new();
+  image.write_to(&mut io::Cursor::new(&mut png), ImageFormat::Png)?;
+
+  Ok(png)
+}
+
+fn render_svg(body: &[u8], size: u32) -> Result<Vec<u8>> {
+  let options = usvg::Options {
+    image_href_resolver: ImageHrefResolver {
+      resolve_data: ImageHrefResolver::default_data_resolver(),
+      // hrefs that are not data URLs are treated as file paths, which must
+      // never be read while rendering inscription content
+      resolve_string: Box::new(|_, _| None),
+    },
+    ..default()
+  };
+
+  let tree = Tree::from_data(body, &options)?;
+
+  let dimensions = tree
+    .size()
+    .to_int_size()
+    .scale_to(IntSize::from_wh(size, size).context("invalid thumbnail size")?);
+
+  let scale = dimensions.width() as f32 / tree.size().width();
+
+  let mut pixmap =
+    Pixmap::new(dimensions.width(), dimensions.height()).context("invalid thumbnail dimensions")?;
+
+  resvg::render(
+    &tree,
+    Transform::from_scale(scale, scale),
+    &mut pixmap.as_mut(),
+  );
+
+  Ok(pixmap.encode_png()?)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, image::GenericImageView};
+
+  fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut png = Vec::new();
+    image::RgbaImage::from_fn(width, height, |x, y| {
+      image::Rgba([
+        u8::try_from(x % 256).unwrap(),
+        u8::try_from(y % 256).unwrap(),
+        0,
+        255,
+      ])
+    })
+    .write_to(&mut io::Cursor::new(&mut png), ImageFormat::Png)
+    .unwrap();
+    png
+  }
+
+  fn dimensions(png: &[u8]) -> (u32, u32) {
+    image::load_from_memory_with_format(png, ImageFormat::Png)
+      .unwrap()
+      .dimensions()
+  }
+
+  #[test]
+  fn large_images_are_scaled_down_preserving_aspect_ratio() {
+    assert_eq!(
+      dimensions(&render("image/png", &png(1000, 500), ImageRendering::Auto, 100).unwrap()),
+      (100, 50)
+    );
+
+    assert_eq!(
+      dimensions(&render("image/png", &png(500, 1000), ImageRendering::Pixelated, 100).unwrap()),
+      (50, 100)
+    );
+  }
+
+  #[test]
+  fn small_images_are_not_scaled_up() {
+    assert_eq!(
+      dimensions(&render("image/png", &png(24, 24), ImageRendering::Pixelated, 256).unwrap()),
+      (24, 24)
+    );
+  }
+
+  #[test]
+  fn pixelated_images_use_nearest_neighbor_scaling() {
+    let mut png = Vec::new();
+    image::RgbaImage::from_fn(4, 4, |x, _| {
+      if x < 2 {
+        image::Rgba([255, 0, 0, 255])
+      } else {
+        image::Rgba([0, 0, 255, 255])
+      }
+    })
+    .write_to(&mut io::Cursor::new(&mut png), ImageFormat::Png)
+    .unwrap();
+
+    let thumbnail =
+      image::load_from_memory(&render("image/png", &png, ImageRendering::Pixelated, 2).unwrap())
+        .unwrap();
+
+    assert_eq!(thumbnail.get_pixel(0, 0), image::Rgba([255, 0, 0, 255]));
+    assert_eq!(thumbnail.get_pixel(1, 0), image::Rgba([0, 0, 255, 255]));
+  }
+
+  #[test]
+  fn svg_is_rendered_to_bounded_raster() {
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 10"><rect width="20" height="10" fill="red"/></svg>"#;
+
+    let thumbnail = render("image/svg+xml", svg, ImageRendering::Auto, 200).unwrap();
+
+    assert_eq!(dimensions(&thumbnail), (200, 100));
+
+    assert_eq!(
+      image::load_from_memory(&thumbnail)
+        .unwrap()
+        .get_pixel(100, 50),
+      image::Rgba([255, 0, 0, 255])
+    );
+  }
+
+  #[test]
+  fn svg_does_not_read_local_files() {
+    let dir = TempDir::new().unwrap();
+
+    let path = dir.path().join("secret.svg");
+
+    fs::write(
+      &path,
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10" fill="red"/></svg>"#,
+    )
+    .unwrap();
+
+    let svg = format!(
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><image href="{}" width="10" height="10"/></svg>"#,
+      path.display()
+    );
+
+    let thumbnail = image::load_from_memory(
+      &render("image/svg+xml", svg.as_bytes(), ImageRendering::Auto, 10).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(thumbnail.get_pixel(5, 5), image::Rgba([0, 0, 0, 0]));
+  }
+
+  #[test]
+  fn oversized_images_are_an_error() {
+    assert!(render(
+      "image/png",
+      &png(MAX_DIMENSION + 1, 1),
+      ImageRendering::Auto,
+      256
+    )
+    .is_err());
+
+    assert!(render(
+      "image/png",
+      &vec![0; usize::try_from(MAX_BODY_SIZE).unwrap() + 1],
+      ImageRendering::Auto,
+      256
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn unsupported_content_types_are_an_error() {
+    assert!(render("text/plain", b"foo", ImageRendering::Auto, 256).is_err());
+    assert!(render("image/heic", b"foo", ImageRendering::Auto, 256).is_err());
+  }
+
+  #[test]
+  fn avif_is_decoded_with_alpha() {
+    // 4x4 image with a red left half, a blue right half, and a half
+    // transparent bottom row
+    let avif = hex::decode(concat!(
+      "000000186674797061766966000000006d6966316d696166000001686d6574610000000000000021",
+      "68646c72000000000000000070696374000000000000000000000000000000000e7069746d000000",
+      "0000010000002c696c6f630000000044000002000200000001000001880000003500010000000100",
+      "0001bd000000680000003869696e6600000000000200000015696e66650200000000010000617630",
+      "310000000015696e6665020000000002000061763031000000001a69726566000000000000000e61",
+      "75786c000200010001000000af697072700000008a6970636f000000146973706500000000000000",
+      "04000000040000000c61763143813f4000000000107069786900000000030a0a0a0000000c617631",
+      "43811f5c000000000e7069786900000000010a00000038617578430000000075726e3a6d7065673a",
+      "6d706567423a636963703a73797374656d733a617578696c696172793a616c706861000000001d69",
+      "706d61000000000000000200010301820300020401840605000000a56d64617412000a051fc47c8d",
+      "40322a6404c00020001000000000000000000202faff435bf84847c019b87a24740faeeb20f43351",
+      "a1cd2b16fd12000a083fc47c8c04341b40325a64041800040002000000000000000000402473369f",
+      "20f78a4346a2e1732d87d12d9b0d06b2cdc3855fdccffe821e7e799535d39dcae92eb455ee45a39e",
+      "b8993b1d50fba679c5e8f130f50c31384a545b2cdc7942f2f5406182c0",
+    ))
+    .unwrap();
+
+    let thumbnail =
+      image::load_from_memory(&render("image/avif", &avif, ImageRendering::Auto, 256).unwrap())
+        .unwrap();
+
+    assert_eq!(thumbnail.dimensions(), (4, 4));
+
+    let close = |actual: image::Rgba<u8>, expected: [u8; 4]| {
+      actual
+        .0
+        .iter()
+        .zip(expected)
+        .all(|(&actual, expected)| actual.abs_diff(expected) <= 8)
+    };
+
+    assert!(close(thumbnail.get_pixel(0, 0), [255, 0, 0, 255]));
+    assert!(close(thumbnail.get_pixel(3, 0), [0, 0, 255, 255]));
+    assert!(close(thumbnail.get_pixel(0, 3), [255, 0, 0, 128]));
+
+    assert!(render("image/avif", b"foo", ImageRendering::Auto, 256).is_err());
+  }
+
+  #[test]
+  fn cache_stores_rendered_thumbnails() {
+    let dir = TempDir::new().unwrap();
+
+    let cache = ThumbnailCache::new(dir.path().join("thumbnails"), u64::MAX);
+
+    assert_eq!(
+      cache
+        .get_or_render(inscription_id(1), 256, || Ok(vec![1, 2, 3]))
+        .unwrap(),
+      [1, 2, 3]
+    );
+
+    assert_eq!(
+      cache
+        .get_or_render(inscription_id(1), 256, || panic!(
+          "thumbnail was not cached"
+        ))
+        .unwrap(),
+      [1, 2, 3]
+    );
+
+    assert_eq!(
+      cache
+        .get_or_render(inscription_id(1), 512, || Ok(vec![4]))
+        .unwrap(),
+      [4]
+    );
+  }
+
+  #[test]
+  fn cache_removes_least_recently_used_thumbnails() {
+    let dir = TempDir::new().unwrap();
+
+    let cache = ThumbnailCache::new(dir.path().to_owned(), 24);
+
+    let path = |size| dir.path().join(format!("{}-{size}.png", inscription_id(1)));
+
+    cache
+      .get_or_render(inscription_id(1), 1, || Ok(vec![0; 10]))
+      .unwrap();
+
+    cache
+      .get_or_render(inscription_id(1), 2, || Ok(vec![0; 10]))
+      .unwrap();
+
+    let touched = SystemTime::now() - Duration::from_secs(60);
+
+    for size in [1u32, 2] {
+      fs::File::options()
+        .write(true)
+        .open(path(size))
+        .unwrap()
+        .set_modified(touched - Duration::from_secs(size.into()))
+        .unwrap();
+    }
+
+    cache
+      .get_or_render(inscription_id(1), 1, || panic!("thumbnail was not cached"))
+      .unwrap();
+
+    cache
+      .get_or_render(inscription_id(1), 3, || Ok(vec![0; 5]))
+      .unwrap();
+
+    assert!(path(1).exists());
+    assert!(!path(2).exists());
+    assert!(path(3).exists());
+  }
+}
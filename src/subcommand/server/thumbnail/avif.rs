@@ -0,0 +1,348 @@
+use {
+  super::*,
+  image::{DynamicImage, RgbaImage},
+  rav1d::{
+    include::dav1d::{
+      data::Dav1dData,
+      dav1d::{Dav1dContext, Dav1dSettings},
+      headers::{
+        DAV1D_MC_BT2020_CL, DAV1D_MC_BT2020_NCL, DAV1D_MC_BT709, DAV1D_MC_FCC, DAV1D_MC_IDENTITY,
+        DAV1D_MC_SMPTE240, DAV1D_PIXEL_LAYOUT_I400, DAV1D_PIXEL_LAYOUT_I420,
+        DAV1D_PIXEL_LAYOUT_I422,
+      },
+      picture::Dav1dPicture,
+    },
+    src::lib::{
+      dav1d_close, dav1d_data_create, dav1d_data_unref, dav1d_default_settings, dav1d_get_picture,
+      dav1d_open, dav1d_picture_unref, dav1d_send_data,
+    },
+  },
+  std::{mem::MaybeUninit, ptr::NonNull},
+};
+
+// Decodes the primary image of an AVIF file, along with its alpha channel, if
+// it has one.
+pub(super) fn decode(body: &[u8]) -> Result<DynamicImage> {
+  let avif = avif_parse::read_avif(&mut io::Cursor::new(body))?;
+
+  let color = Plane::decode(&avif.primary_item)?;
+
+  let alpha = avif
+    .alpha_item
+    .as_ref()
+    .map(|alpha| Plane::decode(alpha))
+    .transpose()?;
+
+  let mut image = RgbaImage::new(color.width, color.height);
+
+  for (x, y, pixel) in image.enumerate_pixels_mut() {
+    let [r, g, b] = color.rgb(x, y);
+
+    let a = alpha
+      .as_ref()
+      .filter(|alpha| x < alpha.width && y < alpha.height)
+      .map(|alpha| alpha.luma(x, y))
+      .unwrap_or(1.0);
+
+    let [r, g, b] = if avif.premultiplied_alpha && a > 0.0 {
+      [r / a, g / a, b / a]
+    } else {
+      [r, g, b]
+    };
+
+    // channels are clamped to between zero and one, so they fit in a `u8`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rgba = [r, g, b, a].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+    *pixel = image::Rgba(rgba);
+  }
+
+  Ok(DynamicImage::ImageRgba8(image))
+}
+
+// A decoded AV1 frame, with its samples normalized to between zero and one
+struct Plane {
+  chroma: Option<[Vec<f32>; 2]>,
+  chroma_width: u32,
+  coefficients: Option<(f32, f32)>,
+  height: u32,
+  luma: Vec<f32>,
+  subsampling: (u32, u32),
+  width: u32,
+}
+
+impl Plane {
+  fn decode(obus: &[u8]) -> Result<Self> {
+    let decoder = Decoder::new()?;
+    let picture = decoder.decode(obus)?;
+
+    let width = u32::try_from(picture.0.p.w)?;
+    let height = u32::try_from(picture.0.p.h)?;
+    let bits = u32::try_from(picture.0.p.bpc)?;
+
+    ensure!(width > 0 && height > 0, "AV1 frame is empty");
+
+    ensure!(
+      width <= MAX_DIMENSION && height <= MAX_DIMENSION,
+      "AV1 frame of {width}x{height} pixels is too large",
+    );
+
+    // SAFETY: `seq_hdr` of a picture returned by `dav1d_get_picture` points to
+    // the sequence header of the picture, which lives as long as the picture
+    let header = unsafe {
+      picture
+        .0
+        .seq_hdr
+        .context("AV1 frame has no sequence header")?
+        .as_ref()
+    };
+
+    let subsampling = match picture.0.p.layout {
+      DAV1D_PIXEL_LAYOUT_I420 => (1, 1),
+      DAV1D_PIXEL_LAYOUT_I422 => (1, 0),
+      _ => (0, 0),
+    };
+
+    let full_range = header.color_range != 0;
+
+    let scale = (1 << (bits - 8)) as f32;
+    let max = ((1 << bits) - 1) as f32;
+
+    let luma = picture
+      .samples(0, width, height, bits)?
+      .into_iter()
+      .map(|sample| {
+        if full_range {
+          f32::from(sample) / max
+        } else {
+          (f32::from(sample) - 16.0 * scale) / (219.0 * scale)
+        }
+      })
+      .collect();
+
+    let chroma_width = (width + subsampling.0) >> subsampling.0;
+    let chroma_height = (height + subsampling.1) >> subsampling.1;
+
+    let chroma = if picture.0.p.layout == DAV1D_PIXEL_LAYOUT_I400 {
+      None
+    } else {
+      let plane = |plane| -> Result<Vec<f32>> {
+        Ok(
+          picture
+            .samples(plane, chroma_width, chroma_height, bits)?
+            .into_iter()
+            .map(|sample| {
+              if full_range {
+                f32::from(sample) / max - 0.5
+              } else {
+                (f32::from(sample) - 128.0 * scale) / (224.0 * scale)
+              }
+            })
+            .collect(),
+        )
+      };
+
+      Some([plane(1)?, plane(2)?])
+    };
+
+    // luma coefficients of red and blue, with unspecified matrices treated as
+    // BT.601, and the identity matrix, where the planes are green, blue, and
+    // red, as `None`
+    let coefficients = match header.mtrx {
+      DAV1D_MC_IDENTITY => None,
+      DAV1D_MC_BT709 => Some((0.2126, 0.0722)),
+      DAV1D_MC_BT2020_NCL | DAV1D_MC_BT2020_CL => Some((0.2627, 0.0593)),
+      DAV1D_MC_FCC => Some((0.30, 0.11)),
+      DAV1D_MC_SMPTE240 => Some((0.212, 0.087)),
+      _ => Some((0.299, 0.114)),
+    };
+
+    Ok(Self {
+      chroma,
+      chroma_width,
+      coefficients,
+      height,
+      luma,
+      subsampling,
+      width,
+    })
+  }
+
+  fn luma(&self, x: u32, y: u32) -> f32 {
+    self.luma[(y * self.width + x).into_usize()]
+  }
+
+  fn rgb(&self, x: u32, y: u32) -> [f32; 3] {
+    let luma = self.luma(x, y);
+
+    let Some([cb, cr]) = &self.chroma else {
+      return [luma; 3];
+    };
+
+    let i =
+      ((y >> self.subsampling.1) * self.chroma_width + (x >> self.subsampling.0)).into_usize();
+
+    let (cb, cr) = (cb[i], cr[i]);
+
+    let Some((kr, kb)) = self.coefficients else {
+      return [cr + 0.5, luma, cb + 0.5];
+    };
+
+    let r = luma + 2.0 * (1.0 - kr) * cr;
+    let b = luma + 2.0 * (1.0 - kb) * cb;
+    let g = (luma - kr * r - kb * b) / (1.0 - kr - kb);
+
+    [r, g, b]
+  }
+}
+
+struct Decoder(Option<Dav1dContext>);
+
+impl Decoder {
+  fn new() -> Result<Self> {
+    let mut settings = MaybeUninit::<Dav1dSettings>::uninit();
+
+    // SAFETY: `dav1d_default_settings` initializes the settings it is given
+    let mut settings = unsafe {
+      dav1d_default_settings(NonNull::new(settings.as_mut_ptr()).unwrap());
+      settings.assume_init()
+    };
+
+    // thumbnails are rendered on a blocking thread, and each frame is
+    // decoded as soon as it is sent
+    settings.n_threads = 1;
+    settings.max_frame_delay = 1;
+
+    // frames with more pixels are rejected before they are allocated
+    settings.frame_size_limit = MAX_PIXELS;
+
+    let mut context = None;
+
+    // SAFETY: both pointers are valid for the duration of the call
+    check(
+      unsafe {
+        dav1d_open(
+          Some(NonNull::from(&mut context)),
+          Some(NonNull::from(&mut settings)),
+        )
+      }
+      .0,
+    )?;
+
+    Ok(Self(context))
+  }
+
+  fn decode(&self, obus: &[u8]) -> Result<Picture> {
+    let mut data = Dav1dData::default();
+
+    // SAFETY: `data` is valid to write to, and on success is given a buffer
+    // of `obus.len()` bytes
+    let buffer = unsafe { dav1d_data_create(Some(NonNull::from(&mut data)), obus.len()) };
+
+    ensure!(!buffer.is_null(), "failed to allocate AV1 data");
+
+    // SAFETY: `buffer` was just allocated with room for `obus.len()` bytes
+    unsafe { std::slice::from_raw_parts_mut(buffer, obus.len()) }.copy_from_slice(obus);
+
+    let mut picture = Picture(Dav1dPicture::default());
+
+    let result = loop {
+      if data.sz > 0 {
+        // SAFETY: the context is open and `data` is valid to read and write
+        let result = unsafe { dav1d_send_data(self.0, Some(NonNull::from(&mut data))) }.0;
+
+        if !again(result) {
+          if let Err(err) = check(result) {
+            break Err(err);
+          }
+        }
+      }
+
+      // SAFETY: the context is open and `picture` is valid to write to
+      let result = unsafe { dav1d_get_picture(self.0, Some(NonNull::from(&mut picture.0))) }.0;
+
+      if again(result) && data.sz > 0 {
+        continue;
+      }
+
+      break check(result).context("AV1 data contains no frame");
+    };
+
+    // SAFETY: `data` was created by `dav1d_data_create`
+    unsafe { dav1d_data_unref(Some(NonNull::from(&mut data))) };
+
+    result.map(|()| picture)
+  }
+}
+
+impl Drop for Decoder {
+  fn drop(&mut self) {
+    // SAFETY: the context was opened by `dav1d_open` and is only closed here
+    unsafe { dav1d_close(Some(NonNull::from(&mut self.0))) };
+  }
+}
+
+struct Picture(Dav1dPicture);
+
+impl Picture {
+  // Samples of `plane`, which has `width` by `height` samples, in row order
+  fn samples(&self, plane: usize, width: u32, height: u32, bits: u32) -> Result<Vec<u16>> {
+    let data = self.0.data[plane].context("AV1 frame is missing a plane")?;
+    let stride = usize::try_from(self.0.stride[plane.min(1)])?;
+
+    let (width, height) = (width.into_usize(), height.into_usize());
+
+    let bytes_per_sample = if bits > 8 { 2 } else { 1 };
+
+    ensure!(
+      stride >= width * bytes_per_sample,
+      "AV1 frame stride is too small"
+    );
+
+    // SAFETY: planes of pictures returned by `dav1d_get_picture` have
+    // `height` rows of `stride` bytes, which live as long as the picture
+    let bytes = unsafe { std::slice::from_raw_parts(data.as_ptr().cast::<u8>(), stride * height) };
+
+    let mut samples = Vec::with_capacity(width * height);
+
+    for row in bytes.chunks_exact(stride) {
+      let row = &row[..width * bytes_per_sample];
+
+      if bytes_per_sample == 2 {
+        samples.extend(
+          row
+            .chunks_exact(2)
+            .map(|sample| u16::from_ne_bytes([sample[0], sample[1]])),
+        );
+      } else {
+        samples.extend(row.iter().copied().map(u16::from));
+      }
+    }
+
+    Ok(samples)
+  }
+}
+
+impl Drop for Picture {
+  fn drop(&mut self) {
+    // SAFETY: the picture is either default, or was returned by
+    // `dav1d_get_picture`, and is only released here
+    unsafe { dav1d_picture_unref(Some(NonNull::from(&mut self.0))) };
+  }
+}
+
+// results are zero on success, and otherwise a negated error number
+fn again(result: i32) -> bool {
+  result < 0 && io::Error::from_raw_os_error(-result).kind() == io::ErrorKind::WouldBlock
+}
+
+fn check(result: i32) -> Result {
+  if result < 0 {
+    bail!(
+      "failed to decode AV1 data: {}",
+      io::Error::from_raw_os_error(-result)
+    );
+  }
+
+  Ok(())
+}
@@ -23,7 +23,7 @@ impl Batch {
       &wallet,
       utxos,
       parent_info.as_ref().map(|info| info.tx_out.value),
-      self.shared.compress,
+      &self.shared.compress,
     )?;
 
     let mut locked_utxos = wallet.locked_utxos().clone();
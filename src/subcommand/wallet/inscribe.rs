@@ -69,7 +69,7 @@ impl Inscribe {
       etching: None,
       inscriptions: vec![Inscription::new(
         chain,
-        self.shared.compress,
+        &self.shared.compress,
         self.delegate,
         Inscribe::parse_metadata(self.cbor_metadata, self.json_metadata)?,
         self.metaprotocol,
@@ -7,8 +7,16 @@ pub(super) struct SharedArgs {
     help = "Use <COMMIT_FEE_RATE> sats/vbyte for commit transaction.\nDefaults to <FEE_RATE> if unset."
   )]
   pub(crate) commit_fee_rate: Option<FeeRate>,
-  #[arg(long, help = "Compress inscription content with brotli.")]
-  pub(crate) compress: bool,
+  #[arg(
+    long,
+    value_name = "ALGORITHMS",
+    num_args = 0..=1,
+    require_equals = true,
+    value_delimiter = ',',
+    default_missing_value = "br",
+    help = "Compress inscription content with each of comma-separated <ALGORITHMS> and keep the smallest result. Compresses with brotli if no algorithms are given."
+  )]
+  pub(crate) compress: Vec<Compression>,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
   pub(crate) fee_rate: FeeRate,
   #[arg(long, help = "Don't sign or broadcast transactions.")]
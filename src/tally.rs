@@ -17,7 +17,11 @@ pub(crate) struct Tallied {
 
 impl Display for Tallied {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-    if self.count == 1 {
+    let locale = Locale::current();
+
+    if locale != Locale::En {
+      write!(f, "{} {}", self.count, locale.translate(self.noun))
+    } else if self.count == 1 {
       write!(f, "{} {}", self.count, self.noun)
     } else {
       write!(f, "{} {}s", self.count, self.noun)
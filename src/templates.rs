@@ -1,7 +1,10 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
-  crate::subcommand::server::ServerConfig,
+  crate::{
+    locale::{t, tf, Sats, Time},
+    subcommand::server::ServerConfig,
+  },
   address::AddressHtml,
   block::BlockHtml,
   children::ChildrenHtml,
@@ -17,8 +20,9 @@ pub(crate) use {
   output::OutputHtml,
   parents::ParentsHtml,
   preview::{
-    PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-    PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
+    PreviewAudioHtml, PreviewCodeHtml, PreviewCsvHtml, PreviewFontHtml, PreviewImageHtml,
+    PreviewJsonHtml, PreviewMarkdownHtml, PreviewMidiHtml, PreviewModelHtml, PreviewPdfHtml,
+    PreviewStlHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
   },
   range::RangeHtml,
   rare::RareTxt,
@@ -26,8 +30,9 @@ pub(crate) use {
 };
 
 pub use {
-  blocks::BlocksHtml, rune::RuneHtml, runes::RunesHtml, status::StatusHtml,
-  transaction::TransactionHtml,
+  blocks::BlocksHtml, collection::CollectionHtml, rune::RuneHtml, rune_mints::RuneMintsHtml,
+  rune_search::RuneSearchHtml, runes::RunesHtml, runes_minting::RunesMintingHtml,
+  status::StatusHtml, transaction::TransactionHtml,
 };
 
 pub mod address;
@@ -35,6 +40,7 @@ pub mod block;
 pub mod blocks;
 mod children;
 mod clock;
+pub mod collection;
 pub mod collections;
 mod home;
 mod iframe;
@@ -49,7 +55,10 @@ mod preview;
 mod range;
 mod rare;
 pub mod rune;
+pub mod rune_mints;
+pub mod rune_search;
 pub mod runes;
+pub mod runes_minting;
 pub mod sat;
 pub mod status;
 pub mod transaction;
@@ -76,6 +85,68 @@ where
     }
   }
 
+  fn site_title(&self) -> &str {
+    self.config.theme.title.as_deref().unwrap_or("Ordinals")
+  }
+
+  // Render the theme's layout, if it has one, with the page content
+  fn layout(&self) -> Option<String> {
+    let layout = self.config.theme.templates.get("page.html")?;
+    Some(self.substitute(layout, &self.render_content()))
+  }
+
+  // Render the page content with the theme's template for it, if it has one,
+  // and otherwise with the compiled template
+  fn render_content(&self) -> String {
+    let content = self.content.to_string();
+
+    match self.config.theme.templates.get(&T::template()) {
+      Some(template) => self.substitute(template, &content),
+      None => content,
+    }
+  }
+
+  // Replace placeholders like `{{ title }}` in a theme template with escaped
+  // values, and `{{ content }}` with `content`
+  fn substitute(&self, mut template: &str, content: &str) -> String {
+    struct Escaped<'a>(&'a str);
+
+    impl Display for Escaped<'_> {
+      fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.escape(f, false)
+      }
+    }
+
+    let mut output = String::new();
+
+    while let Some(start) = template.find("{{") {
+      let Some(end) = template[start..].find("}}").map(|end| start + end + 2) else {
+        break;
+      };
+
+      output.push_str(&template[..start]);
+
+      let value = match template[start + 2..end - 2].trim() {
+        "color_scheme" => self.config.theme.color_scheme.to_string(),
+        "content" => content.into(),
+        "logo" => Escaped(self.config.theme.logo.as_deref().unwrap_or_default()).to_string(),
+        "og_image" => Escaped(&self.og_image()).to_string(),
+        "site_title" => Escaped(self.site_title()).to_string(),
+        "superscript" => Escaped(&self.superscript()).to_string(),
+        "title" => Escaped(&self.content.title()).to_string(),
+        _ => template[start..end].into(),
+      };
+
+      output.push_str(&value);
+
+      template = &template[end..];
+    }
+
+    output.push_str(template);
+
+    output
+  }
+
   fn superscript(&self) -> String {
     if self.config.chain == Chain::Mainnet {
       "alpha".into()
@@ -88,6 +159,35 @@ where
 pub(crate) trait PageContent: Display + 'static {
   fn title(&self) -> String;
 
+  /// Name of the template the content is rendered with, derived from the type
+  /// name the same way boilerplate does, so `RunesMintingHtml` is rendered
+  /// with `runes-minting.html`.
+  fn template() -> String
+  where
+    Self: Sized,
+  {
+    let name = std::any::type_name::<Self>();
+    let name = name.rsplit("::").next().unwrap_or(name);
+    let name = name.strip_suffix("Html").unwrap_or(name);
+
+    let mut template = String::new();
+
+    for (i, c) in name.chars().enumerate() {
+      if c.is_ascii_uppercase() {
+        if i > 0 {
+          template.push('-');
+        }
+        template.push(c.to_ascii_lowercase());
+      } else {
+        template.push(c);
+      }
+    }
+
+    template.push_str(".html");
+
+    template
+  }
+
   fn page(self, server_config: Arc<ServerConfig>) -> PageHtml<Self>
   where
     Self: Sized,
@@ -98,7 +198,10 @@ pub(crate) trait PageContent: Display + 'static {
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use {
+    super::*,
+    crate::subcommand::server::theme::{ColorScheme, Theme},
+  };
 
   struct Foo;
 
@@ -125,7 +228,7 @@ mod tests {
         ..default()
       }),),
       r"<!doctype html>
-<html lang=en>
+<html lang=en data-color-scheme=dark>
   <head>
     <meta charset=utf-8>
     <meta name=format-detection content='telephone=no'>
@@ -178,6 +281,76 @@ mod tests {
     );
   }
 
+  #[test]
+  fn page_with_site_title_logo_and_color_scheme() {
+    assert_regex_match!(
+      Foo.page(Arc::new(ServerConfig {
+        chain: Chain::Mainnet,
+        theme: Theme {
+          color_scheme: ColorScheme::Light,
+          logo: Some("https://example.com/logo.png".into()),
+          title: Some("Example <Explorer>".into()),
+          ..default()
+        },
+        ..default()
+      })),
+      r"<!doctype html>
+<html lang=en data-color-scheme=light>.*<nav>
+      <a href=/ title=home><img class=logo src='https://example.com/logo.png'>Example &lt;Explorer&gt;<sup>alpha</sup></a>.*"
+    );
+  }
+
+  #[test]
+  fn page_with_theme_layout() {
+    assert_eq!(
+      Foo
+        .page(Arc::new(ServerConfig {
+          chain: Chain::Signet,
+          theme: Theme {
+            templates: [(
+              "page.html".into(),
+              "<title>{{ title }} | {{site_title}}</title>{{ superscript }}{{ content }}{{ unknown }}{{"
+                .into()
+            )]
+            .into(),
+            title: Some("<Example>".into()),
+            ..default()
+          },
+          ..default()
+        }))
+        .to_string(),
+      "<title>Foo | &lt;Example&gt;</title>signet<h1>Foo</h1>{{ unknown }}{{\n",
+    );
+  }
+
+  #[test]
+  fn page_with_theme_content_template() {
+    assert_eq!(
+      Foo
+        .page(Arc::new(ServerConfig {
+          theme: Theme {
+            templates: [
+              ("foo.html".into(), "<main>{{ content }}</main>".into()),
+              ("page.html".into(), "{{ title }}: {{ content }}".into()),
+            ]
+            .into(),
+            ..default()
+          },
+          ..default()
+        }))
+        .to_string(),
+      "Foo: <main><h1>Foo</h1></main>\n",
+    );
+  }
+
+  #[test]
+  fn template_names_match_template_files() {
+    assert_eq!(Foo::template(), "foo.html");
+    assert_eq!(TransactionHtml::template(), "transaction.html");
+    assert_eq!(InscriptionsBlockHtml::template(), "inscriptions-block.html");
+    assert_eq!(RunesMintingHtml::template(), "runes-minting.html");
+  }
+
   #[test]
   fn page_no_sat_index() {
     assert_regex_match!(
@@ -7,6 +7,10 @@ pub(crate) struct AddressHtml {
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) sat_balance: u64,
   pub(crate) runes_balances: Vec<(SpacedRune, Decimal, Option<char>)>,
+  pub(crate) rarities: Option<BTreeMap<Rarity, u64>>,
+  pub(crate) transactions: u64,
+  pub(crate) prev: Option<usize>,
+  pub(crate) next: Option<usize>,
 }
 
 impl PageContent for AddressHtml {
@@ -52,6 +56,14 @@ mod tests {
           Some('F'),
         ),
       ],
+      rarities: Some(
+        [(Rarity::Common, 98), (Rarity::Uncommon, 1)]
+          .into_iter()
+          .collect(),
+      ),
+      transactions: 3,
+      prev: None,
+      next: None,
     }
   }
 
@@ -84,6 +96,38 @@ mod tests {
     assert_regex_match!(address_html, expected_pattern);
   }
 
+  #[test]
+  fn test_transactions_rendering() {
+    let address_html = setup();
+    let expected_pattern = r#".*<dt>transactions</dt>\n\s*<dd>3</dd>.*"#;
+    assert_regex_match!(address_html, expected_pattern);
+  }
+
+  #[test]
+  fn test_rarities_rendering() {
+    let address_html = setup();
+    let expected_pattern = r#".*<dt>sat rarities</dt>\n\s*<dd><span class=common>common</span>: 98</dd>\n\s*<dd><span class=uncommon>uncommon</span>: 1</dd>.*"#;
+    assert_regex_match!(address_html, expected_pattern);
+  }
+
+  #[test]
+  fn test_rarities_not_rendered_without_sat_index() {
+    let mut address_html = setup();
+    address_html.rarities = None;
+    assert!(!address_html.to_string().contains("sat rarities"));
+  }
+
+  #[test]
+  fn test_pagination_rendering() {
+    let mut address_html = setup();
+    assert!(!address_html.to_string().contains("class=next"));
+
+    address_html.prev = Some(0);
+    address_html.next = Some(2);
+    let expected_pattern = r#".*<div class=center>\n<a class=prev href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8/0>prev</a>\n<a class=next href=/address/bc1phuq0vkls6w926zdaem6x9n02z2gg7j2xfudgwddyey7uyquarlgsh40ev8/2>next</a>\n</div>.*"#;
+    assert_regex_match!(address_html, expected_pattern);
+  }
+
   #[test]
   fn test_outputs_rendering() {
     let address_html = setup();
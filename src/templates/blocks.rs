@@ -1,9 +1,12 @@
 use super::*;
 
-#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "Blocks")]
 pub struct BlocksHtml {
   pub last: u32,
+  #[schemars(with = "Vec<api::schema::BlockHash>")]
   pub blocks: Vec<BlockHash>,
+  #[schemars(with = "BTreeMap<api::schema::BlockHash, Vec<InscriptionId>>")]
   pub featured_blocks: BTreeMap<BlockHash, Vec<InscriptionId>>,
 }
 
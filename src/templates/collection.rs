@@ -0,0 +1,232 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "Collection")]
+pub struct CollectionHtml {
+  pub children: Vec<CollectionChild>,
+  pub filters: BTreeMap<String, String>,
+  pub matches: usize,
+  pub more: bool,
+  pub page_index: usize,
+  pub parent: InscriptionId,
+  pub parent_number: i32,
+  pub total: usize,
+  pub traits: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CollectionChild {
+  pub id: InscriptionId,
+  pub rank: usize,
+  pub rarity_score: f64,
+  pub traits: BTreeMap<String, String>,
+}
+
+impl CollectionHtml {
+  fn filter_url(&self, name: &str, value: &str) -> String {
+    let mut filters = self.filters.clone();
+
+    if filters.get(name).map(String::as_str) == Some(value) {
+      filters.remove(name);
+    } else {
+      filters.insert(name.into(), value.into());
+    }
+
+    self.url(None, &filters)
+  }
+
+  fn page_url(&self, page_index: usize) -> String {
+    self.url(Some(page_index), &self.filters)
+  }
+
+  fn url(&self, page_index: Option<usize>, filters: &BTreeMap<String, String>) -> String {
+    let mut url = format!("/collection/{}", self.parent);
+
+    if let Some(page_index) = page_index {
+      url.push_str(&format!("/{page_index}"));
+    }
+
+    for (i, (name, value)) in filters.iter().enumerate() {
+      url.push(if i == 0 { '?' } else { '&' });
+      url.push_str("trait.");
+      url.push_str(&urlencoding::encode(name));
+      url.push('=');
+      url.push_str(&urlencoding::encode(value));
+    }
+
+    url
+  }
+
+  fn ranked(&self) -> Vec<&CollectionChild> {
+    let mut children = self.children.iter().collect::<Vec<&CollectionChild>>();
+    children.sort_by_key(|child| child.rank);
+    children
+  }
+}
+
+impl PageContent for CollectionHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Collection", self.parent_number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn collection(filters: BTreeMap<String, String>) -> CollectionHtml {
+    CollectionHtml {
+      children: vec![
+        CollectionChild {
+          id: inscription_id(2),
+          rank: 2,
+          rarity_score: 3.0,
+          traits: [("eyes".into(), "blue".into())].into(),
+        },
+        CollectionChild {
+          id: inscription_id(3),
+          rank: 1,
+          rarity_score: 3.5,
+          traits: [("eyes".into(), "laser eyes".into())].into(),
+        },
+      ],
+      filters,
+      matches: 2,
+      more: false,
+      page_index: 0,
+      parent: inscription_id(1),
+      parent_number: 0,
+      total: 2,
+      traits: [(
+        "eyes".into(),
+        [("blue".into(), 1), ("laser eyes".into(), 1)].into(),
+      )]
+      .into(),
+    }
+  }
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      collection(BTreeMap::new()),
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <h2>Traits</h2>
+        <table>
+          <tr>
+            <th>trait</th>
+            <th>value</th>
+            <th>count</th>
+          </tr>
+          <tr>
+            <td>eyes</td>
+            <td><a href=/collection/1{64}i1\\?trait.eyes=blue>blue</a></td>
+            <td>1</td>
+          </tr>
+          <tr>
+            <td>eyes</td>
+            <td><a href=/collection/1{64}i1\\?trait.eyes=laser%20eyes>laser eyes</a></td>
+            <td>1</td>
+          </tr>
+        </table>
+        <h2>2 Children</h2>
+        <div class=thumbnails>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+          <a href=/inscription/3{64}i3><iframe .* src=/preview/3{64}i3></iframe></a>
+        </div>
+        <div class=center>
+        prev
+        next
+        </div>
+        <h2>Rarity</h2>
+        <table>
+          <tr>
+            <th>rank</th>
+            <th>inscription</th>
+            <th>score</th>
+          </tr>
+          <tr>
+            <td>1</td>
+            <td><a class=monospace href=/inscription/3{64}i3>3{64}i3</a></td>
+            <td>3.50</td>
+          </tr>
+          <tr>
+            <td>2</td>
+            <td><a class=monospace href=/inscription/2{64}i2>2{64}i2</a></td>
+            <td>3.00</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn filtered() {
+    let mut collection = collection([("eyes".into(), "blue".into())].into());
+    collection.children.remove(1);
+    collection.matches = 1;
+
+    assert_regex_match!(
+      collection,
+      "
+        .*
+        <p>Showing 1 of 2 children. <a href=/collection/1{64}i1>Clear filters</a></p>
+        .*
+            <td><a class=selected href=/collection/1{64}i1>blue</a></td>
+        .*
+            <td><a href=/collection/1{64}i1\\?trait.eyes=laser%20eyes>laser eyes</a></td>
+        .*
+        <h2>1 Child</h2>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn paginated() {
+    let mut collection = collection([("eyes".into(), "blue".into())].into());
+    collection.matches = 250;
+    collection.more = true;
+    collection.page_index = 1;
+
+    assert_regex_match!(
+      collection,
+      "
+        .*
+        <h2>250 Children</h2>
+        .*
+        <div class=center>
+          <a class=prev href=/collection/1{64}i1/0\\?trait.eyes=blue>prev</a>
+          <a class=next href=/collection/1{64}i1/2\\?trait.eyes=blue>next</a>
+        </div>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      CollectionHtml {
+        children: Vec::new(),
+        filters: BTreeMap::new(),
+        matches: 0,
+        more: false,
+        page_index: 0,
+        parent: inscription_id(1),
+        parent_number: 0,
+        total: 0,
+        traits: BTreeMap::new(),
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <h2>0 Children</h2>
+        <p>No children.</p>
+      "
+      .unindent()
+    );
+  }
+}
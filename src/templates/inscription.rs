@@ -60,7 +60,7 @@ mod tests {
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
-          <dd><time>1970-01-01 00:00:00 UTC</time></dd>
+          <dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
           <dt>height</dt>
           <dd><a href=/block/0>0</a></dd>
           <dt>fee</dt>
@@ -243,7 +243,7 @@ mod tests {
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
-          <dd><time>1970-01-01 00:00:00 UTC</time></dd>
+          <dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
           <dt>height</dt>
           <dd><a href=/block/0>0</a></dd>
           <dt>fee</dt>
@@ -292,6 +292,7 @@ mod tests {
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all</a>
+              <a href=/collection/1{64}i1>gallery</a>
             </div>
           </dd>
           <dt>id</dt>
@@ -305,7 +306,7 @@ mod tests {
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
-          <dd><time>1970-01-01 00:00:00 UTC</time></dd>
+          <dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
           <dt>height</dt>
           <dd><a href=/block/0>0</a></dd>
           <dt>fee</dt>
@@ -353,6 +354,7 @@ mod tests {
             </div>
             <div class=center>
               <a href=/children/1{64}i1>all</a>
+              <a href=/collection/1{64}i1>gallery</a>
             </div>
           </dd>
           <dt>id</dt>
@@ -366,7 +368,7 @@ mod tests {
           <dt>content type</dt>
           <dd>text/plain;charset=utf-8</dd>
           <dt>timestamp</dt>
-          <dd><time>1970-01-01 00:00:00 UTC</time></dd>
+          <dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
           <dt>height</dt>
           <dd><a href=/block/0>0</a></dd>
           <dt>fee</dt>
@@ -11,6 +11,11 @@ pub(crate) struct PreviewCodeHtml {
   pub(crate) language: media::Language,
 }
 
+#[derive(Boilerplate)]
+pub(crate) struct PreviewCsvHtml {
+  pub(crate) inscription_id: InscriptionId,
+}
+
 #[derive(Boilerplate)]
 pub(crate) struct PreviewFontHtml {
   pub(crate) inscription_id: InscriptionId,
@@ -22,11 +27,21 @@ pub(crate) struct PreviewImageHtml {
   pub(crate) inscription_id: InscriptionId,
 }
 
+#[derive(Boilerplate)]
+pub(crate) struct PreviewJsonHtml {
+  pub(crate) inscription_id: InscriptionId,
+}
+
 #[derive(Boilerplate)]
 pub(crate) struct PreviewMarkdownHtml {
   pub(crate) inscription_id: InscriptionId,
 }
 
+#[derive(Boilerplate)]
+pub(crate) struct PreviewMidiHtml {
+  pub(crate) inscription_id: InscriptionId,
+}
+
 #[derive(Boilerplate)]
 pub(crate) struct PreviewModelHtml {
   pub(crate) inscription_id: InscriptionId,
@@ -37,6 +52,11 @@ pub(crate) struct PreviewPdfHtml {
   pub(crate) inscription_id: InscriptionId,
 }
 
+#[derive(Boilerplate)]
+pub(crate) struct PreviewStlHtml {
+  pub(crate) inscription_id: InscriptionId,
+}
+
 #[derive(Boilerplate)]
 pub(crate) struct PreviewTextHtml {
   pub(crate) inscription_id: InscriptionId,
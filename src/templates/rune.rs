@@ -1,6 +1,7 @@
 use super::*;
 
-#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "Rune")]
 pub struct RuneHtml {
   pub entry: RuneEntry,
   pub id: RuneId,
@@ -57,7 +58,7 @@ mod tests {
   <dt>number</dt>
   <dd>25</dd>
   <dt>timestamp</dt>
-  <dd><time>1970-01-01 00:00:00 UTC</time></dd>
+  <dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
   <dt>id</dt>
   <dd>10:9</dd>
   <dt>etching block</dt>
@@ -74,7 +75,7 @@ mod tests {
       <dt>amount</dt>
       <dd>1.000000001 %</dd>
       <dt>mints</dt>
-      <dd>100</dd>
+      <dd><a href=/rune/B•CGDENLQRQWDSLRUGSNLBTMFIJAV/mints>100</a></dd>
       <dt>cap</dt>
       <dd>101</dd>
       <dt>remaining</dt>
@@ -218,7 +219,7 @@ mod tests {
       <dt>amount</dt>
       <dd>none</dd>
       <dt>mints</dt>
-      <dd>0</dd>
+      <dd><a href=/rune/B•CGDENLQRQWDSLRUGSNLBTMFIJAV/mints>0</a></dd>
       <dt>cap</dt>
       <dd>0</dd>
       <dt>remaining</dt>
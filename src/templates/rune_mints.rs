@@ -0,0 +1,170 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "RuneMints")]
+pub struct RuneMintsHtml {
+  pub activity: Vec<RuneBlockActivity>,
+  pub blocks_until_end: Option<u64>,
+  pub blocks_until_start: Option<u64>,
+  pub entry: RuneEntry,
+  pub id: RuneId,
+  pub mintable: bool,
+  pub projected_mint_out: Option<u64>,
+  pub recent_mints: Vec<RuneMint>,
+  pub remaining: u128,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RuneBlockActivity {
+  pub burned: u128,
+  pub height: u32,
+  pub mints: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RuneMint {
+  pub height: u32,
+  #[schemars(with = "api::schema::Txid")]
+  pub txid: Txid,
+}
+
+impl PageContent for RuneMintsHtml {
+  fn title(&self) -> String {
+    format!("Rune {} Mints", self.entry.spaced_rune)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RuneMintsHtml {
+        activity: vec![
+          RuneBlockActivity {
+            burned: 0,
+            height: 12,
+            mints: 3,
+          },
+          RuneBlockActivity {
+            burned: 5,
+            height: 10,
+            mints: 0,
+          },
+        ],
+        blocks_until_end: Some(8),
+        blocks_until_start: Some(0),
+        entry: RuneEntry {
+          burned: 5,
+          divisibility: 1,
+          mints: 3,
+          spaced_rune: SpacedRune {
+            rune: Rune(26),
+            spacers: 1,
+          },
+          symbol: Some('%'),
+          terms: Some(Terms {
+            amount: Some(10),
+            cap: Some(100),
+            height: (Some(5), Some(21)),
+            offset: (None, None),
+          }),
+          ..default()
+        },
+        id: RuneId { block: 1, tx: 0 },
+        mintable: true,
+        projected_mint_out: Some(19),
+        recent_mints: vec![RuneMint {
+          height: 12,
+          txid: txid(1),
+        }],
+        remaining: 97,
+      },
+      "<h1>A•A Mints</h1>
+<dl>
+  <dt>rune</dt>
+  <dd><a href=/rune/A•A>A•A</a></dd>
+  <dt>amount</dt>
+  <dd>1\u{A0}%</dd>
+  <dt>mints</dt>
+  <dd>3</dd>
+  <dt>cap</dt>
+  <dd>100</dd>
+  <dt>remaining</dt>
+  <dd>97</dd>
+  <dt>mintable</dt>
+  <dd>true</dd>
+  <dt>start</dt>
+  <dd><a href=/block/5>5</a>, started</dd>
+  <dt>end</dt>
+  <dd><a href=/block/21>21</a>, in 8 blocks</dd>
+  <dt>projected mint-out</dt>
+  <dd><a href=/block/19>19</a></dd>
+  <dt>burned</dt>
+  <dd>0.5\u{A0}%</dd>
+</dl>
+<h2>Activity</h2>
+<table>
+  <tr>
+    <th>block</th>
+    <th>mints</th>
+    <th>burned</th>
+  </tr>
+  <tr>
+    <td><a href=/block/12>12</a></td>
+    <td>3</td>
+    <td>0\u{A0}%</td>
+  </tr>
+  <tr>
+    <td><a href=/block/10>10</a></td>
+    <td>0</td>
+    <td>0.5\u{A0}%</td>
+  </tr>
+</table>
+<h2>Recent Mints</h2>
+<ul class=monospace>
+  <li><a href=/tx/1{64}>1{64}</a> in <a href=/block/12>12</a></li>
+</ul>
+"
+    );
+  }
+
+  #[test]
+  fn unmintable() {
+    assert_regex_match!(
+      RuneMintsHtml {
+        activity: Vec::new(),
+        blocks_until_end: None,
+        blocks_until_start: None,
+        entry: RuneEntry {
+          spaced_rune: SpacedRune {
+            rune: Rune(0),
+            spacers: 0,
+          },
+          ..default()
+        },
+        id: RuneId { block: 1, tx: 0 },
+        mintable: false,
+        projected_mint_out: None,
+        recent_mints: Vec::new(),
+        remaining: 0,
+      },
+      "<h1>A Mints</h1>
+<dl>
+  <dt>rune</dt>
+  <dd><a href=/rune/A>A</a></dd>
+  <dt>mintable</dt>
+  <dd>false</dd>
+  <dt>burned</dt>
+  <dd>0\u{A0}¤</dd>
+</dl>
+<h2>Activity</h2>
+<p>No mints or burns.</p>
+<h2>Recent Mints</h2>
+<p>No mints.</p>
+"
+    );
+  }
+}
@@ -0,0 +1,118 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "RuneSearch")]
+pub struct RuneSearchHtml {
+  pub more: bool,
+  pub query: String,
+  pub results: Vec<RuneSearchResult>,
+  pub sort: RuneSort,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RuneSearchResult {
+  pub id: RuneId,
+  pub mints: u128,
+  pub number: u64,
+  pub outputs: u64,
+  pub spaced_rune: SpacedRune,
+  pub symbol: Option<char>,
+}
+
+#[derive(Debug, Default, PartialEq, Copy, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RuneSort {
+  Mints,
+  Number,
+  #[default]
+  Outputs,
+}
+
+impl RuneSort {
+  const ALL: [Self; 3] = [Self::Outputs, Self::Mints, Self::Number];
+
+  fn name(self) -> &'static str {
+    match self {
+      Self::Mints => "mints",
+      Self::Number => "number",
+      Self::Outputs => "outputs",
+    }
+  }
+}
+
+impl PageContent for RuneSearchHtml {
+  fn title(&self) -> String {
+    format!("Rune Search: {}", self.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RuneSearchHtml {
+        more: false,
+        query: "a•b".into(),
+        results: vec![RuneSearchResult {
+          id: RuneId { block: 1, tx: 0 },
+          mints: 3,
+          number: 0,
+          outputs: 2,
+          spaced_rune: SpacedRune {
+            rune: Rune(26),
+            spacers: 1
+          },
+          symbol: Some('%'),
+        }],
+        sort: RuneSort::Outputs,
+      },
+      "<h1>Rune Search</h1>
+<form action=/runes/search method=get>
+  <input .* name=q .* value='a•b'>
+  <input type=hidden name=sort value=outputs>
+</form>
+<div class=center>
+  sort by
+  outputs
+  <a href='/runes/search\\?q=a%E2%80%A2b&sort=mints'>mints</a>
+  <a href='/runes/search\\?q=a%E2%80%A2b&sort=number'>number</a>
+</div>
+<table>
+  <tr>
+    <th>rune</th>
+    <th>number</th>
+    <th>outputs</th>
+    <th>mints</th>
+  </tr>
+  <tr>
+    <td><a href=/rune/A•A>% A•A</a></td>
+    <td>0</td>
+    <td>2</td>
+    <td>3</td>
+  </tr>
+</table>
+"
+    );
+  }
+
+  #[test]
+  fn no_results() {
+    assert_regex_match!(
+      RuneSearchHtml {
+        more: false,
+        query: "Z".into(),
+        results: Vec::new(),
+        sort: RuneSort::Number,
+      },
+      "<h1>Rune Search</h1>
+<form .*>
+.*
+</form>
+<p>No runes match Z.</p>
+"
+    );
+  }
+}
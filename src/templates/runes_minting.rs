@@ -0,0 +1,98 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "RunesMinting")]
+pub struct RunesMintingHtml {
+  pub entries: Vec<MintingRune>,
+  pub more: bool,
+  pub next: Option<usize>,
+  pub prev: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MintingRune {
+  pub end: Option<u64>,
+  pub id: RuneId,
+  pub mints: u128,
+  pub recent_mints: u64,
+  pub remaining: u128,
+  pub spaced_rune: SpacedRune,
+  pub symbol: Option<char>,
+}
+
+impl PageContent for RunesMintingHtml {
+  fn title(&self) -> String {
+    "Runes Minting".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RunesMintingHtml {
+        entries: vec![
+          MintingRune {
+            end: Some(100),
+            id: RuneId { block: 1, tx: 0 },
+            mints: 10,
+            recent_mints: 4,
+            remaining: 90,
+            spaced_rune: SpacedRune {
+              rune: Rune(26),
+              spacers: 1,
+            },
+            symbol: Some('%'),
+          },
+          MintingRune {
+            end: None,
+            id: RuneId { block: 2, tx: 0 },
+            mints: 0,
+            recent_mints: 0,
+            remaining: 5,
+            spaced_rune: SpacedRune {
+              rune: Rune(0),
+              spacers: 0,
+            },
+            symbol: None,
+          },
+        ],
+        more: true,
+        next: Some(2),
+        prev: Some(0),
+      },
+      "<h1>Runes Minting</h1>
+<table>
+  <tr>
+    <th>rune</th>
+    <th>recent mints</th>
+    <th>mints</th>
+    <th>remaining</th>
+    <th>end</th>
+  </tr>
+  <tr>
+    <td><a href=/rune/A•A/mints>% A•A</a></td>
+    <td>4</td>
+    <td>10</td>
+    <td>90</td>
+    <td><a href=/block/100>100</a></td>
+  </tr>
+  <tr>
+    <td><a href=/rune/A/mints>¤ A</a></td>
+    <td>0</td>
+    <td>0</td>
+    <td>5</td>
+    <td>none</td>
+  </tr>
+</table>
+<div class=center>
+  <a class=prev href=/runes/minting/0>prev</a>
+  <a class=next href=/runes/minting/2>next</a>
+</div>
+"
+    );
+  }
+}
@@ -39,7 +39,7 @@ mod tests {
           <dt>period</dt><dd>0</dd>
           <dt>block</dt><dd><a href=/block/0>0</a></dd>
           <dt>offset</dt><dd>0</dd>
-          <dt>timestamp</dt><dd><time>1970-01-01 00:00:00 UTC</time></dd>
+          <dt>timestamp</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
           <dt>rarity</dt><dd><span class=mythic>mythic</span></dd>
           <dt>charms</dt>
           <dd>
@@ -77,7 +77,7 @@ mod tests {
           <dt>period</dt><dd>3437</dd>
           <dt>block</dt><dd><a href=/block/6929999>6929999</a></dd>
           <dt>offset</dt><dd>0</dd>
-          <dt>timestamp</dt><dd><time>1970-01-01 00:00:00 UTC</time></dd>
+          <dt>timestamp</dt><dd><time datetime=1970-01-01T00:00:00Z>1970-01-01 00:00:00 UTC</time></dd>
           <dt>rarity</dt><dd><span class=uncommon>uncommon</span></dd>
           <dt>charms</dt>
           <dd>
@@ -1,14 +1,215 @@
 use super::*;
 
-#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "Transaction")]
 pub struct TransactionHtml {
   pub chain: Chain,
   pub etching: Option<SpacedRune>,
   pub inscription_count: u32,
+  pub sat_flow: Option<SatFlow>,
+  #[schemars(with = "api::schema::Transaction")]
   pub transaction: Transaction,
+  #[schemars(with = "api::schema::Txid")]
   pub txid: Txid,
 }
 
+// Flow of sats from a transaction's inputs to its outputs. Sats are assigned
+// first-in-first-out, so the flow is split into segments wherever an input or
+// output ends. Sats past the end of the last output are lost to fees.
+// Inscriptions and runes are None once an output of the transaction has been
+// spent, since they are only known for unspent outputs.
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SatFlow {
+  pub fee: u64,
+  pub inputs: Vec<u64>,
+  pub inscriptions: Option<Vec<SatFlowInscription>>,
+  pub runes: Option<Vec<SatFlowRune>>,
+  pub segments: Vec<SatFlowSegment>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SatFlowSegment {
+  pub input: u32,
+  pub input_offset: u64,
+  pub output: Option<u32>,
+  pub output_offset: u64,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+  pub size: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SatFlowInscription {
+  pub genesis: bool,
+  pub id: InscriptionId,
+  pub input: u32,
+  pub satpoint: SatPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SatFlowRune {
+  pub amount: Pile,
+  pub output: u32,
+  pub rune: SpacedRune,
+}
+
+impl SatFlow {
+  /// Build the flow of a transaction from the values of its inputs, and the
+  /// values and sat ranges, if known, of its outputs.
+  pub(crate) fn new(
+    txid: Txid,
+    inputs: Vec<u64>,
+    outputs: Vec<(u64, Option<Vec<(u64, u64)>>)>,
+    inscriptions: Option<Vec<(SatPoint, InscriptionId)>>,
+    runes: Option<Vec<SatFlowRune>>,
+  ) -> Self {
+    let total = inputs.iter().sum::<u64>();
+
+    let mut segments = Vec::new();
+    let mut offset = 0;
+    let mut input = 0;
+    let mut input_offset = 0;
+    let mut output = 0;
+    let mut output_offset = 0;
+
+    while offset < total {
+      let input_remaining = inputs[input] - input_offset;
+
+      if input_remaining == 0 {
+        input += 1;
+        input_offset = 0;
+        continue;
+      }
+
+      let output_remaining = match outputs.get(output) {
+        Some((value, _sat_ranges)) => value - output_offset,
+        None => total - offset,
+      };
+
+      if output_remaining == 0 {
+        output += 1;
+        output_offset = 0;
+        continue;
+      }
+
+      let size = input_remaining.min(output_remaining);
+
+      segments.push(SatFlowSegment {
+        input: input.try_into().unwrap(),
+        input_offset,
+        output: (output < outputs.len()).then(|| output.try_into().unwrap()),
+        output_offset,
+        sat_ranges: outputs
+          .get(output)
+          .and_then(|(_value, sat_ranges)| sat_ranges.as_ref())
+          .map(|sat_ranges| Self::slice(sat_ranges, output_offset, size)),
+        size,
+      });
+
+      offset += size;
+      input_offset += size;
+      output_offset += size;
+    }
+
+    let output_starts = outputs
+      .iter()
+      .scan(0, |start, (value, _sat_ranges)| {
+        let output_start = *start;
+        *start += value;
+        Some(output_start)
+      })
+      .collect::<Vec<u64>>();
+
+    let inscriptions = inscriptions.map(|inscriptions| {
+      inscriptions
+        .into_iter()
+        .map(|(satpoint, id)| {
+          let offset =
+            output_starts[usize::try_from(satpoint.outpoint.vout).unwrap()] + satpoint.offset;
+
+          let input = inputs
+            .iter()
+            .scan(0, |end, value| {
+              *end += value;
+              Some(*end)
+            })
+            .position(|end| offset < end)
+            .unwrap_or_default();
+
+          SatFlowInscription {
+            genesis: id.txid == txid,
+            id,
+            input: input.try_into().unwrap(),
+            satpoint,
+          }
+        })
+        .collect()
+    });
+
+    Self {
+      fee: total.saturating_sub(outputs.iter().map(|(value, _sat_ranges)| value).sum()),
+      inputs,
+      inscriptions,
+      runes,
+      segments,
+    }
+  }
+
+  fn slice(sat_ranges: &[(u64, u64)], offset: u64, size: u64) -> Vec<(u64, u64)> {
+    let mut slice = Vec::new();
+    let mut skip = offset;
+    let mut remaining = size;
+
+    for (start, end) in sat_ranges.iter().copied() {
+      if remaining == 0 {
+        break;
+      }
+
+      let len = end - start;
+
+      if skip >= len {
+        skip -= len;
+        continue;
+      }
+
+      let take = (len - skip).min(remaining);
+      slice.push((start + skip, start + skip + take));
+      remaining -= take;
+      skip = 0;
+    }
+
+    slice
+  }
+
+  fn total(&self) -> u64 {
+    self.inputs.iter().sum()
+  }
+
+  // Vertical position of an offset in the flow diagram, as a percentage of
+  // its height.
+  fn y(&self, offset: u64) -> String {
+    format!("{:.3}", offset as f64 * 100.0 / self.total() as f64)
+  }
+
+  fn input_start(&self, input: u32) -> u64 {
+    self.inputs[..input.try_into().unwrap()].iter().sum()
+  }
+}
+
+impl TransactionHtml {
+  fn output_starts(&self) -> Vec<u64> {
+    self
+      .transaction
+      .output
+      .iter()
+      .scan(0, |start, output| {
+        let output_start = *start;
+        *start += output.value;
+        Some(output_start)
+      })
+      .collect()
+  }
+}
+
 impl PageContent for TransactionHtml {
   fn title(&self) -> String {
     format!("Transaction {}", self.txid)
@@ -49,6 +250,7 @@ mod tests {
         chain: Chain::Mainnet,
         etching: None,
         inscription_count: 0,
+        sat_flow: None,
         txid: transaction.txid(),
         transaction,
       }.to_string(),
@@ -87,4 +289,156 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn sat_flow() {
+    let txid = txid(1);
+
+    let satpoint = SatPoint {
+      outpoint: OutPoint { txid, vout: 1 },
+      offset: 1,
+    };
+
+    pretty_assert_eq!(
+      SatFlow::new(
+        txid,
+        vec![3, 0, 5],
+        vec![(4, Some(vec![(0, 3), (10, 11)])), (2, None)],
+        Some(vec![(satpoint, inscription_id(2))]),
+        Some(Vec::new()),
+      ),
+      SatFlow {
+        fee: 2,
+        inputs: vec![3, 0, 5],
+        inscriptions: Some(vec![SatFlowInscription {
+          genesis: false,
+          id: inscription_id(2),
+          input: 2,
+          satpoint,
+        }]),
+        runes: Some(Vec::new()),
+        segments: vec![
+          SatFlowSegment {
+            input: 0,
+            input_offset: 0,
+            output: Some(0),
+            output_offset: 0,
+            sat_ranges: Some(vec![(0, 3)]),
+            size: 3,
+          },
+          SatFlowSegment {
+            input: 2,
+            input_offset: 0,
+            output: Some(0),
+            output_offset: 3,
+            sat_ranges: Some(vec![(10, 11)]),
+            size: 1,
+          },
+          SatFlowSegment {
+            input: 2,
+            input_offset: 1,
+            output: Some(1),
+            output_offset: 0,
+            sat_ranges: None,
+            size: 2,
+          },
+          SatFlowSegment {
+            input: 2,
+            input_offset: 3,
+            output: None,
+            output_offset: 0,
+            sat_ranges: None,
+            size: 2,
+          },
+        ],
+      }
+    );
+  }
+
+  #[test]
+  fn sat_flow_slices_sat_ranges() {
+    assert_eq!(SatFlow::slice(&[(0, 10)], 2, 3), [(2, 5)]);
+    assert_eq!(
+      SatFlow::slice(&[(0, 2), (5, 8), (20, 30)], 1, 5),
+      [(1, 2), (5, 8), (20, 21)]
+    );
+    assert_eq!(SatFlow::slice(&[(0, 2), (5, 8)], 2, 3), [(5, 8)]);
+  }
+
+  #[test]
+  fn sat_flow_html() {
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        sequence: Default::default(),
+        previous_output: Default::default(),
+        script_sig: Default::default(),
+        witness: Default::default(),
+      }],
+      output: vec![TxOut {
+        value: 3,
+        script_pubkey: script::Builder::new().push_int(0).into_script(),
+      }],
+    };
+
+    let txid = transaction.txid();
+
+    assert_regex_match!(
+      TransactionHtml {
+        chain: Chain::Mainnet,
+        etching: None,
+        inscription_count: 0,
+        sat_flow: Some(SatFlow::new(
+          txid,
+          vec![4],
+          vec![(3, Some(vec![(100, 103)]))],
+          Some(Vec::new()),
+          Some(Vec::new()),
+        )),
+        txid,
+        transaction,
+      },
+      format!(
+        "
+        .*
+        <h2>Sat Flow</h2>
+        <dl>
+          <dt>fee</dt><dd>1</dd>
+        </dl>
+        <svg class=sat-flow viewBox='0 0 100 100' preserveAspectRatio=none>
+          <rect class=flow x=20 y=0.000 width=60 height=75.000></rect>
+          <rect class=fee x=20 y=75.000 width=60 height=25.000></rect>
+          <rect class=input x=0 y=0.000 width=20 height=100.000><title>input 0: 4</title></rect>
+          <rect class=output x=80 y=0.000 width=20 height=75.000><title>output 0: 3</title></rect>
+          <rect class=fee x=80 y=75.000 width=20 height=25.000><title>fee: 1</title></rect>
+        </svg>
+        <table>
+          <tr>
+            <th>input</th>
+            <th>output</th>
+            <th>size</th>
+            <th>sat ranges</th>
+          </tr>
+          <tr>
+            <td><a class=monospace href=/output/0{{64}}:4294967295>0</a></td>
+            <td><a class=monospace href=/output/{txid}:0>0</a></td>
+            <td>3</td>
+            <td>
+              <a class=monospace href=/range/100/103>100–103</a>
+            </td>
+          </tr>
+          <tr>
+            <td><a class=monospace href=/output/0{{64}}:4294967295>0</a></td>
+            <td class=fee>fee</td>
+            <td>1</td>
+            <td>
+            </td>
+          </tr>
+        </table>
+        "
+      )
+      .unindent()
+    );
+  }
 }
@@ -108,7 +108,7 @@ impl File {
     wallet: &Wallet,
     utxos: &BTreeMap<OutPoint, TxOut>,
     parent_value: Option<u64>,
-    compress: bool,
+    compress: &[Compression],
   ) -> Result<(
     Vec<Inscription>,
     Vec<(SatPoint, TxOut)>,
@@ -1,5 +1,5 @@
 for (let time of document.body.getElementsByTagName('time')) {
-  time.setAttribute('title', new Date(time.textContent));
+  time.setAttribute('title', new Date(time.dateTime || time.textContent));
 }
 
 let next = document.querySelector('a.next');
@@ -0,0 +1,65 @@
+const inscription = document.documentElement.dataset.inscription;
+
+const response = await fetch(`/content/${inscription}`);
+const text = await response.text();
+
+const delimiter = response.headers.get('content-type')?.startsWith('text/tab-separated-values') ? '\t' : ',';
+
+function parse(text, delimiter) {
+  const rows = [];
+  let row = [];
+  let field = '';
+  let quoted = false;
+
+  for (let i = 0; i < text.length; i++) {
+    const c = text[i];
+
+    if (quoted) {
+      if (c === '"' && text[i + 1] === '"') {
+        field += '"';
+        i++;
+      } else if (c === '"') {
+        quoted = false;
+      } else {
+        field += c;
+      }
+    } else if (c === '"' && field === '') {
+      quoted = true;
+    } else if (c === delimiter) {
+      row.push(field);
+      field = '';
+    } else if (c === '\n' || c === '\r') {
+      if (c === '\r' && text[i + 1] === '\n') {
+        i++;
+      }
+      row.push(field);
+      rows.push(row);
+      row = [];
+      field = '';
+    } else {
+      field += c;
+    }
+  }
+
+  if (field !== '' || row.length > 0) {
+    row.push(field);
+    rows.push(row);
+  }
+
+  return rows;
+}
+
+const rows = parse(text, delimiter);
+const table = document.createElement('table');
+
+for (const [i, row] of rows.entries()) {
+  const tr = document.createElement('tr');
+  for (const field of row) {
+    const cell = document.createElement(i === 0 ? 'th' : 'td');
+    cell.textContent = field;
+    tr.appendChild(cell);
+  }
+  table.appendChild(tr);
+}
+
+document.body.appendChild(table);
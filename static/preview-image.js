@@ -1,15 +1,16 @@
 function resize() {
   if (
-    body.clientWidth * window.devicePixelRatio < img.naturalWidth
-    || body.clientHeight * window.devicePixelRatio < img.naturalHeight
+    img.clientWidth * window.devicePixelRatio < img.naturalWidth
+    || img.clientHeight * window.devicePixelRatio < img.naturalHeight
   ) {
-    body.style.imageRendering = 'auto';
+    img.style.imageRendering = 'auto';
   } else {
-    body.removeAttribute('style');
+    img.removeAttribute('style');
   }
 }
 
-let body = document.body;
 let img = document.getElementsByTagName('img')[0];
 
-(new ResizeObserver(resize)).observe(body);
+img.addEventListener('load', resize);
+
+(new ResizeObserver(resize)).observe(img);
@@ -0,0 +1,45 @@
+const inscription = document.documentElement.dataset.inscription;
+
+const response = await fetch(`/content/${inscription}`);
+const text = await response.text();
+
+function span(className, text) {
+  const span = document.createElement('span');
+  span.className = className;
+  span.textContent = text;
+  return span;
+}
+
+function node(key, value, depth) {
+  const prefix = key === null ? [] : [span('key', JSON.stringify(key)), ': '];
+
+  if (value === null || typeof value !== 'object') {
+    const div = document.createElement('div');
+    div.append(...prefix, span(value === null ? 'null' : typeof value, JSON.stringify(value)));
+    return div;
+  }
+
+  const array = Array.isArray(value);
+  const entries = array ? value.map((value, i) => [null, value]) : Object.entries(value);
+
+  const details = document.createElement('details');
+  details.open = depth < 2;
+
+  const summary = document.createElement('summary');
+  summary.append(...prefix, array ? `[${entries.length}]` : `{${entries.length}}`);
+  details.appendChild(summary);
+
+  for (const [key, value] of entries) {
+    details.appendChild(node(key, value, depth + 1));
+  }
+
+  return details;
+}
+
+try {
+  document.body.appendChild(node(null, JSON.parse(text), 0));
+} catch {
+  const pre = document.createElement('pre');
+  pre.textContent = text;
+  document.body.appendChild(pre);
+}
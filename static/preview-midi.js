@@ -0,0 +1,195 @@
+const inscription = document.documentElement.dataset.inscription;
+
+const response = await fetch(`/content/${inscription}`);
+const data = new DataView(await response.arrayBuffer());
+
+// Parse a standard MIDI file into notes with start times and durations in
+// seconds, honoring tempo changes in any track.
+function parse(data) {
+  let position = 0;
+
+  const u8 = () => data.getUint8(position++);
+  const u16 = () => (position += 2, data.getUint16(position - 2));
+  const u32 = () => (position += 4, data.getUint32(position - 4));
+  const chunk = () => String.fromCharCode(u8(), u8(), u8(), u8());
+
+  const variable = () => {
+    let value = 0;
+    let byte;
+    do {
+      byte = u8();
+      value = (value << 7) | (byte & 0x7f);
+    } while (byte & 0x80);
+    return value;
+  };
+
+  if (chunk() !== 'MThd') {
+    throw new Error('not a MIDI file');
+  }
+
+  const headerLength = u32();
+  u16();
+  const trackCount = u16();
+  const division = u16();
+  position += headerLength - 6;
+
+  if (division & 0x8000) {
+    throw new Error('SMPTE time division is not supported');
+  }
+
+  const events = [];
+
+  for (let track = 0; track < trackCount && position < data.byteLength; track++) {
+    const type = chunk();
+    const end = u32() + position;
+
+    if (type !== 'MTrk') {
+      position = end;
+      continue;
+    }
+
+    let tick = 0;
+    let status = 0;
+
+    while (position < end) {
+      tick += variable();
+
+      let byte = u8();
+
+      if (byte === 0xff) {
+        const meta = u8();
+        const length = variable();
+        if (meta === 0x51) {
+          events.push({ tick, tempo: (u8() << 16) | (u8() << 8) | u8() });
+          position += length - 3;
+        } else {
+          position += length;
+        }
+        continue;
+      }
+
+      if (byte === 0xf0 || byte === 0xf7) {
+        position += variable();
+        continue;
+      }
+
+      if (byte & 0x80) {
+        status = byte;
+        byte = u8();
+      }
+
+      const kind = status & 0xf0;
+      const channel = status & 0x0f;
+
+      if (kind === 0xc0 || kind === 0xd0) {
+        continue;
+      }
+
+      const velocity = u8();
+
+      if (kind === 0x90 && velocity > 0) {
+        events.push({ tick, channel, note: byte, velocity, on: true });
+      } else if (kind === 0x80 || kind === 0x90) {
+        events.push({ tick, channel, note: byte, on: false });
+      }
+    }
+
+    position = end;
+  }
+
+  events.sort((a, b) => a.tick - b.tick);
+
+  const notes = [];
+  const playing = new Map();
+  let tempo = 500000;
+  let tick = 0;
+  let time = 0;
+
+  for (const event of events) {
+    time += (event.tick - tick) * tempo / division / 1e6;
+    tick = event.tick;
+
+    if (event.tempo !== undefined) {
+      tempo = event.tempo;
+      continue;
+    }
+
+    const key = `${event.channel}:${event.note}`;
+
+    if (event.on) {
+      playing.set(key, { note: event.note, velocity: event.velocity, start: time, channel: event.channel });
+    } else if (playing.has(key)) {
+      const note = playing.get(key);
+      note.duration = time - note.start;
+      notes.push(note);
+      playing.delete(key);
+    }
+  }
+
+  return notes;
+}
+
+const button = document.querySelector('button');
+
+let notes;
+
+try {
+  notes = parse(data);
+  button.disabled = false;
+} catch (error) {
+  button.textContent = error.message;
+}
+
+let context;
+
+button.addEventListener('click', async () => {
+  if (context) {
+    await context.close();
+    context = null;
+    button.textContent = '▶';
+    return;
+  }
+
+  context = new AudioContext();
+  button.textContent = '■';
+
+  const start = context.currentTime + 0.1;
+  let end = start;
+
+  for (const note of notes) {
+    // channel 10 is reserved for percussion, which can't be synthesized with
+    // a single oscillator
+    if (note.channel === 9) {
+      continue;
+    }
+
+    const oscillator = context.createOscillator();
+    const gain = context.createGain();
+
+    oscillator.type = 'triangle';
+    oscillator.frequency.value = 440 * Math.pow(2, (note.note - 69) / 12);
+
+    const noteStart = start + note.start;
+    const noteEnd = noteStart + Math.max(note.duration, 0.05);
+    const volume = 0.2 * note.velocity / 127;
+
+    gain.gain.setValueAtTime(0, noteStart);
+    gain.gain.linearRampToValueAtTime(volume, noteStart + 0.01);
+    gain.gain.setValueAtTime(volume, Math.max(noteEnd - 0.03, noteStart + 0.01));
+    gain.gain.linearRampToValueAtTime(0, noteEnd);
+
+    oscillator.connect(gain).connect(context.destination);
+    oscillator.start(noteStart);
+    oscillator.stop(noteEnd);
+
+    end = Math.max(end, noteEnd);
+  }
+
+  const playing = context;
+
+  setTimeout(() => {
+    if (context === playing) {
+      button.click();
+    }
+  }, (end - context.currentTime) * 1000 + 100);
+});
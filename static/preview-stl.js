@@ -0,0 +1,57 @@
+import * as THREE from 'https://cdn.jsdelivr.net/npm/three@0.160.0/+esm';
+import { OrbitControls } from 'https://cdn.jsdelivr.net/npm/three@0.160.0/examples/jsm/controls/OrbitControls.js/+esm';
+import { STLLoader } from 'https://cdn.jsdelivr.net/npm/three@0.160.0/examples/jsm/loaders/STLLoader.js/+esm';
+
+const canvas = document.querySelector('canvas');
+
+const renderer = new THREE.WebGLRenderer({ antialias: true, canvas });
+renderer.setPixelRatio(window.devicePixelRatio);
+
+const scene = new THREE.Scene();
+scene.background = new THREE.Color(0x131516);
+scene.add(new THREE.HemisphereLight(0xffffff, 0x444444, 2));
+
+const light = new THREE.DirectionalLight(0xffffff, 2);
+light.position.set(1, 1, 1);
+scene.add(light);
+
+const camera = new THREE.PerspectiveCamera(45, 1, 0.01, 1000);
+
+const controls = new OrbitControls(camera, canvas);
+controls.autoRotate = true;
+controls.enableDamping = true;
+
+const geometry = new STLLoader().parse(
+  await (await fetch(`/content/${canvas.dataset.inscription}`)).arrayBuffer()
+);
+
+// center the model and scale it to fit within a unit sphere
+geometry.computeBoundingSphere();
+geometry.translate(...geometry.boundingSphere.center.clone().negate().toArray());
+geometry.scale(1 / geometry.boundingSphere.radius, 1 / geometry.boundingSphere.radius, 1 / geometry.boundingSphere.radius);
+geometry.rotateX(-Math.PI / 2);
+
+scene.add(new THREE.Mesh(
+  geometry,
+  new THREE.MeshStandardMaterial({
+    color: geometry.hasColors ? 0xffffff : 0x98a3ad,
+    vertexColors: geometry.hasColors,
+  }),
+));
+
+camera.position.set(0, 0.75, 2.5);
+
+function resize() {
+  const { clientWidth: width, clientHeight: height } = canvas;
+  renderer.setSize(width, height, false);
+  camera.aspect = width / height;
+  camera.updateProjectionMatrix();
+}
+
+window.addEventListener('resize', resize);
+resize();
+
+renderer.setAnimationLoop(() => {
+  controls.update();
+  renderer.render(scene, camera);
+});
@@ -0,0 +1,86 @@
+use {super::*, ord::subcommand::find::Output};
+
+#[test]
+fn index_is_built_from_esplora() {
+  let core = mockcore::builder().esplora(true).build();
+
+  core.mine_blocks(1);
+
+  pretty_assert_eq!(
+    CommandBuilder::new(format!(
+      "--esplora-url {} --index-sats find {}",
+      core.esplora_url(),
+      50 * COIN_VALUE,
+    ))
+    .run_and_deserialize_output::<Output>(),
+    Output {
+      satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: core.tx(1, 0).into(),
+          vout: 0,
+        },
+        offset: 0,
+      },
+    }
+  );
+}
+
+#[test]
+fn esplora_on_wrong_chain_is_an_error() {
+  let core = mockcore::builder().esplora(true).build();
+
+  CommandBuilder::new(format!(
+    "--regtest --esplora-url {} index update",
+    core.esplora_url()
+  ))
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: Esplora server at `{}` is not on regtest\n",
+    core.esplora_url()
+  ))
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn server_serves_inscriptions_indexed_from_esplora() {
+  let core = mockcore::builder().esplora(true).build();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let esplora_url = core.esplora_url();
+
+  let ord = TestServer::spawn_with_args(&core, &["--esplora-url", &esplora_url]);
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    format!(
+      r".*<h1>Inscription 0</h1>.*<dt>location</dt>\s*<dd class=monospace>{reveal}:0:0</dd>.*"
+    ),
+  );
+
+  ord.assert_response_regex(
+    format!("/tx/{reveal}"),
+    format!(".*<h1>Transaction <span class=monospace>{reveal}</span></h1>.*"),
+  );
+
+  ord.assert_response_regex(
+    format!("/output/{reveal}:0"),
+    format!(
+      r".*<h1>Output <span class=monospace>{reveal}:0</span></h1>.*<dt>spent</dt><dd>false</dd>.*"
+    ),
+  );
+
+  ord.assert_response_regex("/block/1", ".*<h1>Block 1</h1>.*");
+
+  let hash = core.state().hashes[1];
+
+  ord.assert_response_regex(format!("/block/{hash}"), ".*<h1>Block 1</h1>.*");
+
+  assert_eq!(
+    ord.request("/r/blockinfo/1").status(),
+    StatusCode::NOT_FOUND
+  );
+}
@@ -90,3 +90,131 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn export_inscriptions_as_jsonl_with_selected_columns() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let jsonl = CommandBuilder::new(
+    "index export --format jsonl --columns id,number,height,content-type,content-length,charms,parents --output foo.jsonl",
+  )
+  .core(&core)
+  .run_and_extract_file("foo.jsonl");
+
+  let entries = jsonl
+    .lines()
+    .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+    .collect::<Vec<serde_json::Value>>();
+
+  pretty_assert_eq!(
+    entries,
+    vec![serde_json::json!({
+      "id": inscription,
+      "number": 0,
+      "height": 2,
+      "content_type": "text/plain;charset=utf-8",
+      "content_length": 3,
+      "charms": [],
+      "parents": [],
+    })]
+  );
+}
+
+#[test]
+fn export_inscriptions_as_csv_filtered_by_height() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  inscribe(&core, &ord);
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let csv = CommandBuilder::new(
+    "index export --format csv --columns id,height,sequence-number --from-height 3 --output foo.csv",
+  )
+  .core(&core)
+  .run_and_extract_file("foo.csv");
+
+  pretty_assert_eq!(
+    csv,
+    format!("id,height,sequence_number\n{inscription},4,1\n"),
+  );
+}
+
+#[test]
+fn export_inscriptions_as_tsv_with_header() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let tsv = CommandBuilder::new("index export --format tsv --columns id,height --output foo.tsv")
+    .core(&core)
+    .run_and_extract_file("foo.tsv");
+
+  pretty_assert_eq!(
+    tsv,
+    format!("# export at block height 3\nid\theight\n{inscription}\t2\n"),
+  );
+}
+
+#[test]
+fn export_runes_and_rune_balances() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etch = etch(&core, &ord, Rune(RUNE));
+
+  let runes = CommandBuilder::new(
+    "--regtest --index-runes index export --table runes --format jsonl --output runes.jsonl",
+  )
+  .core(&core)
+  .run_and_extract_file("runes.jsonl");
+
+  let rune = serde_json::from_str::<serde_json::Value>(runes.trim()).unwrap();
+
+  assert_eq!(rune["id"], "7:1");
+  assert_eq!(rune["rune"], "AAAAAAAAAAAAA");
+  assert_eq!(rune["etching"], etch.output.reveal.to_string());
+  assert_eq!(rune["premine"], 1000);
+  assert_eq!(rune["symbol"], "¢");
+  assert_eq!(rune["mintable"], false);
+
+  let balances = CommandBuilder::new(
+    "--regtest --index-runes index export --table rune-balances --format csv --output balances.csv",
+  )
+  .core(&core)
+  .run_and_extract_file("balances.csv");
+
+  assert_regex_match!(
+    balances,
+    format!(
+      "outpoint,id,rune,amount,divisibility\n{}:[0-9],7:1,AAAAAAAAAAAAA,1000,0\n",
+      etch.output.reveal
+    ),
+  );
+}
+
+#[test]
+fn export_columns_are_only_allowed_for_inscriptions() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  CommandBuilder::new(
+    "--regtest --index-runes index export --table runes --columns id --output runes.csv",
+  )
+  .core(&core)
+  .expected_exit_code(1)
+  .expected_stderr("error: columns can only be selected when exporting inscriptions\n")
+  .run_and_extract_stdout();
+}
@@ -456,6 +456,20 @@ fn get_transaction() {
       chain: Chain::Mainnet,
       etching: None,
       inscription_count: 0,
+      sat_flow: Some(api::SatFlow {
+        fee: 0,
+        inputs: vec![50 * COIN_VALUE],
+        inscriptions: Some(Vec::new()),
+        runes: Some(Vec::new()),
+        segments: vec![api::SatFlowSegment {
+          input: 0,
+          input_offset: 0,
+          output: Some(0),
+          output_offset: 0,
+          sat_ranges: None,
+          size: 50 * COIN_VALUE,
+        }],
+      }),
       transaction,
       txid,
     }
@@ -636,8 +650,10 @@ fn get_runes() {
         )
       ],
       more: false,
-      next: None,
       prev: None,
+      next: None,
+      next_cursor: None,
+      prev_cursor: None,
     }
   );
 }
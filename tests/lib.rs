@@ -57,11 +57,13 @@ mod test_server;
 mod balances;
 mod decode;
 mod epochs;
+mod esplora;
 mod find;
 mod index;
 mod info;
 mod json_api;
 mod list;
+mod openapi;
 mod parse;
 mod runes;
 mod server;
@@ -304,7 +306,7 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
     ));
 
     mint_definition.push("<dt>mints</dt>".into());
-    mint_definition.push("<dd>0</dd>".into());
+    mint_definition.push(format!("<dd><a href=/rune/{rune}/mints>0</a></dd>"));
     mint_definition.push("<dt>cap</dt>".into());
     mint_definition.push(format!("<dd>{}</dd>", terms.cap));
     mint_definition.push("<dt>remaining</dt>".into());
@@ -0,0 +1,392 @@
+use {
+  super::*,
+  serde_json::{json, Value},
+  std::collections::BTreeSet,
+};
+
+fn documented_operations(spec: &Value) -> BTreeSet<(String, String)> {
+  spec["paths"]
+    .as_object()
+    .unwrap()
+    .iter()
+    .flat_map(|(path, item)| {
+      item
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|method| (method.clone(), path.clone()))
+    })
+    .collect()
+}
+
+// Checks `value` against `schema`, which may refer to schemas in the
+// components of `spec`
+fn validate(spec: &Value, schema: &Value, value: &Value) -> Result<(), String> {
+  let mut schema = schema.clone();
+  schema["components"] = spec["components"].clone();
+
+  jsonschema::draft202012::validate(&schema, value).map_err(|err| err.to_string())
+}
+
+#[test]
+fn json_responses_match_openapi_document() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &[
+      "--regtest",
+      "--index-addresses",
+      "--index-runes",
+      "--index-sats",
+    ],
+    &["--enable-graphql"],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etched = batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        premine: "1000".parse().unwrap(),
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 1,
+        },
+        supply: "3000".parse().unwrap(),
+        symbol: '¢',
+        terms: Some(batch::Terms {
+          amount: "1000".parse().unwrap(),
+          cap: 2,
+          height: None,
+          offset: Some(batch::Range {
+            start: None,
+            end: Some(100),
+          }),
+        }),
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("parent.txt".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let parent = etched.output.inscriptions[0].id;
+
+  let child = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet inscribe --fee-rate 1 --parent {parent} --file child.txt --json-metadata metadata.json"
+  ))
+  .write("child.txt", "child")
+  .write("metadata.json", r#"{"foo": "bar"}"#)
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let child_height = core.height();
+  let child_address = child.inscriptions[0].destination.clone().assume_checked();
+  let child_reveal = child.reveal;
+  let child = child.inscriptions[0].id;
+
+  let send = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 500:{}",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  core.broadcast_tx(TransactionTemplate {
+    inputs: &[(core.height().try_into().unwrap(), 0, 0, Witness::new())],
+    op_return: Some(
+      Runestone {
+        mint: Some(etched.id),
+        ..default()
+      }
+      .encipher(),
+    ),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let cenotaph = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(core.height().try_into().unwrap(), 0, 0, Witness::new())],
+    // OP_RETURN OP_13 with a truncated varint
+    op_return: Some(vec![0x6a, 0x5d, 0x01, 0xff].into()),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let json = |path: &str| -> Value {
+    serde_json::from_str(&ord.json_request(path).text().unwrap()).unwrap()
+  };
+
+  let sat = json(&format!("/inscription/{parent}"))["sat"]
+    .as_u64()
+    .unwrap();
+
+  let rune_output = json("/runes/balances")
+    .as_object()
+    .unwrap()
+    .values()
+    .next()
+    .unwrap()
+    .as_object()
+    .unwrap()
+    .keys()
+    .next()
+    .unwrap()
+    .clone();
+
+  let reveal = etched.output.reveal;
+  let id = etched.id;
+
+  let mut examples = [
+    ("/address/{address}", format!("/address/{child_address}")),
+    (
+      "/address/{address}/{page}",
+      format!("/address/{child_address}/0"),
+    ),
+    ("/block/{query}", format!("/block/{child_height}")),
+    ("/blocks", "/blocks".into()),
+    ("/children/{inscription_id}", format!("/children/{parent}")),
+    (
+      "/children/{inscription_id}/{page}",
+      format!("/children/{parent}/0"),
+    ),
+    (
+      "/collection/{inscription_id}",
+      format!("/collection/{parent}"),
+    ),
+    (
+      "/collection/{inscription_id}/{page}",
+      format!("/collection/{parent}/0?trait.eyes=laser"),
+    ),
+    ("/collections", "/collections".into()),
+    ("/collections/{page}", "/collections/0".into()),
+    ("/decode/{txid}", format!("/decode/{reveal}")),
+    ("/decode/{txid}", format!("/decode/{}", send.txid)),
+    ("/decode/{txid}", format!("/decode/{cenotaph}")),
+    (
+      "/inscription/{inscription_query}",
+      format!("/inscription/{child}"),
+    ),
+    (
+      "/inscription/{inscription_query}/{child}",
+      format!("/inscription/{parent}/0"),
+    ),
+    ("/inscriptions", "/inscriptions?limit=1".into()),
+    (
+      "/inscriptions/block/{height}",
+      format!("/inscriptions/block/{child_height}"),
+    ),
+    (
+      "/inscriptions/block/{height}/{page}",
+      format!("/inscriptions/block/{child_height}/0"),
+    ),
+    ("/inscriptions/{page}", "/inscriptions/0".into()),
+    ("/output/{output}", format!("/output/{rune_output}")),
+    (
+      "/r/address/{address}/inscriptions",
+      format!("/r/address/{child_address}/inscriptions"),
+    ),
+    (
+      "/r/address/{address}/inscriptions/{page}",
+      format!("/r/address/{child_address}/inscriptions/0"),
+    ),
+    ("/r/blockhash", "/r/blockhash".into()),
+    ("/r/blockhash/{height}", "/r/blockhash/1".into()),
+    ("/r/blockheight", "/r/blockheight".into()),
+    ("/r/blockinfo/{query}", "/r/blockinfo/1".into()),
+    ("/r/blocktime", "/r/blocktime".into()),
+    (
+      "/r/children/{inscription_id}",
+      format!("/r/children/{parent}"),
+    ),
+    (
+      "/r/children/{inscription_id}/inscriptions",
+      format!("/r/children/{parent}/inscriptions"),
+    ),
+    (
+      "/r/children/{inscription_id}/inscriptions/{page}",
+      format!("/r/children/{parent}/inscriptions/0"),
+    ),
+    (
+      "/r/children/{inscription_id}/{page}",
+      format!("/r/children/{parent}/0"),
+    ),
+    (
+      "/r/inscription/{inscription_id}",
+      format!("/r/inscription/{child}"),
+    ),
+    (
+      "/r/metadata/{inscription_id}",
+      format!("/r/metadata/{child}"),
+    ),
+    ("/r/parents/{inscription_id}", format!("/r/parents/{child}")),
+    (
+      "/r/parents/{inscription_id}/{page}",
+      format!("/r/parents/{child}/0"),
+    ),
+    ("/r/rune/{rune}", format!("/r/rune/{id}")),
+    ("/r/sat/{sat_number}", format!("/r/sat/{sat}")),
+    (
+      "/r/sat/{sat_number}/at/{index}",
+      format!("/r/sat/{sat}/at/-1"),
+    ),
+    ("/r/sat/{sat_number}/{page}", format!("/r/sat/{sat}/0")),
+    ("/r/utxo/{outpoint}", format!("/r/utxo/{rune_output}")),
+    ("/rune/{rune}", format!("/rune/{id}")),
+    ("/rune/{rune}/mints", format!("/rune/{id}/mints")),
+    ("/runes", "/runes".into()),
+    ("/runes/balances", "/runes/balances".into()),
+    ("/runes/minting", "/runes/minting".into()),
+    ("/runes/minting/{page}", "/runes/minting/0".into()),
+    ("/runes/search", "/runes/search?q=a".into()),
+    ("/runes/{page}", "/runes/0".into()),
+    ("/sat/{sat}", format!("/sat/{sat}")),
+    ("/status", "/status".into()),
+    ("/tx/{txid}", format!("/tx/{reveal}")),
+    ("/tx/{txid}", format!("/tx/{child_reveal}")),
+    ("/tx/{txid}", format!("/tx/{}", send.txid)),
+  ]
+  .into_iter()
+  .map(|(path, url)| ("get", path, url, None))
+  .collect::<Vec<_>>();
+
+  examples.push((
+    "post",
+    "/inscriptions",
+    "/inscriptions".into(),
+    Some(json!([parent, child])),
+  ));
+  for (path, body) in [
+    ("/addresses", json!([child_address, "foo"])),
+    (
+      "/decode",
+      json!(bitcoin::consensus::encode::serialize_hex(
+        &core.tx_by_id(send.txid)
+      )),
+    ),
+    ("/blocks", json!([child_height, "foo"])),
+    ("/inscriptions/numbers", json!([0, 1_000_000])),
+    ("/runes", json!([id, "FOO"])),
+    ("/sats", json!([sat, "foo!"])),
+    ("/txs", json!([reveal, "foo"])),
+  ] {
+    examples.push(("post", path, path.into(), Some(body)));
+  }
+
+  examples.push((
+    "post",
+    "/graphql",
+    "/graphql".into(),
+    Some(json!({
+      "query": "query Sat($query: String!) { sat(query: $query) { name inscriptions { id } } }",
+      "operationName": "Sat",
+      "variables": { "query": sat.to_string() },
+    })),
+  ));
+  examples.push((
+    "post",
+    "/outputs",
+    "/outputs".into(),
+    Some(json!([rune_output])),
+  ));
+
+  let spec = json("/openapi.json");
+
+  assert_eq!(spec["info"]["version"], env!("CARGO_PKG_VERSION"));
+
+  pretty_assert_eq!(
+    examples
+      .iter()
+      .map(|(method, path, _, _)| (method.to_string(), path.to_string()))
+      .collect::<BTreeSet<(String, String)>>(),
+    documented_operations(&spec),
+    "every documented operation must be exercised",
+  );
+
+  let client = reqwest::blocking::Client::new();
+
+  for (method, path, url, body) in examples {
+    ord.sync_server();
+
+    let request = match body {
+      Some(body) => {
+        if let Err(err) = validate(
+          &spec,
+          &spec["paths"][path][method]["requestBody"]["content"]["application/json"]["schema"],
+          &body,
+        ) {
+          panic!("{method} {url} request does not match OpenAPI document: {err}");
+        }
+
+        client.post(ord.url().join(&url).unwrap()).json(&body)
+      }
+      None => client.get(ord.url().join(&url).unwrap()),
+    };
+
+    let response = request
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    let status = response.status();
+
+    let content_type = response
+      .headers()
+      .get(reqwest::header::CONTENT_TYPE)
+      .unwrap()
+      .to_str()
+      .unwrap()
+      .to_string();
+
+    let text = response.text().unwrap();
+
+    assert_eq!(status, StatusCode::OK, "{method} {url}: {text}");
+
+    let (documented_content_type, media_type) = spec["paths"][path][method]["responses"]["200"]
+      ["content"]
+      .as_object()
+      .unwrap()
+      .iter()
+      .next()
+      .unwrap();
+
+    assert!(
+      content_type.starts_with(documented_content_type.as_str()),
+      "{method} {url}: expected {documented_content_type} but got {content_type}",
+    );
+
+    let value = if documented_content_type == "application/json" {
+      serde_json::from_str(&text).unwrap()
+    } else {
+      Value::String(text)
+    };
+
+    if let Err(err) = validate(&spec, &media_type["schema"], &value) {
+      panic!("{method} {url} response does not match OpenAPI document: {err}");
+    }
+  }
+}
+
+#[test]
+fn openapi_document_is_not_served_when_json_api_is_disabled() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &["--disable-json-api"]);
+
+  assert_eq!(ord.request("/openapi.json").status(), StatusCode::NOT_FOUND);
+}
@@ -262,7 +262,7 @@ fn inscription_page() {
   <dt>content type</dt>
   <dd>text/plain;charset=utf-8</dd>
   <dt>timestamp</dt>
-  <dd><time>1970-01-01 00:00:02 UTC</time></dd>
+  <dd><time datetime=1970-01-01T00:00:02Z>1970-01-01 00:00:02 UTC</time></dd>
   <dt>height</dt>
   <dd><a href=/block/2>2</a></dd>
   <dt>fee</dt>
@@ -588,7 +588,7 @@ fn expected_sat_time_is_rounded() {
 
   TestServer::spawn_with_args(&core, &[]).assert_response_regex(
     "/sat/2099999997689999",
-    r".*<dt>timestamp</dt><dd><time>.* \d+:\d+:\d+ UTC</time> \(expected\)</dd>.*",
+    r".*<dt>timestamp</dt><dd><time datetime=.*>.* \d+:\d+:\d+ UTC</time> \(expected\)</dd>.*",
   );
 }
 
@@ -887,3 +887,55 @@ fn ctrl_c() {
     thread::sleep(Duration::from_millis(50));
   }
 }
+
+#[test]
+fn follower_serves_snapshots_published_by_leader() {
+  let core = mockcore::spawn();
+  let tempdir = TempDir::new().unwrap();
+  let snapshots = tempdir.path().to_str().unwrap();
+
+  let leader = TestServer::spawn_with_server_args(
+    &core,
+    &[],
+    &["--publish", snapshots, "--publish-interval", "0s"],
+  );
+
+  core.mine_blocks(2);
+
+  leader.sync_server();
+
+  let follower = TestServer::spawn_with_server_args(&core, &[], &["--follow", snapshots]);
+
+  let wait_for_height = |height: u64| {
+    for attempt in 0.. {
+      let response = reqwest::blocking::get(follower.url().join("/blockheight").unwrap()).unwrap();
+
+      if response.status() == StatusCode::OK && response.text().unwrap() == height.to_string() {
+        break;
+      }
+
+      if attempt == 200 {
+        panic!("follower did not reach height {height}");
+      }
+
+      thread::sleep(Duration::from_millis(50));
+    }
+  };
+
+  wait_for_height(2);
+
+  core.mine_blocks(1);
+
+  leader.sync_server();
+
+  wait_for_height(3);
+
+  assert_eq!(
+    fs::read_dir(tempdir.path())
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+      .filter(|name| name.ends_with(".redb"))
+      .count(),
+    2
+  );
+}
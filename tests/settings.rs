@@ -6,6 +6,7 @@ fn default() {
     .integration_test(false)
     .stdout_regex(
       r#"\{
+  "access": null,
   "bitcoin_data_dir": ".*(Bitcoin|bitcoin)",
   "bitcoin_rpc_limit": 12,
   "bitcoin_rpc_password": null,
@@ -17,6 +18,7 @@ fn default() {
   "config_dir": null,
   "cookie_file": ".*\.cookie",
   "data_dir": ".*",
+  "esplora_url": null,
   "first_inscription_height": 767430,
   "height_limit": null,
   "hidden": \[\],
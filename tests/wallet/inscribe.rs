@@ -1150,6 +1150,81 @@ fn server_can_decompress_brotli() {
   assert_eq!(response.bytes().unwrap().deref(), [0; 350_000]);
 }
 
+#[test]
+fn server_can_transcode_content_encodings() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let content = "foo".repeat(1000);
+
+  let Batch { inscriptions, .. } =
+    CommandBuilder::new("wallet inscribe --compress=gzip --file foo.txt --fee-rate 1".to_string())
+      .write("foo.txt", &content)
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output();
+
+  let inscription = inscriptions[0].id;
+
+  core.mine_blocks(1);
+
+  let get = |server: &TestServer, accept_encoding: Option<&str>| {
+    let mut request = reqwest::blocking::Client::builder()
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(
+        server
+          .url()
+          .join(format!("/content/{inscription}").as_ref())
+          .unwrap(),
+      );
+
+    if let Some(accept_encoding) = accept_encoding {
+      request = request.header(reqwest::header::ACCEPT_ENCODING, accept_encoding);
+    }
+
+    request.send().unwrap()
+  };
+
+  ord.sync_server();
+
+  let response = get(&ord, Some("gzip"));
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.headers()["content-encoding"], "gzip");
+  assert_eq!(
+    std::io::read_to_string(flate2::read::GzDecoder::new(
+      response.bytes().unwrap().deref()
+    ))
+    .unwrap(),
+    content,
+  );
+
+  assert_eq!(get(&ord, Some("zstd")).status(), StatusCode::NOT_ACCEPTABLE);
+
+  let decompressing = TestServer::spawn_with_server_args(&core, &[], &["--decompress"]);
+
+  decompressing.sync_server();
+
+  let response = get(&decompressing, Some("zstd"));
+  assert_eq!(response.status(), StatusCode::OK);
+  assert_eq!(response.headers()["content-encoding"], "zstd");
+  assert_eq!(
+    zstd::decode_all(response.bytes().unwrap().deref()).unwrap(),
+    content.as_bytes(),
+  );
+
+  let response = get(&decompressing, None);
+  assert_eq!(response.status(), StatusCode::OK);
+  assert!(!response.headers().contains_key("content-encoding"));
+  assert_eq!(response.text().unwrap(), content);
+}
+
 #[test]
 fn file_inscribe_with_delegate_inscription() {
   let core = mockcore::spawn();